@@ -0,0 +1,2 @@
+ALTER TABLE encounters
+DROP COLUMN phases;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_training_dummy_runs_started;
+DROP TABLE IF EXISTS training_dummy_runs;
@@ -0,0 +1,18 @@
+-- Completed training dummy segments, kept when their encounter is pruned
+CREATE TABLE IF NOT EXISTS training_dummy_runs (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  encounter_id INTEGER,
+  monster_id INTEGER NOT NULL,
+  segment_secs INTEGER NOT NULL,
+  stop_after_damage_secs INTEGER,
+  started_at_ms INTEGER NOT NULL,
+  duration_ms INTEGER NOT NULL,
+  active_ms INTEGER NOT NULL,
+  total_dmg INTEGER NOT NULL,
+  player_id INTEGER NOT NULL,
+  class_id INTEGER NOT NULL,
+  class_spec INTEGER NOT NULL,
+  ability_score INTEGER NOT NULL,
+  FOREIGN KEY(encounter_id) REFERENCES encounters(id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_training_dummy_runs_started ON training_dummy_runs(started_at_ms);
@@ -0,0 +1,109 @@
+use resonance_logs_lib::headless::{self, NameLocale, ReplayInput, ReportFormat};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: resonance-logs-cli [OPTIONS] <CAPTURE>
+
+Replays a pcap/pcapng capture or a recorded .rlsession file and prints a summary of every
+encounter in it. Replays run as fast as possible; timings come from the capture timestamps.
+
+Options:
+  --format <table|json|csv>  Output format (default: table)
+  --locale <zh-CN|en-US>     Language of scene and monster names (default: zh-CN). English
+                             names fall back to Chinese where missing.
+  --session                  Read the input as a session file regardless of its extension
+  --pcap                     Read the input as a pcap/pcapng file regardless of its extension
+  -h, --help                 Show this help
+
+Logs go to stderr; set RES_LOG or RUST_LOG to change the filter (default: warn).";
+
+struct Args {
+    input: ReplayInput,
+    format: ReportFormat,
+    locale: NameLocale,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut format = ReportFormat::Table;
+    let mut locale = NameLocale::ZhCn;
+    let mut forced_kind: Option<fn(PathBuf) -> ReplayInput> = None;
+    let mut path = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--session" => forced_kind = Some(ReplayInput::Session),
+            "--pcap" => forced_kind = Some(ReplayInput::Pcap),
+            "--format" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                format = ReportFormat::parse(&value)
+                    .ok_or_else(|| format!("unknown format '{value}'"))?;
+            }
+            "--locale" => {
+                let value = args.next().ok_or("--locale needs a value")?;
+                locale =
+                    NameLocale::parse(&value).ok_or_else(|| format!("unknown locale '{value}'"))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ if path.is_some() => return Err("only one capture file can be given".to_string()),
+            _ => path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let path = path.ok_or("missing capture file")?;
+    let input = match forced_kind {
+        Some(kind) => kind(path),
+        None => ReplayInput::from_path(path),
+    };
+    Ok(Some(Args {
+        input,
+        format,
+        locale,
+    }))
+}
+
+fn init_logging() {
+    let filter = tracing_subscriber::EnvFilter::try_from_env("RES_LOG")
+        .or_else(|_| tracing_subscriber::EnvFilter::try_from_default_env())
+        // There is no database in headless runs; don't report every skipped write.
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn,app::db=off"));
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .try_init();
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    init_logging();
+    headless::set_name_locale(args.locale);
+
+    let reports = match headless::replay(&args.input) {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match headless::render_reports(&reports, args.format) {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -3,9 +3,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::database::PlayerNameEntry;
 use crate::database::db_exec;
+use crate::database::models as m;
 use crate::database::schema as sch;
 use crate::live::commands_models as lc;
 use crate::live::opcodes_models::class;
+use crate::live::phases::EncounterPhase;
+use crate::live::training_dummy::{
+    TrainingDummyRun, TrainingDummyRunFilter, TrainingDummyRunStats, summarize_training_runs,
+};
 use blueprotobuf_lib::blueprotobuf::EEntityType;
 
 /// A summary of a player in an encounter.
@@ -50,6 +55,8 @@ pub struct EncounterSummaryDto {
     pub remote_encounter_id: Option<i64>,
     /// Whether the encounter is favorited.
     pub is_favorite: bool,
+    /// Phase boundaries of the encounter. Only filled in by `get_encounter_by_id`.
+    pub phases: Vec<EncounterPhase>,
 }
 
 /// The result of a query for recent encounters.
@@ -435,6 +442,7 @@ pub fn get_recent_encounters_filtered(
                 players: player_entries,
                 remote_encounter_id: remote_id,
                 is_favorite: is_fav != 0,
+                phases: Vec::new(),
             });
         }
 
@@ -571,6 +579,7 @@ pub fn get_encounter_by_id(encounter_id: i32) -> Result<EncounterSummaryDto, Str
         i32,
         Option<String>,
         Option<String>,
+        Option<String>,
     ) = with_db(move |conn| {
         e::encounters
             .filter(e::id.eq(encounter_id))
@@ -589,6 +598,7 @@ pub fn get_encounter_by_id(encounter_id: i32) -> Result<EncounterSummaryDto, Str
                 e::is_favorite,
                 e::boss_monster_ids,
                 e::player_names,
+                e::phases,
             ))
             .first(conn)
             .map_err(|er| er.to_string())
@@ -607,6 +617,11 @@ pub fn get_encounter_by_id(encounter_id: i32) -> Result<EncounterSummaryDto, Str
         })
         .collect();
     let player_entries = parse_player_entries(&row.13);
+    let phases: Vec<EncounterPhase> = row
+        .14
+        .as_ref()
+        .and_then(|j| serde_json::from_str(j).ok())
+        .unwrap_or_default();
 
     Ok(EncounterSummaryDto {
         id: row.0,
@@ -623,14 +638,34 @@ pub fn get_encounter_by_id(encounter_id: i32) -> Result<EncounterSummaryDto, Str
         players: player_entries,
         remote_encounter_id: row.10,
         is_favorite: row.11 != 0,
+        phases,
     })
 }
 
+/// Start and end of an encounter in unix milliseconds. An unfinished encounter ends at its start.
+fn encounter_window(encounter_id: i32) -> Result<(u128, u128), String> {
+    use sch::encounters::dsl as e;
+
+    let (started_at_ms, ended_at_ms): (i64, Option<i64>) = with_db(move |conn| {
+        e::encounters
+            .filter(e::id.eq(encounter_id))
+            .select((e::started_at_ms, e::ended_at_ms))
+            .first(conn)
+            .map_err(|er| er.to_string())
+    })?;
+    let window_start_ms = u128::try_from(started_at_ms).unwrap_or_default();
+    let window_end_ms = ended_at_ms
+        .and_then(|ms| u128::try_from(ms).ok())
+        .unwrap_or(window_start_ms);
+    Ok((window_start_ms, window_end_ms))
+}
+
 /// Gets raw actor entities for a historical encounter.
 #[tauri::command]
 #[specta::specta]
 pub fn get_encounter_entities_raw(encounter_id: i32) -> Result<Vec<lc::HistoryEntityData>, String> {
     let entities = crate::database::load_encounter_data(encounter_id)?;
+    let (window_start_ms, window_end_ms) = encounter_window(encounter_id)?;
     let mut rows = Vec::new();
     for (&uid, entity) in &entities {
         if entity.entity_type != EEntityType::EntChar {
@@ -674,12 +709,279 @@ pub fn get_encounter_entities_raw(encounter_id: i32) -> Result<Vec<lc::HistoryEn
             ),
             heal_per_target: lc::build_per_target_stats(&entity.skill_heal_to_target, None),
             deaths: entity.deaths.clone(),
+            life: entity.life_timeline.summary(window_start_ms, window_end_ms),
+            phases: lc::to_raw_phase_stats(&entity.phase_stats),
+            buff_uptime: entity.buff_uptime.clone(),
+            summon_damage: lc::to_raw_combat_stats(&entity.summon_damage),
         });
     }
     rows.sort_by_key(|row| row.uid);
     Ok(rows)
 }
 
+/// Gets debuff uptime and applications on the bosses of a historical encounter.
+///
+/// # Arguments
+///
+/// * `encounter_id` - The ID of the encounter.
+///
+/// # Returns
+///
+/// * `Result<Vec<lc::BossDebuffSummary>, String>` - One entry per boss with monitored debuffs.
+#[tauri::command]
+#[specta::specta]
+pub fn get_encounter_boss_debuffs(encounter_id: i32) -> Result<Vec<lc::BossDebuffSummary>, String> {
+    let entities = crate::database::load_encounter_data(encounter_id)?;
+    let mut bosses: Vec<lc::BossDebuffSummary> = entities
+        .into_iter()
+        .filter(|(_, entity)| {
+            !entity.debuff_uptime.is_empty() || !entity.debuff_applications.is_empty()
+        })
+        .map(|(uid, entity)| lc::BossDebuffSummary {
+            uid,
+            monster_id: entity.monster_type_id,
+            name: entity.name,
+            debuff_uptime: entity.debuff_uptime,
+            applications: entity.debuff_applications,
+        })
+        .collect();
+    bosses.sort_by_key(|boss| boss.uid);
+    Ok(bosses)
+}
+
+/// Gets the top-threat holder changes on the bosses of a historical encounter.
+///
+/// # Arguments
+///
+/// * `encounter_id` - The ID of the encounter.
+///
+/// # Returns
+///
+/// * `Result<Vec<lc::BossAggroSummary>, String>` - One entry per boss with a recorded holder.
+#[tauri::command]
+#[specta::specta]
+pub fn get_encounter_boss_aggro(encounter_id: i32) -> Result<Vec<lc::BossAggroSummary>, String> {
+    let entities = crate::database::load_encounter_data(encounter_id)?;
+    let (window_start_ms, window_end_ms) = encounter_window(encounter_id)?;
+    let mut bosses: Vec<lc::BossAggroSummary> = entities
+        .iter()
+        .filter(|(_, entity)| !entity.aggro.changes.is_empty())
+        .map(|(&uid, entity)| {
+            let mut holds = entity.aggro.holds(window_start_ms, window_end_ms);
+            for hold in &mut holds {
+                hold.name = entities
+                    .get(&hold.uid)
+                    .map(|holder| holder.name.clone())
+                    .unwrap_or_default();
+            }
+            lc::BossAggroSummary {
+                uid,
+                monster_id: entity.monster_type_id,
+                name: entity.name.clone(),
+                changes: entity.aggro.changes.clone(),
+                holds,
+            }
+        })
+        .collect();
+    bosses.sort_by_key(|boss| boss.uid);
+    Ok(bosses)
+}
+
+/// Gets the local player's skill rotation in a historical encounter.
+///
+/// # Arguments
+///
+/// * `encounter_id` - The ID of the encounter.
+///
+/// # Returns
+///
+/// * `Result<Option<lc::EncounterRotation>, String>` - The rotation, if any casts were recorded.
+#[tauri::command]
+#[specta::specta]
+pub fn get_encounter_rotation(encounter_id: i32) -> Result<Option<lc::EncounterRotation>, String> {
+    use sch::encounters::dsl as e;
+
+    let entities = crate::database::load_encounter_data(encounter_id)?;
+    let Some((uid, entity)) = entities
+        .into_iter()
+        .find(|(_, entity)| !entity.rotation.is_empty())
+    else {
+        return Ok(None);
+    };
+    let started_at_ms: i64 = with_db(move |conn| {
+        e::encounters
+            .filter(e::id.eq(encounter_id))
+            .select(e::started_at_ms)
+            .first(conn)
+            .map_err(|er| er.to_string())
+    })?;
+    Ok(Some(lc::EncounterRotation {
+        uid,
+        name: entity.name,
+        start_ms: u64::try_from(started_at_ms).unwrap_or_default(),
+        truncated: entity.rotation_truncated,
+        entries: entity.rotation,
+    }))
+}
+
+/// Gets per-second damage, healing and damage taken for entities of a historical encounter.
+///
+/// # Arguments
+///
+/// * `encounter_id` - The ID of the encounter.
+/// * `entity_uids` - Entities to return; empty returns every player with combat.
+///
+/// # Returns
+///
+/// * `Result<lc::EncounterTimeSeries, String>` - Series aligned to the encounter start.
+#[tauri::command]
+#[specta::specta]
+pub fn get_encounter_time_series(
+    encounter_id: i32,
+    entity_uids: Vec<i64>,
+) -> Result<lc::EncounterTimeSeries, String> {
+    use crate::live::time_series::{SeriesKind, TIME_SERIES_BUCKET_MS, bucket_of};
+    use sch::encounters::dsl as e;
+
+    let entities = crate::database::load_encounter_data(encounter_id)?;
+    let (started_at_ms, duration): (i64, f64) = with_db(move |conn| {
+        e::encounters
+            .filter(e::id.eq(encounter_id))
+            .select((e::started_at_ms, e::duration))
+            .first(conn)
+            .map_err(|er| er.to_string())
+    })?;
+    let start_ms = u128::try_from(started_at_ms).unwrap_or_default();
+    let end_ms = start_ms + (duration.max(0.0) * 1000.0) as u128;
+    let first_bucket = bucket_of(start_ms);
+    let bucket_count = (bucket_of(end_ms) - first_bucket + 1) as usize;
+
+    let mut series: Vec<lc::EntityTimeSeries> = entities
+        .iter()
+        .filter(|&(uid, entity)| {
+            if entity_uids.is_empty() {
+                entity.entity_type == EEntityType::EntChar && !entity.time_series.is_empty()
+            } else {
+                entity_uids.contains(uid)
+            }
+        })
+        .map(|(&uid, entity)| lc::EntityTimeSeries {
+            uid,
+            damage: entity
+                .time_series
+                .window(SeriesKind::Damage, first_bucket, bucket_count),
+            healing: entity
+                .time_series
+                .window(SeriesKind::Healing, first_bucket, bucket_count),
+            taken: entity
+                .time_series
+                .window(SeriesKind::Taken, first_bucket, bucket_count),
+        })
+        .collect();
+    series.sort_by_key(|entity| entity.uid);
+
+    Ok(lc::EncounterTimeSeries {
+        bucket_ms: TIME_SERIES_BUCKET_MS as u32,
+        start_ms: first_bucket * TIME_SERIES_BUCKET_MS as u64,
+        entities: series,
+    })
+}
+
+/// Loads saved training dummy runs matching `filter`, oldest first.
+fn load_training_dummy_runs(
+    filter: TrainingDummyRunFilter,
+) -> Result<Vec<TrainingDummyRun>, String> {
+    use sch::training_dummy_runs::dsl as tr;
+
+    let mut query = tr::training_dummy_runs.into_boxed();
+    if let Some(monster_id) = filter.monster_id {
+        query = query.filter(tr::monster_id.eq(monster_id));
+    }
+    if let Some(class_spec) = filter.class_spec {
+        query = query.filter(tr::class_spec.eq(class_spec));
+    }
+    if let Some(segment_secs) = filter.segment_secs {
+        // Segments are capped well below i32::MAX, so a larger value matches nothing.
+        let Ok(segment_secs) = i32::try_from(segment_secs) else {
+            return Ok(Vec::new());
+        };
+        query = query.filter(tr::segment_secs.eq(segment_secs));
+    }
+    if let Some(from_ms) = filter.from_ms {
+        query = query.filter(tr::started_at_ms.ge(from_ms));
+    }
+    if let Some(to_ms) = filter.to_ms {
+        query = query.filter(tr::started_at_ms.le(to_ms));
+    }
+
+    let rows: Vec<m::TrainingDummyRunRow> = with_db(|conn| {
+        query
+            .order((tr::started_at_ms.asc(), tr::id.asc()))
+            .load(conn)
+            .map_err(|er| er.to_string())
+    })?;
+    Ok(rows
+        .into_iter()
+        .map(|row| TrainingDummyRun {
+            id: row.id,
+            encounter_id: row.encounter_id,
+            monster_id: row.monster_id,
+            segment_secs: u32::try_from(row.segment_secs).unwrap_or_default(),
+            stop_after_damage_secs: row
+                .stop_after_damage_secs
+                .and_then(|secs| u32::try_from(secs).ok()),
+            started_at_ms: row.started_at_ms,
+            duration_ms: row.duration_ms,
+            active_ms: row.active_ms,
+            total_damage: row.total_dmg,
+            dps: if row.duration_ms > 0 {
+                row.total_dmg as f64 * 1000.0 / row.duration_ms as f64
+            } else {
+                0.0
+            },
+            player_uid: row.player_id,
+            class_id: row.class_id,
+            class_spec: row.class_spec,
+            ability_score: row.ability_score,
+        })
+        .collect())
+}
+
+/// Gets saved training dummy runs.
+///
+/// # Arguments
+///
+/// * `filter` - Restricts the runs by monster, class spec, segment length and start date.
+///
+/// # Returns
+///
+/// * `Result<Vec<TrainingDummyRun>, String>` - The matching runs, oldest first.
+#[tauri::command]
+#[specta::specta]
+pub fn get_training_dummy_runs(
+    filter: Option<TrainingDummyRunFilter>,
+) -> Result<Vec<TrainingDummyRun>, String> {
+    load_training_dummy_runs(filter.unwrap_or_default())
+}
+
+/// Gets DPS statistics across saved training dummy runs.
+///
+/// # Arguments
+///
+/// * `filter` - Restricts the runs by monster, class spec, segment length and start date.
+///
+/// # Returns
+///
+/// * `Result<TrainingDummyRunStats, String>` - Mean, spread, best run and trend of the runs.
+#[tauri::command]
+#[specta::specta]
+pub fn get_training_dummy_run_stats(
+    filter: Option<TrainingDummyRunFilter>,
+) -> Result<TrainingDummyRunStats, String> {
+    let runs = load_training_dummy_runs(filter.unwrap_or_default())?;
+    Ok(summarize_training_runs(&runs))
+}
+
 /// Deletes an encounter by its ID.
 ///
 /// # Arguments
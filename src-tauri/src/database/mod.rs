@@ -5,7 +5,6 @@ pub mod schema;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{OnceLock, mpsc};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use diesel::prelude::*;
 use diesel::sqlite::SqliteConnection;
@@ -15,6 +14,7 @@ use serde::{Deserialize, Serialize};
 use crate::database::models as m;
 use crate::database::schema as sch;
 use crate::live::opcodes_models::{Encounter, Entity};
+use crate::live::phases::EncounterPhase;
 
 pub const MIGRATIONS: EmbeddedMigrations = diesel_migrations::embed_migrations!();
 const MAX_ENCOUNTER_HISTORY: i64 = 200;
@@ -51,13 +51,25 @@ pub struct EncounterMetadata {
     pub is_manually_reset: bool,
     pub boss_monster_ids: Vec<i32>,
     pub player_names: Vec<PlayerNameEntry>,
+    pub phases: Vec<EncounterPhase>,
+    /// Set when the encounter is a completed training dummy segment.
+    pub training_dummy_run: Option<TrainingDummyRunMetadata>,
 }
 
-pub fn now_ms() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64
+/// A completed training dummy segment, saved along with its encounter.
+#[derive(Debug, Clone, Default)]
+pub struct TrainingDummyRunMetadata {
+    pub monster_id: i32,
+    pub segment_secs: u32,
+    pub stop_after_damage_secs: Option<u32>,
+    pub duration_ms: i64,
+    pub active_ms: i64,
+    /// Damage dealt by the player.
+    pub total_dmg: i64,
+    pub player_id: i64,
+    pub class_id: i32,
+    pub class_spec: i32,
+    pub ability_score: i32,
 }
 
 pub fn default_db_path() -> PathBuf {
@@ -251,7 +263,8 @@ fn prune_and_reindex_encounters(conn: &mut SqliteConnection, keep: i64) -> Resul
                    is_favorite,
                    is_manually_reset,
                    boss_monster_ids,
-                   player_names
+                   player_names,
+                   phases
                  FROM encounters;",
             )
             .execute(tx)?;
@@ -266,6 +279,18 @@ fn prune_and_reindex_encounters(conn: &mut SqliteConnection, keep: i64) -> Resul
             )
             .execute(tx)?;
 
+            // Foreign keys are off, so runs keep pointing at the old IDs until remapped.
+            diesel::sql_query(
+                "UPDATE training_dummy_runs
+                 SET encounter_id = (
+                   SELECT te.new_id
+                   FROM temp_encounters_reindex te
+                   WHERE te.old_id = training_dummy_runs.encounter_id
+                 )
+                 WHERE encounter_id IS NOT NULL;",
+            )
+            .execute(tx)?;
+
             diesel::sql_query("DELETE FROM encounter_data;").execute(tx)?;
             diesel::sql_query("DELETE FROM encounters;").execute(tx)?;
 
@@ -286,7 +311,8 @@ fn prune_and_reindex_encounters(conn: &mut SqliteConnection, keep: i64) -> Resul
                    is_favorite,
                    is_manually_reset,
                    boss_monster_ids,
-                   player_names
+                   player_names,
+                   phases
                  )
                  SELECT
                    new_id,
@@ -304,7 +330,8 @@ fn prune_and_reindex_encounters(conn: &mut SqliteConnection, keep: i64) -> Resul
                    is_favorite,
                    is_manually_reset,
                    boss_monster_ids,
-                   player_names
+                   player_names,
+                   phases
                  FROM temp_encounters_reindex
                  ORDER BY new_id;",
             )
@@ -381,6 +408,7 @@ pub fn flush_playerdata(player_id: i64, last_seen_ms: i64, vdata_bytes: Vec<u8>)
 pub fn save_encounter(encounter: &Encounter, metadata: &EncounterMetadata) {
     use sch::encounter_data::dsl as ed;
     use sch::encounters::dsl as e;
+    use sch::training_dummy_runs::dsl as tr;
 
     let encounter = encounter.clone();
     let metadata = metadata.clone();
@@ -424,6 +452,14 @@ pub fn save_encounter(encounter: &Encounter, metadata: &EncounterMetadata) {
             }
         };
 
+        let phases_json = match serde_json::to_string(&metadata.phases) {
+            Ok(v) => v,
+            Err(e) => {
+                log::warn!(target: "app::db", "save_encounter_phases_json_failed error={}", e);
+                return;
+            }
+        };
+
         let result = conn.transaction::<i32, diesel::result::Error, _>(|tx| {
             let new_enc = m::NewEncounter {
                 started_at_ms: metadata.started_at_ms,
@@ -447,6 +483,7 @@ pub fn save_encounter(encounter: &Encounter, metadata: &EncounterMetadata) {
                     e::is_manually_reset.eq(if metadata.is_manually_reset { 1 } else { 0 }),
                     e::boss_monster_ids.eq(Some(boss_monster_ids_json)),
                     e::player_names.eq(Some(player_names_json)),
+                    e::phases.eq(Some(phases_json)),
                 ))
                 .execute(tx)?;
 
@@ -457,6 +494,28 @@ pub fn save_encounter(encounter: &Encounter, metadata: &EncounterMetadata) {
             diesel::insert_into(ed::encounter_data)
                 .values(&payload)
                 .execute(tx)?;
+
+            if let Some(run) = &metadata.training_dummy_run {
+                let new_run = m::NewTrainingDummyRun {
+                    encounter_id: Some(encounter_id),
+                    monster_id: run.monster_id,
+                    segment_secs: i32::try_from(run.segment_secs).unwrap_or(i32::MAX),
+                    stop_after_damage_secs: run
+                        .stop_after_damage_secs
+                        .map(|secs| i32::try_from(secs).unwrap_or(i32::MAX)),
+                    started_at_ms: metadata.started_at_ms,
+                    duration_ms: run.duration_ms,
+                    active_ms: run.active_ms,
+                    total_dmg: run.total_dmg,
+                    player_id: run.player_id,
+                    class_id: run.class_id,
+                    class_spec: run.class_spec,
+                    ability_score: run.ability_score,
+                };
+                diesel::insert_into(tr::training_dummy_runs)
+                    .values(&new_run)
+                    .execute(tx)?;
+            }
             Ok(encounter_id)
         });
 
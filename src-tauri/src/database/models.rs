@@ -139,6 +139,7 @@ pub struct EncounterRow {
     pub is_manually_reset: i32,
     pub boss_monster_ids: Option<String>,
     pub player_names: Option<String>,
+    pub phases: Option<String>,
 }
 
 /// Represents a new encounter to be inserted into the `encounters` table.
@@ -179,3 +180,53 @@ pub struct NewEncounterData<'a> {
     pub encounter_id: i32,
     pub data: &'a [u8],
 }
+
+/// Represents a row in the `training_dummy_runs` table.
+#[derive(Debug, Clone, Queryable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = sch::training_dummy_runs)]
+pub struct TrainingDummyRunRow {
+    /// The unique ID of the run.
+    pub id: i32,
+    /// The encounter of the run; cleared when the encounter is deleted.
+    pub encounter_id: Option<i32>,
+    /// The monster template ID of the dummy.
+    pub monster_id: i32,
+    /// The configured segment length in seconds.
+    pub segment_secs: i32,
+    /// The configured active damage time limit in seconds, if any.
+    pub stop_after_damage_secs: Option<i32>,
+    /// The timestamp of when the run started, in milliseconds since the Unix epoch.
+    pub started_at_ms: i64,
+    /// The duration of the run in milliseconds.
+    pub duration_ms: i64,
+    /// The accumulated active damage time in milliseconds.
+    pub active_ms: i64,
+    /// The damage dealt by the player.
+    pub total_dmg: i64,
+    /// The ID of the player.
+    pub player_id: i64,
+    /// The class ID of the player.
+    pub class_id: i32,
+    /// The class spec of the player.
+    pub class_spec: i32,
+    /// The ability score of the player.
+    pub ability_score: i32,
+}
+
+/// Represents a new run to be inserted into the `training_dummy_runs` table.
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = sch::training_dummy_runs)]
+pub struct NewTrainingDummyRun {
+    pub encounter_id: Option<i32>,
+    pub monster_id: i32,
+    pub segment_secs: i32,
+    pub stop_after_damage_secs: Option<i32>,
+    pub started_at_ms: i64,
+    pub duration_ms: i64,
+    pub active_ms: i64,
+    pub total_dmg: i64,
+    pub player_id: i64,
+    pub class_id: i32,
+    pub class_spec: i32,
+    pub ability_score: i32,
+}
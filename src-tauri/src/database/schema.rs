@@ -82,6 +82,40 @@ diesel::table! {
         boss_monster_ids -> Nullable<Text>,
         // JSON-encoded array of player names for fast list/filter queries.
         player_names -> Nullable<Text>,
+        // JSON-encoded array of encounter phase boundaries.
+        phases -> Nullable<Text>,
+    }
+}
+
+// Represents the `training_dummy_runs` table.
+diesel::table! {
+    training_dummy_runs (id) {
+        // The unique ID of the run.
+        id -> Integer,
+        // The encounter of the run; cleared when the encounter is deleted.
+        encounter_id -> Nullable<Integer>,
+        // The monster template ID of the dummy.
+        monster_id -> Integer,
+        // The configured segment length in seconds.
+        segment_secs -> Integer,
+        // The configured active damage time limit in seconds, if any.
+        stop_after_damage_secs -> Nullable<Integer>,
+        // The timestamp of when the run started, in milliseconds since the Unix epoch.
+        started_at_ms -> BigInt,
+        // The duration of the run in milliseconds.
+        duration_ms -> BigInt,
+        // The accumulated active damage time in milliseconds.
+        active_ms -> BigInt,
+        // The damage dealt by the player.
+        total_dmg -> BigInt,
+        // The ID of the player.
+        player_id -> BigInt,
+        // The class ID of the player.
+        class_id -> Integer,
+        // The class spec of the player.
+        class_spec -> Integer,
+        // The ability score of the player.
+        ability_score -> Integer,
     }
 }
 
@@ -96,10 +130,12 @@ diesel::table! {
 }
 
 diesel::joinable!(encounter_data -> encounters (encounter_id));
+diesel::joinable!(training_dummy_runs -> encounters (encounter_id));
 diesel::allow_tables_to_appear_in_same_query!(
     entities,
     encounters,
     encounter_data,
+    training_dummy_runs,
     detailed_playerdata,
     app_config,
 );
@@ -0,0 +1,390 @@
+use crate::live::clock::ReplayTimeGuard;
+use crate::live::data_tables::data_tables;
+use crate::live::live_main::{replay_packet_into_state, replay_session_into_state};
+use crate::live::name_registry::{labeled, monster_name, scene_name, skill_name};
+use crate::live::opcodes_models::{Encounter, Entity};
+use crate::live::state::{AppState, AppStateManager, persist_and_save_encounter};
+use crate::packets::packet_capture::spawn_pcap_replay;
+use blueprotobuf_lib::blueprotobuf::EEntityType;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+pub use crate::live::name_registry::{NameLocale, set_name_locale};
+
+/// A capture file to replay.
+#[derive(Debug, Clone)]
+pub enum ReplayInput {
+    /// A pcap or pcapng capture.
+    Pcap(PathBuf),
+    /// A session recorded by the meter (`.rlsession`).
+    Session(PathBuf),
+}
+
+impl ReplayInput {
+    /// Picks the input kind from the file extension; anything but `.rlsession` is read as pcap.
+    pub fn from_path(path: PathBuf) -> Self {
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("rlsession"))
+        {
+            Self::Session(path)
+        } else {
+            Self::Pcap(path)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "table" => Some(Self::Table),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Summary of one encounter found in a replayed capture.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterReport {
+    /// 1-based position of the encounter in the capture.
+    pub index: usize,
+    pub scene_id: Option<i32>,
+    pub scene_name: Option<String>,
+    pub started_at_ms: u64,
+    pub duration_ms: u64,
+    pub total_damage: u64,
+    pub total_healing: u64,
+    /// Damage dealt to bosses by everyone.
+    pub boss_damage: u64,
+    /// Players with damage or healing, highest damage first.
+    pub players: Vec<PlayerReport>,
+    /// Bosses that took damage, most damaged first.
+    pub bosses: Vec<BossReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerReport {
+    pub uid: i64,
+    pub name: String,
+    pub class_id: i32,
+    pub damage: u64,
+    pub dps: f64,
+    pub healing: u64,
+    pub hps: f64,
+    pub boss_damage: u64,
+    pub deaths: u32,
+    /// Skill with the most damage, as `name(skill key)`.
+    pub top_skill: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BossReport {
+    pub uid: i64,
+    pub monster_id: Option<i32>,
+    pub name: String,
+    pub damage_taken: u64,
+}
+
+/// Replays `input` through the packet pipeline and returns one report per encounter.
+///
+/// Packets are fed as fast as they decode, with the meter clock following their capture
+/// timestamps, so encounter splits, durations and per-second numbers match the capture and
+/// repeat runs give the same output.
+pub fn replay(input: &ReplayInput) -> Result<Vec<EncounterReport>, String> {
+    let (state_manager, _control_rx) = AppStateManager::new();
+    let mut state = AppState::new();
+    state.finished_encounters = Some(Vec::new());
+    let clock = ReplayTimeGuard::default();
+
+    match input {
+        ReplayInput::Session(path) => {
+            replay_session_into_state(&state_manager, &mut state, &clock, path)?;
+        }
+        ReplayInput::Pcap(path) => {
+            let (mut rx, capture) = spawn_pcap_replay(path.clone());
+            while let Some(item) = rx.blocking_recv() {
+                replay_packet_into_state(&state_manager, &mut state, &clock, item);
+            }
+            capture
+                .join()
+                .map_err(|_| "pcap replay thread panicked".to_string())??;
+        }
+    }
+
+    if state.encounter.time_fight_start_ms > 0 {
+        persist_and_save_encounter(&mut state, false, "replay_end");
+    }
+    let encounters = state.finished_encounters.take().unwrap_or_default();
+    Ok(encounters
+        .iter()
+        .enumerate()
+        .map(|(i, encounter)| EncounterReport::from_encounter(i + 1, encounter))
+        .collect())
+}
+
+fn saturating_u64(value: u128) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+impl EncounterReport {
+    fn from_encounter(index: usize, encounter: &Encounter) -> Self {
+        let start_ms = encounter.time_fight_start_ms;
+        let end_ms = encounter.time_last_combat_packet_ms.max(start_ms);
+        let duration_ms = end_ms - start_ms;
+        let per_second = |total: u128| {
+            if duration_ms == 0 {
+                0.0
+            } else {
+                total as f64 * 1000.0 / duration_ms as f64
+            }
+        };
+
+        let mut players: Vec<PlayerReport> = encounter
+            .entity_uid_to_entity
+            .iter()
+            .filter(|(_, entity)| {
+                entity.entity_type == EEntityType::EntChar
+                    && (entity.damage.hits > 0 || entity.healing.hits > 0)
+            })
+            .map(|(&uid, entity)| PlayerReport {
+                uid,
+                name: display_name(uid, entity),
+                class_id: entity.class_id,
+                damage: saturating_u64(entity.damage.total),
+                dps: per_second(entity.damage.total),
+                healing: saturating_u64(entity.healing.total),
+                hps: per_second(entity.healing.total),
+                boss_damage: saturating_u64(entity.damage_boss_only.total),
+                deaths: entity.life_timeline.summary(start_ms, end_ms).death_count,
+                top_skill: top_skill(entity),
+            })
+            .collect();
+        players.sort_by(|a, b| b.damage.cmp(&a.damage).then(a.uid.cmp(&b.uid)));
+
+        let tables = data_tables();
+        let mut bosses: Vec<BossReport> = encounter
+            .entity_uid_to_entity
+            .iter()
+            .filter(|(_, entity)| entity.is_boss(&tables) && entity.taken.hits > 0)
+            .map(|(&uid, entity)| BossReport {
+                uid,
+                monster_id: entity.monster_type_id,
+                name: boss_name(uid, entity),
+                damage_taken: saturating_u64(entity.taken.total),
+            })
+            .collect();
+        bosses.sort_by(|a, b| b.damage_taken.cmp(&a.damage_taken).then(a.uid.cmp(&b.uid)));
+
+        Self {
+            index,
+            scene_id: encounter.current_scene_id,
+            scene_name: encounter.current_scene_id.and_then(scene_name),
+            started_at_ms: saturating_u64(start_ms),
+            duration_ms: saturating_u64(duration_ms),
+            total_damage: saturating_u64(encounter.total_dmg),
+            total_healing: saturating_u64(encounter.total_heal),
+            boss_damage: saturating_u64(encounter.total_dmg_boss_only),
+            players,
+            bosses,
+        }
+    }
+}
+
+fn display_name(uid: i64, entity: &Entity) -> String {
+    if entity.name.is_empty() {
+        format!("#{uid}")
+    } else {
+        entity.name.clone()
+    }
+}
+
+fn top_skill(entity: &Entity) -> Option<String> {
+    entity
+        .skill_uid_to_dmg_skill
+        .iter()
+        .max_by(|(ka, a), (kb, b)| a.total_value.cmp(&b.total_value).then(kb.cmp(ka)))
+        .map(|(&key, _)| labeled(skill_name(key), key))
+}
+
+/// The entity name, else the monster's name from the name tables.
+fn boss_name(uid: i64, entity: &Entity) -> String {
+    if entity.name.is_empty()
+        && let Some(name) = entity.monster_type_id.and_then(monster_name)
+    {
+        return name;
+    }
+    display_name(uid, entity)
+}
+
+/// Renders reports for printing. CSV has one row per player.
+pub fn render_reports(reports: &[EncounterReport], format: ReportFormat) -> Result<String, String> {
+    match format {
+        ReportFormat::Json => serde_json::to_string_pretty(reports)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("failed to serialize reports: {e}")),
+        ReportFormat::Csv => Ok(render_csv(reports)),
+        ReportFormat::Table => Ok(render_table(reports)),
+    }
+}
+
+const CSV_HEADER: &str = "encounter,scene_id,scene_name,duration_s,uid,name,class_id,damage,dps,healing,hps,boss_damage,deaths";
+
+fn render_csv(reports: &[EncounterReport]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for report in reports {
+        let scene_id = report.scene_id.map(|id| id.to_string()).unwrap_or_default();
+        let scene_name = csv_field(report.scene_name.as_deref().unwrap_or_default());
+        for player in &report.players {
+            let _ = writeln!(
+                out,
+                "{},{},{},{:.1},{},{},{},{},{:.1},{},{:.1},{},{}",
+                report.index,
+                scene_id,
+                scene_name,
+                report.duration_ms as f64 / 1000.0,
+                player.uid,
+                csv_field(&player.name),
+                player.class_id,
+                player.damage,
+                player.dps,
+                player.healing,
+                player.hps,
+                player.boss_damage,
+                player.deaths
+            );
+        }
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_table(reports: &[EncounterReport]) -> String {
+    let mut out = String::new();
+    if reports.is_empty() {
+        out.push_str("No encounters found.\n");
+        return out;
+    }
+    for report in reports {
+        let scene = match (&report.scene_name, report.scene_id) {
+            (Some(name), Some(id)) => format!("{name} ({id})"),
+            (None, Some(id)) => id.to_string(),
+            _ => "-".to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "Encounter {}  scene {}  duration {:.1}s  damage {}  boss damage {}  healing {}",
+            report.index,
+            scene,
+            report.duration_ms as f64 / 1000.0,
+            report.total_damage,
+            report.boss_damage,
+            report.total_healing
+        );
+        let name_width = report
+            .players
+            .iter()
+            .map(|player| player.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Player".len());
+        let _ = writeln!(
+            out,
+            "  {:<name_width$}  {:>12}  {:>10}  {:>12}  {:>10}  {:>12}  {:>6}",
+            "Player", "Damage", "DPS", "Healing", "HPS", "Boss dmg", "Deaths"
+        );
+        for player in &report.players {
+            let padding = name_width - player.name.chars().count();
+            let _ = writeln!(
+                out,
+                "  {}{:padding$}  {:>12}  {:>10.0}  {:>12}  {:>10.0}  {:>12}  {:>6}",
+                player.name,
+                "",
+                player.damage,
+                player.dps,
+                player.healing,
+                player.hps,
+                player.boss_damage,
+                player.deaths
+            );
+        }
+        for boss in &report.bosses {
+            let _ = writeln!(
+                out,
+                "  Boss {} ({}): {} damage taken",
+                boss.name,
+                boss.monster_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                boss.damage_taken
+            );
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EncounterReport, ReportFormat, render_reports};
+    use crate::live::opcodes_models::{Encounter, Entity};
+    use blueprotobuf_lib::blueprotobuf::EEntityType;
+
+    #[test]
+    fn summarizes_players_and_renders_csv() {
+        let mut encounter = Encounter {
+            time_fight_start_ms: 10_000,
+            time_last_combat_packet_ms: 20_000,
+            total_dmg: 15_000,
+            ..Default::default()
+        };
+        for (uid, name, damage) in [(1, "Alpha", 5_000), (2, "Beta, Jr.", 10_000)] {
+            let mut entity = Entity {
+                name: name.to_string(),
+                entity_type: EEntityType::EntChar,
+                ..Default::default()
+            };
+            entity.damage.total = damage;
+            entity.damage.hits = 1;
+            encounter.entity_uid_to_entity.insert(uid, entity);
+        }
+        encounter.entity_uid_to_entity.insert(
+            3,
+            Entity {
+                entity_type: EEntityType::EntChar,
+                ..Default::default()
+            },
+        );
+
+        let report = EncounterReport::from_encounter(1, &encounter);
+        assert_eq!(report.duration_ms, 10_000);
+        assert_eq!(report.players.len(), 2);
+        assert_eq!(report.players[0].uid, 2);
+        assert_eq!(report.players[0].dps, 1_000.0);
+
+        let csv = render_reports(&[report], ReportFormat::Csv).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1], "1,,,10.0,2,\"Beta, Jr.\",0,10000,1000.0,0,0.0,0,0");
+    }
+}
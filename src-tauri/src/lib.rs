@@ -1,4 +1,6 @@
 mod build_app;
+/// Capture file replay without a webview, used by the `resonance-logs-cli` binary.
+pub mod headless;
 mod live;
 pub mod module_optimizer;
 mod packets;
@@ -6,8 +8,9 @@ mod packets;
 use crate::build_app::build_and_run;
 use log::{info, warn};
 use specta_typescript::{BigIntExportBehavior, Typescript};
+use std::process::Command;
 #[cfg(windows)]
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
@@ -59,6 +62,14 @@ pub fn run() {
             live::commands::start_training_dummy,
             live::commands::stop_training_dummy,
             live::commands::save_and_apply_monitor_runtime_snapshot,
+            live::commands::start_session_recording,
+            live::commands::stop_session_recording,
+            live::commands::get_packet_drop_stats,
+            live::commands::get_capture_health,
+            live::commands::get_capture_status,
+            live::commands::restart_capture,
+            live::commands::get_meter_data_status,
+            live::commands::reload_meter_data,
             database::commands::get_recent_encounters,
             database::commands::get_unique_scene_ids,
             database::commands::get_unique_boss_monster_ids,
@@ -66,6 +77,12 @@ pub fn run() {
             database::commands::get_recent_encounters_filtered,
             database::commands::get_encounter_by_id,
             database::commands::get_encounter_entities_raw,
+            database::commands::get_encounter_boss_debuffs,
+            database::commands::get_encounter_boss_aggro,
+            database::commands::get_encounter_rotation,
+            database::commands::get_encounter_time_series,
+            database::commands::get_training_dummy_runs,
+            database::commands::get_training_dummy_run_stats,
             database::commands::delete_encounter,
             database::commands::delete_encounters,
             database::commands::toggle_favorite_encounter,
@@ -115,8 +132,7 @@ pub fn run() {
             let _setup_guard = setup_span.enter();
 
             log::info!(target: "app::startup", "starting app v{}", app.package_info().version);
-            stop_windivert();
-            remove_windivert();
+            unload_and_remove_windivert();
 
             // Initialize database and background writer early to avoid startup races where
             // multiple background tasks/commands trigger migrations concurrently.
@@ -237,10 +253,14 @@ mod packet_settings_commands {
                 continue;
             }
             let path = target_dir.join("packetCapture.json");
-            let payload = json!({
-                "method": method,
-                "npcapDevice": npcap_device,
-            });
+            // Keep keys this command doesn't manage (queue tuning, replay paths).
+            let mut payload = std::fs::read(&path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+                .filter(serde_json::Value::is_object)
+                .unwrap_or_else(|| json!({}));
+            payload["method"] = json!(method);
+            payload["npcapDevice"] = json!(npcap_device);
             match std::fs::write(
                 &path,
                 serde_json::to_vec_pretty(&payload).map_err(|e| e.to_string())?,
@@ -313,10 +333,10 @@ mod debug_commands {
 /// Starts the WinDivert driver.
 ///
 /// This function executes a shell command to create and start the WinDivert driver service.
+#[cfg(windows)]
 #[allow(dead_code)]
 fn start_windivert() {
-    // Run the command silently (no console window) on Windows. On other platforms, just
-    // redirect stdio to null so nothing is printed.
+    // Run the command silently (no console window).
     let mut cmd = Command::new("sc");
     cmd.args([
         "create",
@@ -339,6 +359,7 @@ fn start_windivert() {
 /// Stops the WinDivert driver.
 ///
 /// This function executes a shell command to stop the WinDivert driver service.
+#[cfg(windows)]
 fn stop_windivert() {
     let mut cmd = Command::new("sc");
     cmd.args(["stop", "windivert"]);
@@ -353,6 +374,7 @@ fn stop_windivert() {
 /// Removes the WinDivert driver.
 ///
 /// This function executes a shell command to delete the WinDivert driver service.
+#[cfg(windows)]
 fn remove_windivert() {
     let mut cmd = Command::new("sc");
     cmd.args(["delete", "windivert", "start=", "demand"]);
@@ -382,27 +404,17 @@ fn unload_and_remove_windivert() {
     }
 }
 
-/// Helper to run a prepared Command with stdio redirected to null and (on Windows)
-/// with the CREATE_NO_WINDOW flag so no console window appears.
+/// Helper to run a prepared Command with stdio redirected to null and with the
+/// CREATE_NO_WINDOW flag so no console window appears.
+#[cfg(windows)]
 fn run_command_silently(cmd: &mut Command) -> std::io::Result<std::process::ExitStatus> {
-    #[cfg(windows)]
-    {
-        // CREATE_NO_WINDOW = 0x08000000
-        use std::os::windows::process::CommandExt;
-        cmd.creation_flags(0x08000000)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .stdin(Stdio::null())
-            .status()
-    }
-
-    #[cfg(not(windows))]
-    {
-        cmd.stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .stdin(Stdio::null())
-            .status()
-    }
+    // CREATE_NO_WINDOW = 0x08000000
+    use std::os::windows::process::CommandExt;
+    cmd.creation_flags(0x08000000)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .stdin(Stdio::null())
+        .status()
 }
 
 // Updater helper: checks for updates and emits an event for frontend reminder.
@@ -725,6 +737,7 @@ fn setup_tray(app: &tauri::AppHandle) -> tauri::Result<()> {
                 disable_live_clickthrough(tray_app, &live_meter_window);
             }
             "quit" => {
+                #[cfg(windows)]
                 stop_windivert();
                 tray_app.exit(0);
             }
@@ -0,0 +1,151 @@
+use crate::live::commands_models::HateEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The top-threat holder of a boss changed.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AggroChange {
+    pub timestamp_ms: u128,
+    /// New holder; `None` when the hate list emptied.
+    pub uid: Option<i64>,
+}
+
+/// Top-threat holders of one boss, in time order.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AggroTimeline {
+    pub changes: Vec<AggroChange>,
+}
+
+/// How long one player was at the top of a boss's hate list.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AggroHold {
+    pub uid: i64,
+    pub name: String,
+    pub held_ms: u128,
+    /// Times the player took aggro, including holding it when the window starts.
+    pub times_gained: u32,
+}
+
+/// Entity with the most hate; the earlier entry wins ties.
+pub fn top_threat(entries: &[HateEntry]) -> Option<i64> {
+    entries
+        .iter()
+        .rev()
+        .max_by_key(|entry| entry.hate_val)
+        .map(|entry| entry.uid)
+}
+
+impl AggroTimeline {
+    pub fn holder(&self) -> Option<i64> {
+        self.changes.last().and_then(|change| change.uid)
+    }
+
+    /// Records the current top-threat holder. Returns whether it changed.
+    pub fn record(&mut self, uid: Option<i64>, timestamp_ms: u128) -> bool {
+        if self.holder() == uid {
+            return false;
+        }
+        // Hate lists come from different packets; never let a change go backwards in time.
+        let timestamp_ms = self
+            .changes
+            .last()
+            .map_or(timestamp_ms, |last| timestamp_ms.max(last.timestamp_ms));
+        self.changes.push(AggroChange { timestamp_ms, uid });
+        true
+    }
+
+    /// Drops the history but keeps whoever holds aggro right now.
+    pub fn clear_keeping_holder(&mut self) {
+        let last = self
+            .changes
+            .last()
+            .copied()
+            .filter(|last| last.uid.is_some());
+        self.changes.clear();
+        self.changes.extend(last);
+    }
+
+    /// Time each player held aggro between `start_ms` and `end_ms`, longest first. Names are
+    /// left for the caller to fill in.
+    pub fn holds(&self, start_ms: u128, end_ms: u128) -> Vec<AggroHold> {
+        let end_ms = end_ms.max(start_ms);
+        let mut holds: HashMap<i64, AggroHold> = HashMap::new();
+        for (index, change) in self.changes.iter().enumerate() {
+            let Some(uid) = change.uid else {
+                continue;
+            };
+            let until_ms = self
+                .changes
+                .get(index + 1)
+                .map_or(end_ms, |next| next.timestamp_ms);
+            let from_ms = change.timestamp_ms.clamp(start_ms, end_ms);
+            let until_ms = until_ms.clamp(start_ms, end_ms);
+            if until_ms <= from_ms {
+                continue;
+            }
+            let hold = holds.entry(uid).or_insert_with(|| AggroHold {
+                uid,
+                ..Default::default()
+            });
+            hold.held_ms += until_ms - from_ms;
+            hold.times_gained += 1;
+        }
+        let mut holds: Vec<AggroHold> = holds.into_values().collect();
+        holds.sort_by(|a, b| b.held_ms.cmp(&a.held_ms).then(a.uid.cmp(&b.uid)));
+        holds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AggroTimeline, top_threat};
+    use crate::live::commands_models::HateEntry;
+
+    #[test]
+    fn tracks_aggro_swaps() {
+        let entries = [
+            HateEntry {
+                uid: 1,
+                hate_val: 50,
+            },
+            HateEntry {
+                uid: 2,
+                hate_val: 80,
+            },
+            HateEntry {
+                uid: 3,
+                hate_val: 80,
+            },
+        ];
+        assert_eq!(top_threat(&entries), Some(2));
+        assert_eq!(top_threat(&[]), None);
+
+        let mut timeline = AggroTimeline::default();
+        assert!(!timeline.record(None, 0));
+        assert!(timeline.record(Some(1), 1_000));
+        assert!(!timeline.record(Some(1), 2_000));
+        assert!(timeline.record(Some(2), 4_000));
+        // Out of order: clamped to the previous change.
+        assert!(timeline.record(Some(1), 3_000));
+        assert!(timeline.record(Some(2), 7_000));
+        assert!(timeline.record(None, 9_000));
+
+        let holds = timeline.holds(2_000, 10_000);
+        assert_eq!(holds.len(), 2);
+        assert_eq!(
+            (holds[0].uid, holds[0].held_ms, holds[0].times_gained),
+            (1, 5_000, 2)
+        );
+        assert_eq!(
+            (holds[1].uid, holds[1].held_ms, holds[1].times_gained),
+            (2, 2_000, 1)
+        );
+
+        timeline.record(Some(3), 9_500);
+        timeline.clear_keeping_holder();
+        assert_eq!(timeline.changes.len(), 1);
+        assert_eq!(timeline.holds(10_000, 12_000)[0].held_ms, 2_000);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::live::counter_tracker::CounterRule;
+use crate::live::phases::{DEFAULT_PHASE_HP_THRESHOLDS, normalize_phase_hp_thresholds};
+use crate::live::rolling_window::{DEFAULT_ROLLING_WINDOWS_SECS, normalize_rolling_windows};
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -28,6 +30,8 @@ impl Default for MonitorRuntimeSnapshot {
 impl MonitorRuntimeSnapshot {
     pub fn normalize(mut self) -> Result<Self, String> {
         self.live.event_update_rate_ms = self.live.event_update_rate_ms.clamp(50, 2000);
+        normalize_phase_hp_thresholds(&mut self.live.phase_hp_thresholds);
+        normalize_rolling_windows(&mut self.live.rolling_windows_secs);
 
         dedup_and_sort_i32(&mut self.skill.monitored_skill_ids);
         if self.skill.monitored_skill_ids.len() > 10 {
@@ -65,12 +69,21 @@ impl MonitorRuntimeSnapshot {
 #[serde(rename_all = "camelCase", default)]
 pub struct LiveRuntimeSnapshot {
     pub event_update_rate_ms: u64,
+    /// Boss HP percentages that start a new encounter phase.
+    pub phase_hp_thresholds: Vec<u8>,
+    /// Lengths of the rolling DPS windows in the live payload, in seconds.
+    pub rolling_windows_secs: Vec<u32>,
+    /// UI locale, e.g. `zh-CN`; picks the language of game names in logs.
+    pub locale: String,
 }
 
 impl Default for LiveRuntimeSnapshot {
     fn default() -> Self {
         Self {
             event_update_rate_ms: 200,
+            phase_hp_thresholds: DEFAULT_PHASE_HP_THRESHOLDS.to_vec(),
+            rolling_windows_secs: DEFAULT_ROLLING_WINDOWS_SECS.to_vec(),
+            locale: "zh-CN".to_string(),
         }
     }
 }
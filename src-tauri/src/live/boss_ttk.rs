@@ -0,0 +1,215 @@
+use crate::live::data_tables::parse_id_keys;
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// HP samples older than this don't count towards the kill time estimate.
+pub const TTK_WINDOW_MS: u128 = 30_000;
+/// Samples closer together than this are merged, so fast HP updates don't grow the window.
+const SAMPLE_INTERVAL_MS: u128 = 250;
+const MIN_SAMPLES: usize = 3;
+const MIN_SAMPLE_SPAN_MS: u128 = 3_000;
+/// Two-sided z-score of the 90% confidence band.
+const CONFIDENCE_Z: f64 = 1.645;
+
+/// Estimated time until a boss dies at its recent HP loss rate.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeToKill {
+    pub estimate_ms: u64,
+    /// Lower edge of the 90% confidence band.
+    pub low_ms: u64,
+    /// Upper edge of the 90% confidence band; `None` when the slowest plausible rate
+    /// doesn't bring the boss down.
+    pub high_ms: Option<u64>,
+    /// HP lost per second over the last `TTK_WINDOW_MS`.
+    pub hp_loss_per_sec: f64,
+}
+
+/// Enrage timer of a boss from `meter-data/EnrageTimers.json`, counted from the first HP
+/// loss.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrageTimer {
+    pub enrage_ms: u64,
+    pub remaining_ms: u64,
+    /// Whether the estimated kill time fits in the remaining time, if there is an estimate.
+    pub on_pace: Option<bool>,
+}
+
+/// Recent HP of a boss, for kill time and enrage tracking.
+#[derive(Debug, Default, Clone)]
+pub struct BossHpHistory {
+    /// When the boss first lost HP; reset when it's back to full HP.
+    engaged_at_ms: Option<u128>,
+    /// `(timestamp_ms, current_hp)`, oldest first.
+    samples: VecDeque<(u128, u128)>,
+}
+
+impl BossHpHistory {
+    pub fn record(&mut self, current_hp: u128, max_hp: u128, timestamp_ms: u128) {
+        if current_hp >= max_hp {
+            // Untouched or reset after a wipe.
+            self.clear();
+            return;
+        }
+        self.engaged_at_ms.get_or_insert(timestamp_ms);
+        match self.samples.back_mut() {
+            Some(last) if timestamp_ms.saturating_sub(last.0) < SAMPLE_INTERVAL_MS => {
+                last.1 = current_hp;
+            }
+            _ => self.samples.push_back((timestamp_ms, current_hp)),
+        }
+        self.prune(timestamp_ms);
+    }
+
+    fn prune(&mut self, now_ms: u128) {
+        let oldest_ms = now_ms.saturating_sub(TTK_WINDOW_MS);
+        while self.samples.front().is_some_and(|(ts, _)| *ts < oldest_ms) {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn engaged_at_ms(&self) -> Option<u128> {
+        self.engaged_at_ms
+    }
+
+    /// Fits a line through the recent samples and extrapolates it to zero HP. `None` until
+    /// there are enough samples, or when the boss isn't losing HP.
+    pub fn time_to_kill(&self, now_ms: u128) -> Option<TimeToKill> {
+        let oldest_ms = now_ms.saturating_sub(TTK_WINDOW_MS);
+        let samples: Vec<(f64, f64)> = self
+            .samples
+            .iter()
+            .filter(|(ts, _)| (oldest_ms..=now_ms).contains(ts))
+            .map(|&(ts, hp)| ((ts - oldest_ms) as f64 / 1000.0, hp as f64))
+            .collect();
+        let span_secs = samples.last()?.0 - samples.first()?.0;
+        if samples.len() < MIN_SAMPLES || span_secs * 1000.0 < MIN_SAMPLE_SPAN_MS as f64 {
+            return None;
+        }
+
+        let n = samples.len() as f64;
+        let mean_t = samples.iter().map(|(t, _)| t).sum::<f64>() / n;
+        let mean_hp = samples.iter().map(|(_, hp)| hp).sum::<f64>() / n;
+        let sxx: f64 = samples.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+        let sxy: f64 = samples
+            .iter()
+            .map(|(t, hp)| (t - mean_t) * (hp - mean_hp))
+            .sum();
+        if sxx <= 0.0 {
+            return None;
+        }
+        let slope = sxy / sxx;
+        let rate = -slope;
+        if rate <= 0.0 {
+            return None;
+        }
+        let residuals: f64 = samples
+            .iter()
+            .map(|(t, hp)| (hp - (mean_hp + slope * (t - mean_t))).powi(2))
+            .sum();
+        let slope_error = (residuals / (n - 2.0) / sxx).sqrt();
+
+        let now_t = (now_ms - oldest_ms) as f64 / 1000.0;
+        let hp_now = (mean_hp + slope * (now_t - mean_t)).max(0.0);
+        let secs_to_ms = |secs: f64| (secs * 1000.0).round() as u64;
+        let fastest = rate + CONFIDENCE_Z * slope_error;
+        let slowest = rate - CONFIDENCE_Z * slope_error;
+        Some(TimeToKill {
+            estimate_ms: secs_to_ms(hp_now / rate),
+            low_ms: secs_to_ms(hp_now / fastest),
+            high_ms: (slowest > 0.0).then(|| secs_to_ms(hp_now / slowest)),
+            hp_loss_per_sec: rate,
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.engaged_at_ms = None;
+        self.samples.clear();
+    }
+}
+
+/// Remaining enrage time of a boss engaged at `engaged_at_ms`.
+pub fn enrage_timer(
+    enrage_secs: u32,
+    engaged_at_ms: u128,
+    now_ms: u128,
+    time_to_kill: Option<&TimeToKill>,
+) -> EnrageTimer {
+    let enrage_ms = u64::from(enrage_secs) * 1000;
+    let elapsed_ms = u64::try_from(now_ms.saturating_sub(engaged_at_ms)).unwrap_or(u64::MAX);
+    let remaining_ms = enrage_ms.saturating_sub(elapsed_ms);
+    EnrageTimer {
+        enrage_ms,
+        remaining_ms,
+        on_pace: time_to_kill.map(|ttk| ttk.estimate_ms <= remaining_ms),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEnrageTimer {
+    seconds: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEnrageTimers {
+    #[serde(default)]
+    monsters: HashMap<String, RawEnrageTimer>,
+}
+
+/// Parses `EnrageTimers.json` into enrage seconds by monster type ID.
+pub(crate) fn parse_enrage_timers(contents: &str) -> Result<HashMap<i32, u32>> {
+    let raw: RawEnrageTimers = serde_json::from_str(contents)?;
+    let timers: HashMap<i32, RawEnrageTimer> = parse_id_keys(raw.monsters)?;
+    let mut enrage_secs = HashMap::with_capacity(timers.len());
+    for (monster_id, timer) in timers {
+        if monster_id <= 0 || timer.seconds == 0 {
+            bail!("invalid enrage timer for monster {}", monster_id);
+        }
+        enrage_secs.insert(monster_id, timer.seconds);
+    }
+    Ok(enrage_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BossHpHistory, TTK_WINDOW_MS, enrage_timer, parse_enrage_timers};
+
+    #[test]
+    fn estimates_kill_time_from_recent_hp_loss() {
+        let mut history = BossHpHistory::default();
+        history.record(1_000, 1_000, 0);
+        assert_eq!(history.engaged_at_ms(), None);
+
+        // 10 HP per second with some noise, starting at 10s.
+        for (secs, noise) in (10..=20).zip([0i64, 3, -2, 1, -3, 2, 0, -1, 3, -2, 0]) {
+            let hp = (900 - 10 * (secs - 10) + noise) as u128;
+            history.record(hp, 1_000, secs as u128 * 1000);
+        }
+        assert_eq!(history.engaged_at_ms(), Some(10_000));
+        let ttk = history.time_to_kill(20_000).unwrap();
+        assert!((ttk.hp_loss_per_sec - 10.0).abs() < 0.5);
+        assert!((ttk.estimate_ms as i64 - 80_000).abs() < 3_000);
+        assert!(ttk.low_ms < ttk.estimate_ms);
+        assert!(ttk.high_ms.unwrap() > ttk.estimate_ms);
+
+        let enrage = enrage_timer(60, 10_000, 20_000, Some(&ttk));
+        assert_eq!(enrage.remaining_ms, 50_000);
+        assert_eq!(enrage.on_pace, Some(false));
+        assert_eq!(enrage_timer(60, 10_000, 90_000, None).remaining_ms, 0);
+
+        // Samples leave the window, and a boss back at full HP starts over.
+        assert!(history.time_to_kill(20_000 + TTK_WINDOW_MS).is_none());
+        history.record(1_000, 1_000, 21_000);
+        assert_eq!(history.engaged_at_ms(), None);
+        assert!(history.time_to_kill(21_000).is_none());
+
+        let timers = parse_enrage_timers(r#"{ "monsters": { "115": { "seconds": 480 } } }"#);
+        assert_eq!(timers.unwrap().get(&115), Some(&480));
+        assert!(parse_enrage_timers(r#"{ "monsters": { "115": { "seconds": 0 } } }"#).is_err());
+        assert!(parse_enrage_timers(r#"{ "monsters": { "115": 480 } }"#).is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::database::now_ms;
+use crate::live::buff_uptime::{BuffUptimeTracker, UptimeWindow};
+use crate::live::clock::now_ms;
 use crate::live::commands_models::BuffUpdateState;
 use blueprotobuf_lib::blueprotobuf::{
     BuffChange, BuffEffectSync, BuffInfo, EBuffEffectLogicPbType, EBuffEventType,
@@ -14,6 +15,8 @@ pub struct ActiveBuff {
     pub create_time: i64,
     pub received_time_ms: i64,
     pub source_config_id: Option<i32>,
+    /// Entity that applied the buff, from `fire_uuid`.
+    pub caster_uid: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,6 +35,7 @@ pub struct BuffChangeEvent {
     pub create_time_ms: Option<i64>,
     pub duration_ms: Option<i32>,
     pub source_config_id: Option<i32>,
+    pub caster_uid: Option<i64>,
 }
 
 #[derive(Debug, Default)]
@@ -90,50 +94,31 @@ impl BuffMonitor {
 
                 if effect_type == EBuffEffectLogicPbType::BuffEffectAddBuff as i32 {
                     if let Ok(buff_info) = BuffInfo::decode(raw.as_slice()) {
-                        let Some(base_id) = buff_info.base_id else {
+                        let Some(buff) = self.active_buff_from_info(
+                            &buff_info,
+                            now,
+                            server_clock_offset,
+                            local_player_uid,
+                        ) else {
                             continue;
                         };
-                        let fire_uid = buff_info.fire_uuid.unwrap_or(0) >> 16;
-                        let in_self_list = self.self_applied_buff_ids.contains(&base_id);
-                        if in_self_list && fire_uid != local_player_uid {
-                            continue;
-                        }
-                        let layer = buff_info.layer.unwrap_or(1);
-                        let duration = buff_info.duration.unwrap_or(0);
-                        let create_time = buff_info.create_time.unwrap_or(now);
-                        let source_config_id = buff_info
-                            .fight_source_info
-                            .as_ref()
-                            .and_then(|info| info.source_config_id);
-                        if buff_info.create_time.is_some() {
-                            *server_clock_offset = now - create_time;
-                        }
-
-                        self.active_buffs.insert(
-                            buff_uuid,
-                            ActiveBuff {
-                                base_id,
-                                layer,
-                                duration,
-                                create_time,
-                                received_time_ms: now,
-                                source_config_id,
-                            },
-                        );
                         changes.push(BuffChangeEvent {
-                            base_id,
+                            base_id: buff.base_id,
                             buff_uuid,
                             change_type: BuffChangeType::Added,
                             create_time_ms: Some(now),
-                            duration_ms: Some(duration),
-                            source_config_id,
+                            duration_ms: Some(buff.duration),
+                            source_config_id: buff.source_config_id,
+                            caster_uid: buff.caster_uid,
                         });
+                        self.active_buffs.insert(buff_uuid, buff);
                     }
                 } else if effect_type == EBuffEffectLogicPbType::BuffEffectBuffChange as i32 {
                     if let Ok(change_info) = BuffChange::decode(raw.as_slice()) {
                         if let Some(entry) = self.active_buffs.get_mut(&buff_uuid) {
                             let base_id = entry.base_id;
                             let source_config_id = entry.source_config_id;
+                            let caster_uid = entry.caster_uid;
                             if let Some(layer) = change_info.layer {
                                 entry.layer = layer;
                             }
@@ -150,6 +135,7 @@ impl BuffMonitor {
                                 create_time_ms: Some(entry.received_time_ms),
                                 duration_ms: Some(entry.duration),
                                 source_config_id,
+                                caster_uid,
                             });
                         }
                     }
@@ -166,6 +152,7 @@ impl BuffMonitor {
                         create_time_ms: Some(removed_buff.received_time_ms),
                         duration_ms: Some(removed_buff.duration),
                         source_config_id: removed_buff.source_config_id,
+                        caster_uid: removed_buff.caster_uid,
                     });
                 }
             }
@@ -178,6 +165,106 @@ impl BuffMonitor {
         }
     }
 
+    /// Replaces the active buffs with the full buff list from a `BuffInfoSync`.
+    ///
+    /// Buffs already being tracked keep their receive time; buffs missing from the list are
+    /// reported as removed.
+    pub(crate) fn apply_buff_info_sync(
+        &mut self,
+        buff_infos: &[BuffInfo],
+        server_clock_offset: &mut i64,
+        local_player_uid: i64,
+    ) -> BuffProcessResult {
+        let now = now_ms();
+        let mut changes = Vec::new();
+        let mut previous = std::mem::take(&mut self.active_buffs);
+
+        for buff_info in buff_infos {
+            let Some(buff_uuid) = buff_info.buff_uuid else {
+                continue;
+            };
+            let Some(mut buff) =
+                self.active_buff_from_info(buff_info, now, server_clock_offset, local_player_uid)
+            else {
+                continue;
+            };
+            let change_type = match previous.remove(&buff_uuid) {
+                Some(existing) => {
+                    buff.received_time_ms = existing.received_time_ms;
+                    BuffChangeType::Changed
+                }
+                None => BuffChangeType::Added,
+            };
+            changes.push(BuffChangeEvent {
+                base_id: buff.base_id,
+                buff_uuid,
+                change_type,
+                create_time_ms: Some(buff.received_time_ms),
+                duration_ms: Some(buff.duration),
+                source_config_id: buff.source_config_id,
+                caster_uid: buff.caster_uid,
+            });
+            self.active_buffs.insert(buff_uuid, buff);
+        }
+
+        for (buff_uuid, removed_buff) in previous {
+            changes.push(BuffChangeEvent {
+                base_id: removed_buff.base_id,
+                buff_uuid,
+                change_type: BuffChangeType::Removed,
+                create_time_ms: Some(removed_buff.received_time_ms),
+                duration_ms: Some(removed_buff.duration),
+                source_config_id: removed_buff.source_config_id,
+                caster_uid: removed_buff.caster_uid,
+            });
+        }
+
+        let update_payload = self.build_update_payload(*server_clock_offset);
+        BuffProcessResult {
+            update_payload,
+            changes,
+        }
+    }
+
+    /// Converts a `BuffInfo` into a tracked buff, or `None` if it should be ignored
+    /// (no base ID, or a self-applied buff cast by someone else).
+    fn active_buff_from_info(
+        &self,
+        buff_info: &BuffInfo,
+        now: i64,
+        server_clock_offset: &mut i64,
+        local_player_uid: i64,
+    ) -> Option<ActiveBuff> {
+        let base_id = buff_info.base_id?;
+        let fire_uid = buff_info.fire_uuid.unwrap_or(0) >> 16;
+        if self.self_applied_buff_ids.contains(&base_id) && fire_uid != local_player_uid {
+            return None;
+        }
+        let create_time = buff_info.create_time.unwrap_or(now);
+        if buff_info.create_time.is_some() {
+            *server_clock_offset = now - create_time;
+        }
+        Some(ActiveBuff {
+            base_id,
+            layer: buff_info.layer.unwrap_or(1),
+            duration: buff_info.duration.unwrap_or(0),
+            create_time,
+            received_time_ms: now,
+            source_config_id: buff_info
+                .fight_source_info
+                .as_ref()
+                .and_then(|info| info.source_config_id),
+            caster_uid: (fire_uid != 0).then_some(fire_uid),
+        })
+    }
+
+    /// Whether buffs with this base ID are shown to the user.
+    pub(crate) fn is_monitored(&self, base_id: i32) -> bool {
+        self.monitor_all_buff
+            || self.monitored_buff_ids.contains(&base_id)
+            || self.self_applied_buff_ids.contains(&base_id)
+    }
+
     fn build_update_payload(&self, server_clock_offset: i64) -> Option<Vec<BuffUpdateState>> {
         if self.monitored_buff_ids.is_empty()
             && self.self_applied_buff_ids.is_empty()
@@ -189,11 +276,7 @@ impl BuffMonitor {
         Some(
             self.active_buffs
                 .values()
-                .filter(|buff| {
-                    self.monitor_all_buff
-                        || self.monitored_buff_ids.contains(&buff.base_id)
-                        || self.self_applied_buff_ids.contains(&buff.base_id)
-                })
+                .filter(|buff| self.is_monitored(buff.base_id))
                 .map(|buff| BuffUpdateState {
                     base_id: buff.base_id,
                     layer: buff.layer,
@@ -208,6 +291,8 @@ impl BuffMonitor {
 #[derive(Debug, Default)]
 pub struct BossBuffMonitors {
     pub monitors: HashMap<i64, BuffMonitor>,
+    /// Debuff uptime and applications per boss in the current encounter.
+    pub debuff_trackers: HashMap<i64, BuffUptimeTracker>,
     pub monitored_buff_ids: HashSet<i32>,
     pub self_applied_buff_ids: HashSet<i32>,
 }
@@ -219,6 +304,7 @@ impl BossBuffMonitors {
 
     pub(crate) fn clear(&mut self) {
         self.monitors.clear();
+        self.debuff_trackers.clear();
     }
 
     pub(crate) fn set_config(&mut self, global_ids: Vec<i32>, self_applied_ids: Vec<i32>) {
@@ -243,6 +329,63 @@ impl BossBuffMonitors {
         })
     }
 
+    /// Credits debuff uptime on a boss up to now. Call before its monitor's buffs change.
+    pub(crate) fn advance_debuff_uptime(&mut self, boss_uid: i64, window: UptimeWindow) {
+        if let Some(monitor) = self.monitors.get(&boss_uid) {
+            self.debuff_trackers
+                .entry(boss_uid)
+                .or_default()
+                .advance(&monitor.active_buffs, window);
+        }
+    }
+
+    /// Applies a buff effect packet for a boss, keeping its debuff tracker up to date.
+    pub(crate) fn process_buff_effect_bytes(
+        &mut self,
+        boss_uid: i64,
+        raw_bytes: &[u8],
+        server_clock_offset: &mut i64,
+        local_player_uid: i64,
+        window: UptimeWindow,
+    ) {
+        self.advance_debuff_uptime(boss_uid, window);
+        let result = self.monitor_for(boss_uid).process_buff_effect_bytes(
+            raw_bytes,
+            server_clock_offset,
+            local_player_uid,
+        );
+        self.debuff_trackers
+            .entry(boss_uid)
+            .or_default()
+            .record_applications(&result.changes, window.start_ms);
+    }
+
+    /// Replaces a boss's buffs from a `BuffInfoSync`, keeping its debuff tracker up to date.
+    pub(crate) fn apply_buff_info_sync(
+        &mut self,
+        boss_uid: i64,
+        buff_infos: &[BuffInfo],
+        server_clock_offset: &mut i64,
+        local_player_uid: i64,
+        window: UptimeWindow,
+    ) {
+        self.advance_debuff_uptime(boss_uid, window);
+        let result = self.monitor_for(boss_uid).apply_buff_info_sync(
+            buff_infos,
+            server_clock_offset,
+            local_player_uid,
+        );
+        // A sync lists every buff again; only the ones it adds are new applications.
+        let added = result
+            .changes
+            .iter()
+            .filter(|change| change.change_type == BuffChangeType::Added);
+        self.debuff_trackers
+            .entry(boss_uid)
+            .or_default()
+            .record_applications(added, window.start_ms);
+    }
+
     pub(crate) fn build_all_buff_snapshots(
         &self,
         server_clock_offset: i64,
@@ -0,0 +1,332 @@
+use crate::live::buff_monitor::{ActiveBuff, BuffChangeEvent, BuffChangeType};
+use crate::live::opcodes_models::Encounter;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Cumulative uptime of one buff over an encounter.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuffUptimeEntry {
+    pub base_id: i32,
+    /// Time with at least one instance of the buff up, up to the last combat packet.
+    pub uptime_ms: u64,
+    /// Average stack count while the buff was up.
+    pub average_layer: f64,
+    /// Uptime relative to the encounter duration, 0-100.
+    pub uptime_percent: f64,
+    /// Share of active combat time the buff was up for, 0-100. Idle gaps don't count.
+    pub active_uptime_percent: f64,
+}
+
+/// How often one caster applied or refreshed a buff during an encounter.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuffApplication {
+    pub base_id: i32,
+    /// Entity that applied the buff, when the server reported it.
+    pub caster_uid: Option<i64>,
+    /// Name of the caster if it was a player in the encounter.
+    pub caster_name: Option<String>,
+    /// Skill or other config the buff came from, when known.
+    pub source_config_id: Option<i32>,
+    pub count: u32,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct UptimeTotals {
+    uptime_ms: u64,
+    /// Uptime weighted by stack count, for the average layer.
+    layer_ms: u64,
+    /// Uptime that fell within active combat time.
+    active_ms: u64,
+}
+
+/// The encounter timeline buff uptime is accounted against.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UptimeWindow {
+    /// Fight start; 0 while no encounter is running.
+    pub start_ms: i64,
+    /// Last combat packet. Time after it isn't credited until combat carries on.
+    pub last_combat_ms: i64,
+    /// Active combat time of the encounter so far.
+    pub active_combat_ms: u128,
+}
+
+impl UptimeWindow {
+    pub fn of(encounter: &Encounter) -> Self {
+        Self {
+            start_ms: encounter.time_fight_start_ms as i64,
+            last_combat_ms: encounter.time_last_combat_packet_ms as i64,
+            active_combat_ms: encounter.active_combat_time_ms,
+        }
+    }
+
+    fn duration_ms(&self) -> u64 {
+        u64::try_from(self.last_combat_ms.saturating_sub(self.start_ms)).unwrap_or(0)
+    }
+}
+
+/// Accumulates how long each buff of a `BuffMonitor` was up during the current encounter.
+///
+/// The monitor's active buffs are the source of truth: call `advance` before they change so
+/// the elapsed time is credited to the buffs that were up during it.
+#[derive(Debug, Default)]
+pub struct BuffUptimeTracker {
+    totals: HashMap<i32, UptimeTotals>,
+    /// Applications by base ID, caster and source config.
+    applications: HashMap<(i32, Option<i64>, Option<i32>), u32>,
+    /// Fight start the totals belong to; 0 while no encounter is running.
+    window_start_ms: i64,
+    /// Time up to which the totals are accumulated.
+    accounted_until_ms: i64,
+    /// Active combat time already credited in the current encounter.
+    accounted_active_ms: u128,
+}
+
+impl BuffUptimeTracker {
+    /// Credits the time since the last call to the buffs in `active_buffs`.
+    ///
+    /// Time is credited from the fight start up to the last combat packet, and active combat
+    /// time is credited as the encounter accrues it. A different window start than last time
+    /// means a new encounter, which drops the previous totals.
+    pub fn advance(&mut self, active_buffs: &HashMap<i32, ActiveBuff>, window: UptimeWindow) {
+        self.sync_window(window.start_ms);
+        if window.start_ms == 0 {
+            return;
+        }
+        let from = self.accounted_until_ms.max(window.start_ms);
+        let elapsed = u64::try_from(window.last_combat_ms.saturating_sub(from)).unwrap_or(0);
+        let active_elapsed = u64::try_from(
+            window
+                .active_combat_ms
+                .saturating_sub(self.accounted_active_ms),
+        )
+        .unwrap_or(u64::MAX);
+        if elapsed > 0 || active_elapsed > 0 {
+            let mut layers: HashMap<i32, i32> = HashMap::new();
+            for buff in active_buffs.values() {
+                let layer = layers.entry(buff.base_id).or_default();
+                *layer = (*layer).max(buff.layer.max(1));
+            }
+            for (base_id, layer) in layers {
+                let totals = self.totals.entry(base_id).or_default();
+                totals.uptime_ms = totals.uptime_ms.saturating_add(elapsed);
+                totals.layer_ms = totals
+                    .layer_ms
+                    .saturating_add(elapsed.saturating_mul(layer as u64));
+                totals.active_ms = totals.active_ms.saturating_add(active_elapsed);
+            }
+        }
+        self.accounted_until_ms = self.accounted_until_ms.max(window.last_combat_ms);
+        self.accounted_active_ms = self.accounted_active_ms.max(window.active_combat_ms);
+    }
+
+    fn sync_window(&mut self, window_start_ms: i64) {
+        if window_start_ms != self.window_start_ms {
+            self.totals.clear();
+            self.applications.clear();
+            self.window_start_ms = window_start_ms;
+            self.accounted_active_ms = 0;
+        }
+    }
+
+    /// Counts the buffs added or refreshed in `changes` while an encounter is running, each
+    /// under the caster its change event names.
+    pub fn record_applications<'a>(
+        &mut self,
+        changes: impl IntoIterator<Item = &'a BuffChangeEvent>,
+        window_start_ms: i64,
+    ) {
+        self.sync_window(window_start_ms);
+        if window_start_ms == 0 {
+            return;
+        }
+        for change in changes {
+            if matches!(
+                change.change_type,
+                BuffChangeType::Added | BuffChangeType::Changed
+            ) {
+                let key = (change.base_id, change.caster_uid, change.source_config_id);
+                *self.applications.entry(key).or_default() += 1;
+            }
+        }
+    }
+
+    /// Applications of the buffs accepted by `include`, by base ID and then most frequent
+    /// caster. `caster_name` resolves player names.
+    pub fn applications(
+        &self,
+        include: impl Fn(i32) -> bool,
+        caster_name: impl Fn(i64) -> Option<String>,
+    ) -> Vec<BuffApplication> {
+        let mut applications: Vec<BuffApplication> = self
+            .applications
+            .iter()
+            .filter(|&(&(base_id, _, _), _)| include(base_id))
+            .map(
+                |(&(base_id, caster_uid, source_config_id), &count)| BuffApplication {
+                    base_id,
+                    caster_uid,
+                    caster_name: caster_uid.and_then(&caster_name),
+                    source_config_id,
+                    count,
+                },
+            )
+            .collect();
+        applications.sort_by(|a, b| {
+            a.base_id
+                .cmp(&b.base_id)
+                .then(b.count.cmp(&a.count))
+                .then(a.caster_uid.cmp(&b.caster_uid))
+                .then(a.source_config_id.cmp(&b.source_config_id))
+        });
+        applications
+    }
+
+    /// Uptime of the buffs accepted by `include` within `window`, longest first.
+    ///
+    /// Call `advance` with the same window first so the totals are up to date.
+    pub fn entries(
+        &self,
+        include: impl Fn(i32) -> bool,
+        window: UptimeWindow,
+    ) -> Vec<BuffUptimeEntry> {
+        let percent_of = |part_ms: u64, total_ms: u128| {
+            if total_ms == 0 {
+                0.0
+            } else {
+                part_ms as f64 * 100.0 / total_ms as f64
+            }
+        };
+        let duration_ms = u128::from(window.duration_ms());
+        let mut entries: Vec<BuffUptimeEntry> = self
+            .totals
+            .iter()
+            .filter(|&(&base_id, totals)| totals.uptime_ms > 0 && include(base_id))
+            .map(|(&base_id, totals)| BuffUptimeEntry {
+                base_id,
+                uptime_ms: totals.uptime_ms,
+                average_layer: totals.layer_ms as f64 / totals.uptime_ms as f64,
+                uptime_percent: percent_of(totals.uptime_ms, duration_ms),
+                active_uptime_percent: percent_of(totals.active_ms, window.active_combat_ms),
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.uptime_ms
+                .cmp(&a.uptime_ms)
+                .then(a.base_id.cmp(&b.base_id))
+        });
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuffUptimeTracker, UptimeWindow};
+    use crate::live::buff_monitor::{ActiveBuff, BuffChangeEvent, BuffChangeType};
+    use std::collections::HashMap;
+
+    fn buff(base_id: i32, layer: i32) -> ActiveBuff {
+        ActiveBuff {
+            base_id,
+            layer,
+            duration: 0,
+            create_time: 0,
+            received_time_ms: 0,
+            source_config_id: None,
+            caster_uid: None,
+        }
+    }
+
+    fn window(start_ms: i64, last_combat_ms: i64, active_combat_ms: u128) -> UptimeWindow {
+        UptimeWindow {
+            start_ms,
+            last_combat_ms,
+            active_combat_ms,
+        }
+    }
+
+    #[test]
+    fn accumulates_uptime_and_layers_within_the_encounter() {
+        let mut tracker = BuffUptimeTracker::default();
+        let mut active = HashMap::new();
+
+        // Up before the fight starts: only counts from the fight start.
+        active.insert(1, buff(100, 1));
+        tracker.advance(&active, window(0, 0, 0));
+        tracker.advance(&active, window(1_000, 2_000, 1_000));
+        active.insert(1, buff(100, 3));
+        active.insert(2, buff(200, 1));
+        tracker.advance(&active, window(1_000, 4_000, 1_500));
+        active.remove(&2);
+        // Nothing is credited past the last combat packet.
+        tracker.advance(&active, window(1_000, 4_000, 1_500));
+        tracker.advance(&active, window(1_000, 5_000, 2_000));
+
+        let entries = tracker.entries(|_| true, window(1_000, 5_000, 2_000));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].base_id, 100);
+        assert_eq!(entries[0].uptime_ms, 4_000);
+        assert_eq!(entries[0].average_layer, 2.5);
+        assert_eq!(entries[0].uptime_percent, 100.0);
+        assert_eq!(entries[0].active_uptime_percent, 100.0);
+        assert_eq!(entries[1].base_id, 200);
+        assert_eq!(entries[1].uptime_ms, 2_000);
+        assert_eq!(entries[1].uptime_percent, 50.0);
+        // Up for 2s of the fight but only 0.5s of active combat.
+        assert_eq!(entries[1].active_uptime_percent, 25.0);
+        assert_eq!(
+            tracker
+                .entries(|id| id == 200, window(1_000, 5_000, 2_000))
+                .len(),
+            1
+        );
+
+        // A new encounter starts from scratch.
+        tracker.advance(&active, window(5_500, 6_000, 500));
+        let entries = tracker.entries(|_| true, window(5_500, 6_000, 500));
+        assert_eq!(entries[0].uptime_ms, 500);
+        assert_eq!(entries[0].active_uptime_percent, 100.0);
+    }
+
+    #[test]
+    fn attributes_applications_to_casters() {
+        let change = |change_type, caster_uid| BuffChangeEvent {
+            base_id: 300,
+            buff_uuid: 1,
+            change_type,
+            create_time_ms: None,
+            duration_ms: None,
+            source_config_id: Some(9),
+            caster_uid,
+        };
+        let mut tracker = BuffUptimeTracker::default();
+        // Outside an encounter nothing is counted.
+        tracker.record_applications(&[change(BuffChangeType::Added, Some(1))], 0);
+        tracker.record_applications(
+            &[
+                change(BuffChangeType::Added, Some(1)),
+                // Refreshes count for the caster named on the change.
+                change(BuffChangeType::Changed, Some(1)),
+                change(BuffChangeType::Changed, Some(1)),
+                change(BuffChangeType::Added, Some(2)),
+                change(BuffChangeType::Changed, Some(2)),
+                change(BuffChangeType::Removed, Some(2)),
+                change(BuffChangeType::Added, None),
+            ],
+            1_000,
+        );
+
+        let applications =
+            tracker.applications(|_| true, |uid| (uid == 2).then(|| "Beta".to_string()));
+        assert_eq!(applications.len(), 3);
+        assert_eq!(applications[0].caster_uid, Some(1));
+        assert_eq!(applications[0].count, 3);
+        assert_eq!(applications[1].caster_uid, Some(2));
+        assert_eq!(applications[1].caster_name.as_deref(), Some("Beta"));
+        assert_eq!(applications[1].count, 2);
+        assert_eq!(applications[2].caster_uid, None);
+        assert_eq!(applications[2].count, 1);
+        assert!(tracker.applications(|id| id != 300, |_| None).is_empty());
+    }
+}
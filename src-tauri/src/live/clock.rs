@@ -0,0 +1,63 @@
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+thread_local! {
+    /// Capture time of the packet being replayed on this thread, if any.
+    static REPLAY_NOW_MS: Cell<Option<i64>> = const { Cell::new(None) };
+}
+
+/// Current time for the meter as Unix milliseconds.
+///
+/// Reads the wall clock unless a [`ReplayTimeGuard`] on this thread has pinned it to the capture
+/// time of the packet being replayed.
+pub fn now_ms() -> i64 {
+    REPLAY_NOW_MS.with(Cell::get).unwrap_or_else(|| {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_millis() as i64,
+            Err(_) => 0,
+        }
+    })
+}
+
+/// Pins [`now_ms`] on the current thread to packet capture times until dropped.
+#[derive(Default)]
+pub struct ReplayTimeGuard {
+    /// Keeps the guard on the thread whose clock it pins.
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl ReplayTimeGuard {
+    /// Moves the clock to `captured_at_ms`, never backwards.
+    pub fn advance_to(&self, captured_at_ms: u64) {
+        let captured_at_ms = captured_at_ms as i64;
+        REPLAY_NOW_MS.with(|now| {
+            let current = now.get().unwrap_or(i64::MIN);
+            now.set(Some(current.max(captured_at_ms)));
+        });
+    }
+}
+
+impl Drop for ReplayTimeGuard {
+    fn drop(&mut self) {
+        REPLAY_NOW_MS.with(|now| now.set(None));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay_guard_pins_clock_to_capture_time() {
+        {
+            let guard = ReplayTimeGuard::default();
+            guard.advance_to(1_000);
+            assert_eq!(now_ms(), 1_000);
+            guard.advance_to(900);
+            assert_eq!(now_ms(), 1_000);
+            guard.advance_to(2_500);
+            assert_eq!(now_ms(), 2_500);
+        }
+        assert!(now_ms() > 2_500);
+    }
+}
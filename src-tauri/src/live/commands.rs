@@ -1,11 +1,14 @@
 use crate::WINDOW_LIVE_LABEL;
 use crate::live::bootstrap_snapshot::{MonitorRuntimeSnapshot, save_monitor_runtime_snapshot};
+use crate::live::commands_models::{CaptureHealth, PacketDropStats};
+use crate::live::data_tables::{DataTableStatus, data_table_statuses, reload_data_tables};
 use crate::live::state::{AppStateManager, StateEvent};
-use crate::live::training_dummy::TrainingDummyMonsterId;
+use crate::live::training_dummy::{TrainingDummyMonsterId, TrainingDummyOptions};
+use crate::packets::capture_status::{CaptureStatus, capture_status};
+use crate::packets::packet_capture::request_restart;
 use log::info;
 use tauri::Manager;
 use window_vibrancy::{apply_blur, clear_blur};
-// request_restart is not needed in this module at present
 
 /// Enables blur on the live meter window.
 ///
@@ -106,10 +109,13 @@ pub fn toggle_pause_encounter(
 #[specta::specta]
 pub fn start_training_dummy(
     monster_id: i32,
+    options: Option<TrainingDummyOptions>,
     state_manager: tauri::State<'_, AppStateManager>,
 ) -> Result<(), String> {
     let monster_id = TrainingDummyMonsterId::try_from(monster_id).map_err(|err| err.to_string())?;
-    state_manager.start_training_dummy(monster_id)?;
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    state_manager.start_training_dummy(monster_id, options)?;
     Ok(())
 }
 
@@ -132,3 +138,126 @@ pub fn save_and_apply_monitor_runtime_snapshot(
     state_manager.apply_monitor_runtime_snapshot(snapshot)?;
     Ok(())
 }
+
+/// Starts teeing the decoded packet stream into a session file.
+///
+/// If `path` is omitted the file is created under `<app log dir>/sessions`.
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The path of the session file being written.
+#[tauri::command]
+#[specta::specta]
+pub fn start_session_recording(
+    path: Option<String>,
+    app_handle: tauri::AppHandle,
+    state_manager: tauri::State<'_, AppStateManager>,
+) -> Result<String, String> {
+    let path = match path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let log_dir = app_handle
+                .path()
+                .app_log_dir()
+                .map_err(|e| format!("Failed to get log dir: {e}"))?;
+            let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+            log_dir
+                .join("sessions")
+                .join(format!("session_{timestamp}.rlsession"))
+        }
+    };
+    state_manager.start_session_recording(path.clone())?;
+    info!("session recording requested path={}", path.display());
+    Ok(path.display().to_string())
+}
+
+/// Stops the active session recording, if any, and finalizes the file.
+#[tauri::command]
+#[specta::specta]
+pub fn stop_session_recording(
+    state_manager: tauri::State<'_, AppStateManager>,
+) -> Result<(), String> {
+    state_manager.stop_session_recording()?;
+    Ok(())
+}
+
+/// Returns how many packets the capture queue has dropped or coalesced under load.
+///
+/// # Returns
+///
+/// * `Result<PacketDropStats, String>` - Per-opcode loss counts since the app started.
+#[tauri::command]
+#[specta::specta]
+pub fn get_packet_drop_stats() -> Result<PacketDropStats, String> {
+    Ok(PacketDropStats::from(
+        crate::packets::packet_queue::queue_stats(),
+    ))
+}
+
+/// Returns capture quality counters: traffic seen, TCP gaps and resets, decompression and
+/// decode failures, and packet queue losses.
+///
+/// # Returns
+///
+/// * `Result<CaptureHealth, String>` - Counters accumulated since the app started.
+#[tauri::command]
+#[specta::specta]
+pub fn get_capture_health() -> Result<CaptureHealth, String> {
+    Ok(CaptureHealth::current())
+}
+
+/// Returns the capture thread status: starting, running, degraded, failed or finished.
+///
+/// # Returns
+///
+/// * `Result<CaptureStatus, String>` - The current status.
+#[tauri::command]
+#[specta::specta]
+pub fn get_capture_status() -> Result<CaptureStatus, String> {
+    Ok(capture_status().snapshot())
+}
+
+/// Closes and reopens the capture source, skipping any pending restart backoff.
+///
+/// # Returns
+///
+/// * `Result<(), String>` - An error if no capture is running.
+#[tauri::command]
+#[specta::specta]
+pub fn restart_capture() -> Result<(), String> {
+    request_restart()?;
+    info!("Packet capture restart requested");
+    Ok(())
+}
+
+/// Returns where each meter-data table was loaded from and any files that failed to load.
+///
+/// # Returns
+///
+/// * `Result<Vec<DataTableStatus>, String>` - One status per table.
+#[tauri::command]
+#[specta::specta]
+pub fn get_meter_data_status() -> Result<Vec<DataTableStatus>, String> {
+    Ok(data_table_statuses())
+}
+
+/// Reloads the meter-data tables from the user override directory and the bundled files, so
+/// updated game data applies without a restart. Tables that fail to load keep their previous
+/// contents.
+///
+/// # Returns
+///
+/// * `Result<Vec<DataTableStatus>, String>` - One status per table after the reload.
+#[tauri::command]
+#[specta::specta]
+pub fn reload_meter_data() -> Result<Vec<DataTableStatus>, String> {
+    let statuses = reload_data_tables();
+    info!(
+        "Meter data reloaded ({} tables with errors)",
+        statuses
+            .iter()
+            .filter(|status| !status.errors.is_empty())
+            .count()
+    );
+    Ok(statuses)
+}
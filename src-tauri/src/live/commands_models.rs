@@ -1,6 +1,15 @@
+use crate::live::aggro_history::{AggroChange, AggroHold};
+use crate::live::boss_ttk::{EnrageTimer, TimeToKill};
+use crate::live::buff_uptime::{BuffApplication, BuffUptimeEntry};
+use crate::live::life_timeline::LifeSummary;
 use crate::live::opcodes_models::SkillTargetStats;
-use crate::live::opcodes_models::{CombatStats, Skill};
+use crate::live::opcodes_models::{CombatStats, Skill, SummonDamage};
+use crate::live::phases::{EncounterPhase, PhaseCombatStats};
+use crate::live::rolling_window::RollingWindowStats;
+use crate::live::rotation_log::RotationEntry;
 use crate::live::training_dummy::TrainingDummyPhase;
+use crate::packets::capture_stats::capture_stats;
+use crate::packets::packet_queue::{PacketQueueStats, queue_stats};
 use std::collections::HashMap;
 
 /// Represents the health of a boss.
@@ -17,6 +26,10 @@ pub struct BossHealth {
     pub max_hp: Option<i64>,
     /// Whether the boss is in ActorStateDead.
     pub is_dead: bool,
+    /// Estimated time until the boss dies at the recent HP loss rate.
+    pub time_to_kill: Option<TimeToKill>,
+    /// Enrage timer, if one is configured for the monster.
+    pub enrage: Option<EnrageTimer>,
 }
 
 /// Represents the header information for an encounter.
@@ -60,6 +73,8 @@ pub struct LiveDataPayload {
     pub is_paused: bool,
     pub bosses: Vec<BossHealth>,
     pub entities: Vec<RawEntityData>,
+    /// Phases of the current encounter, in order.
+    pub phases: Vec<EncounterPhase>,
 }
 
 #[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone, PartialEq)]
@@ -86,6 +101,13 @@ pub struct RawEntityData {
     pub dmg_skills: HashMap<i64, RawSkillStats>,
     pub heal_skills: HashMap<i64, RawSkillStats>,
     pub taken_skills: HashMap<i64, RawSkillStats>,
+    /// Stats per encounter phase; entries after the last phase the player was active in
+    /// are left out.
+    pub phases: Vec<RawPhaseStats>,
+    /// Part of `damage` dealt through summons.
+    pub summon_damage: RawCombatStats,
+    /// Recent damage, healing and taken per second, one entry per configured window.
+    pub rolling: Vec<RollingWindowStats>,
 }
 
 #[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
@@ -109,6 +131,35 @@ pub struct HistoryEntityData {
     pub dmg_per_target: Vec<PerTargetStats>,
     pub heal_per_target: Vec<PerTargetStats>,
     pub deaths: Vec<DeathRecord>,
+    /// Deaths and time spent dead within the encounter.
+    pub life: LifeSummary,
+    /// Stats per encounter phase, indexed like `EncounterSummaryDto::phases`.
+    pub phases: Vec<RawPhaseStats>,
+    /// Uptime of the monitored buffs; only recorded for the local player.
+    pub buff_uptime: Vec<BuffUptimeEntry>,
+    /// Part of `damage` dealt through summons.
+    pub summon_damage: RawCombatStats,
+}
+
+/// Damage, healing and damage taken of one entity per time bucket.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityTimeSeries {
+    pub uid: i64,
+    pub damage: Vec<u64>,
+    pub healing: Vec<u64>,
+    pub taken: Vec<u64>,
+}
+
+/// Time series of a historical encounter. Every entity has the same number of buckets.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterTimeSeries {
+    /// Width of one bucket in milliseconds.
+    pub bucket_ms: u32,
+    /// Unix time of the first bucket; bucket `i` starts at `start_ms + i * bucket_ms`.
+    pub start_ms: u64,
+    pub entities: Vec<EntityTimeSeries>,
 }
 
 #[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
@@ -123,6 +174,15 @@ pub struct RawCombatStats {
     pub lucky_total: u128,
 }
 
+/// Damage, healing and damage taken of one entity within one encounter phase.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RawPhaseStats {
+    pub damage: RawCombatStats,
+    pub healing: RawCombatStats,
+    pub taken: RawCombatStats,
+}
+
 #[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RawSkillStats {
@@ -135,6 +195,21 @@ pub struct RawSkillStats {
     pub lucky_total_value: u128,
     pub property: Option<i32>,
     pub damage_mode: Option<i32>,
+    /// Part of the damage dealt through summons, by summon kind.
+    pub summons: Vec<RawSummonStats>,
+}
+
+/// Damage a skill dealt through one kind of summon.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RawSummonStats {
+    /// Entity type of the summon, as an `EEntityType` value.
+    pub entity_type: i32,
+    pub monster_id: Option<i32>,
+    /// UID of the summon that hit last.
+    pub last_uid: i64,
+    pub hits: u128,
+    pub total_value: u128,
 }
 
 #[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
@@ -159,6 +234,17 @@ pub fn to_raw_combat_stats(stats: &CombatStats) -> RawCombatStats {
     }
 }
 
+pub fn to_raw_phase_stats(stats: &[PhaseCombatStats]) -> Vec<RawPhaseStats> {
+    stats
+        .iter()
+        .map(|phase| RawPhaseStats {
+            damage: to_raw_combat_stats(&phase.damage),
+            healing: to_raw_combat_stats(&phase.healing),
+            taken: to_raw_combat_stats(&phase.taken),
+        })
+        .collect()
+}
+
 pub fn to_raw_skill_stats(skill: &Skill) -> RawSkillStats {
     RawSkillStats {
         total_value: skill.total_value,
@@ -170,6 +256,17 @@ pub fn to_raw_skill_stats(skill: &Skill) -> RawSkillStats {
         lucky_total_value: skill.lucky_total_value,
         property: skill.property,
         damage_mode: skill.damage_mode,
+        summons: skill.summons.iter().map(to_raw_summon_stats).collect(),
+    }
+}
+
+fn to_raw_summon_stats(summon: &SummonDamage) -> RawSummonStats {
+    RawSummonStats {
+        entity_type: summon.entity_type,
+        monster_id: summon.monster_id,
+        last_uid: summon.last_uid,
+        hits: summon.hits,
+        total_value: summon.total_value,
     }
 }
 
@@ -204,6 +301,7 @@ pub fn build_per_target_stats(
                 lucky_total_value: stats.lucky_total,
                 property: None,
                 damage_mode: None,
+                summons: Vec::new(),
             },
         );
         entry.total_value += stats.total_value;
@@ -268,6 +366,54 @@ pub struct BuffUpdatePayload {
     pub buffs: Vec<BuffUpdateState>,
 }
 
+/// Debuff coverage on one boss of a saved encounter.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BossDebuffSummary {
+    pub uid: i64,
+    pub monster_id: Option<i32>,
+    pub name: String,
+    /// Uptime of each monitored debuff over the encounter, longest first.
+    pub debuff_uptime: Vec<BuffUptimeEntry>,
+    /// Debuff applications by caster.
+    pub applications: Vec<BuffApplication>,
+}
+
+/// Aggro history of one boss in a saved encounter.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BossAggroSummary {
+    pub uid: i64,
+    pub monster_id: Option<i32>,
+    pub name: String,
+    /// Top-threat holder changes in time order. The first one may be from before the encounter
+    /// started.
+    pub changes: Vec<AggroChange>,
+    /// Time each player held aggro during the encounter, longest first.
+    pub holds: Vec<AggroHold>,
+}
+
+/// Skill rotation of the local player in a saved encounter.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterRotation {
+    pub uid: i64,
+    pub name: String,
+    /// Unix time the encounter started; casts before it belong to the opener.
+    pub start_ms: u64,
+    /// Whether the encounter had more casts than were kept.
+    pub truncated: bool,
+    pub entries: Vec<RotationEntry>,
+}
+
+/// Uptime of the monitored buffs on one player in the current encounter.
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuffUptimeUpdatePayload {
+    pub uid: i64,
+    pub entries: Vec<BuffUptimeEntry>,
+}
+
 #[derive(serde::Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BossBuffUpdatePayload {
@@ -424,3 +570,106 @@ pub struct DeathRecord {
 pub struct DeathReplayPayload {
     pub records: Vec<DeathRecord>,
 }
+
+/// Packets the capture queue discarded under backpressure, cumulative for this run.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PacketDropStats {
+    pub total_dropped: u64,
+    pub total_coalesced: u64,
+    pub by_opcode: Vec<PacketDropEntry>,
+}
+
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PacketDropEntry {
+    /// `Pkt` variant name, e.g. `SyncNearEntities`.
+    pub opcode: String,
+    pub dropped: u64,
+    pub coalesced: u64,
+}
+
+impl From<&PacketQueueStats> for PacketDropStats {
+    fn from(stats: &PacketQueueStats) -> Self {
+        let by_opcode: Vec<PacketDropEntry> = stats
+            .snapshot()
+            .into_iter()
+            .map(|(op, counts)| PacketDropEntry {
+                opcode: format!("{op:?}"),
+                dropped: counts.dropped,
+                coalesced: counts.coalesced,
+            })
+            .collect();
+        Self {
+            total_dropped: by_opcode.iter().map(|e| e.dropped).sum(),
+            total_coalesced: by_opcode.iter().map(|e| e.coalesced).sum(),
+            by_opcode,
+        }
+    }
+}
+
+/// Capture quality counters, cumulative for this run.
+///
+/// Lets the UI tell a lossy capture apart from a meter bug.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureHealth {
+    pub packets_seen: u64,
+    pub bytes_captured: u64,
+    pub gaps_skipped: GapSkipStats,
+    pub sequence_regression_resets: u64,
+    pub syn_resets: u64,
+    /// Streams reset by a FIN or RST.
+    pub fin_resets: u64,
+    pub zstd_failures: u64,
+    pub service_uuid_mismatches: u64,
+    /// Protobuf decode failures per opcode.
+    pub decode_failures: Vec<OpcodeCount>,
+    pub packet_queue: PacketDropStats,
+}
+
+/// TCP gaps given up on, by `GapSkipReason`.
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GapSkipStats {
+    pub buffered_bytes: u64,
+    pub wait_time: u64,
+    pub hard_limit: u64,
+}
+
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcodeCount {
+    pub opcode: String,
+    pub count: u64,
+}
+
+impl CaptureHealth {
+    /// Reads the current capture and packet queue counters.
+    pub fn current() -> Self {
+        let stats = capture_stats().snapshot();
+        Self {
+            packets_seen: stats.packets_seen,
+            bytes_captured: stats.bytes_captured,
+            gaps_skipped: GapSkipStats {
+                buffered_bytes: stats.gaps_skipped.buffered_bytes,
+                wait_time: stats.gaps_skipped.wait_time,
+                hard_limit: stats.gaps_skipped.hard_limit,
+            },
+            sequence_regression_resets: stats.sequence_regression_resets,
+            syn_resets: stats.syn_resets,
+            fin_resets: stats.fin_resets,
+            zstd_failures: stats.zstd_failures,
+            service_uuid_mismatches: stats.service_uuid_mismatches,
+            decode_failures: stats
+                .decode_failures
+                .into_iter()
+                .map(|(op, count)| OpcodeCount {
+                    opcode: format!("{op:?}"),
+                    count,
+                })
+                .collect(),
+            packet_queue: PacketDropStats::from(queue_stats()),
+        }
+    }
+}
@@ -0,0 +1,463 @@
+use crate::live::boss_ttk::parse_enrage_timers;
+use crate::live::damage_id::parse_skill_level_to_effect;
+use crate::live::monster_registry::{
+    MonsterType, parse_extra_buff_monitored_monster_ids, parse_monster_types,
+};
+use crate::live::name_registry::{NameTable, parse_name_table};
+use crate::live::reset_rules::{BUILT_IN_RESET_RULES, ResetRules, parse_reset_rules};
+use crate::live::skill_cd_monitor::{
+    CdTempAttrDef, parse_cd_temp_attr_defs, parse_skill_effect_tags,
+};
+use anyhow::{Context, Result, anyhow};
+use log::{info, warn};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock, RwLock};
+
+const METER_DATA_DIR: &str = "meter-data";
+
+/// Game data tables from `meter-data`, loaded together so they can be reloaded at runtime.
+#[derive(Debug, Default)]
+pub struct DataTables {
+    pub monster_types: HashMap<i32, MonsterType>,
+    pub extra_buff_monitored_monsters: HashSet<i32>,
+    /// Skill level ID to skill effect ID, used for damage skill keys.
+    pub skill_level_to_effect: HashMap<i32, i32>,
+    /// Temp attributes that change skill cooldowns, by temp attr ID.
+    pub cd_temp_attr_defs: HashMap<i32, CdTempAttrDef>,
+    /// Skill effect tags by skill level ID.
+    pub skill_effect_tags: HashMap<i32, Vec<i32>>,
+    pub reset_rules: ResetRules,
+    pub names_zh_cn: NameTable,
+    /// Optional English names; missing entries fall back to `names_zh_cn`.
+    pub names_en_us: NameTable,
+    /// Enrage time in seconds by monster type ID.
+    pub enrage_timers: HashMap<i32, u32>,
+}
+
+/// Where a table was loaded from.
+#[derive(specta::Type, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DataTableSource {
+    /// The user override directory.
+    UserOverride,
+    /// The `meter-data` files shipped with the app.
+    Bundled,
+    /// The copy compiled into the binary.
+    BuiltIn,
+    /// Every file failed to load on reload, so the previously loaded table was kept.
+    Previous,
+    /// No usable file was found; the table is empty. Not an error for optional tables.
+    Missing,
+}
+
+/// Load result of one meter-data table.
+#[derive(specta::Type, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableStatus {
+    pub file_name: String,
+    pub source: DataTableSource,
+    /// File the table was read from, if any.
+    pub path: Option<String>,
+    pub entries: u32,
+    /// Files that were skipped and why, e.g. an invalid user override.
+    pub errors: Vec<String>,
+}
+
+struct Registry {
+    tables: Arc<DataTables>,
+    statuses: Vec<DataTableStatus>,
+}
+
+/// Directory whose files replace the bundled ones, usually `<app data>/meter-data`.
+static USER_OVERRIDE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Set once the registry starts loading, so a late `init_data_tables` knows to reload.
+static REGISTRY_STARTED: AtomicBool = AtomicBool::new(false);
+
+static REGISTRY: LazyLock<RwLock<Registry>> = LazyLock::new(|| {
+    REGISTRY_STARTED.store(true, Ordering::SeqCst);
+    let (tables, statuses) = load_data_tables(USER_OVERRIDE_DIR.get().map(PathBuf::as_path), None);
+    RwLock::new(Registry {
+        tables: Arc::new(tables),
+        statuses,
+    })
+});
+
+/// Serializes reloads, which parse outside the registry lock.
+static RELOAD_LOCK: Mutex<()> = Mutex::new(());
+
+/// Sets the user override directory and loads the tables. If something already read the
+/// tables before the directory was known, they are reloaded so the overrides apply.
+pub fn init_data_tables(user_override_dir: PathBuf) {
+    if USER_OVERRIDE_DIR.set(user_override_dir).is_err() {
+        warn!(target: "app::live", "[data-tables] user override directory already set");
+    }
+    if REGISTRY_STARTED.load(Ordering::SeqCst) {
+        reload_data_tables();
+    } else {
+        LazyLock::force(&REGISTRY);
+    }
+}
+
+/// The currently loaded tables. Hold on to the returned `Arc` instead of calling this in a loop.
+pub fn data_tables() -> Arc<DataTables> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .tables
+        .clone()
+}
+
+pub fn data_table_statuses() -> Vec<DataTableStatus> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .statuses
+        .clone()
+}
+
+/// Reloads every table from disk. A table whose files all fail to load keeps its previous
+/// contents.
+pub fn reload_data_tables() -> Vec<DataTableStatus> {
+    let _reload = RELOAD_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let previous = data_tables();
+    let (tables, statuses) = load_data_tables(
+        USER_OVERRIDE_DIR.get().map(PathBuf::as_path),
+        Some(&previous),
+    );
+    let mut registry = REGISTRY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    registry.tables = Arc::new(tables);
+    registry.statuses = statuses.clone();
+    statuses
+}
+
+struct TableSpec<T> {
+    file_name: &'static str,
+    built_in: Option<&'static str>,
+    /// Whether a missing file is expected and not worth a warning.
+    optional: bool,
+    parse: fn(&str) -> Result<T>,
+    entries: fn(&T) -> usize,
+}
+
+fn load_data_tables(
+    user_dir: Option<&Path>,
+    previous: Option<&DataTables>,
+) -> (DataTables, Vec<DataTableStatus>) {
+    let mut statuses = Vec::with_capacity(9);
+
+    let (monster_types, status) = load_table(
+        &TableSpec {
+            file_name: "MonsterIdType.json",
+            built_in: Some(include_str!("../../meter-data/MonsterIdType.json")),
+            optional: false,
+            parse: parse_monster_types,
+            entries: HashMap::len,
+        },
+        user_dir,
+        previous.map(|tables| &tables.monster_types),
+    );
+    statuses.push(status);
+    let (extra_buff_monitored_monsters, status) = load_table(
+        &TableSpec {
+            file_name: "ExtraBuffMonitoredMonsters.json",
+            built_in: None,
+            optional: false,
+            parse: parse_extra_buff_monitored_monster_ids,
+            entries: HashSet::len,
+        },
+        user_dir,
+        previous.map(|tables| &tables.extra_buff_monitored_monsters),
+    );
+    statuses.push(status);
+    let (skill_level_to_effect, status) = load_table(
+        &TableSpec {
+            file_name: "SkillFightLevelTable.json",
+            built_in: None,
+            optional: false,
+            parse: parse_skill_level_to_effect,
+            entries: HashMap::len,
+        },
+        user_dir,
+        previous.map(|tables| &tables.skill_level_to_effect),
+    );
+    statuses.push(status);
+    let (cd_temp_attr_defs, status) = load_table(
+        &TableSpec {
+            file_name: "TempAttrTable.json",
+            built_in: None,
+            optional: false,
+            parse: parse_cd_temp_attr_defs,
+            entries: HashMap::len,
+        },
+        user_dir,
+        previous.map(|tables| &tables.cd_temp_attr_defs),
+    );
+    statuses.push(status);
+    let (skill_effect_tags, status) = load_table(
+        &TableSpec {
+            file_name: "SkillEffectTable.json",
+            built_in: None,
+            optional: false,
+            parse: parse_skill_effect_tags,
+            entries: HashMap::len,
+        },
+        user_dir,
+        previous.map(|tables| &tables.skill_effect_tags),
+    );
+    statuses.push(status);
+    let (reset_rules, status) = load_table(
+        &TableSpec {
+            file_name: "ResetRules.json",
+            built_in: Some(BUILT_IN_RESET_RULES),
+            optional: false,
+            parse: parse_reset_rules,
+            entries: |rules: &ResetRules| {
+                rules.wipe_buff_ids.len() + rules.ignored_targets.len() + rules.scenes.len()
+            },
+        },
+        user_dir,
+        previous.map(|tables| &tables.reset_rules),
+    );
+    statuses.push(status);
+    let (names_zh_cn, status) = load_table(
+        &TableSpec {
+            file_name: "Names.zh-CN.json",
+            built_in: None,
+            optional: false,
+            parse: parse_name_table,
+            entries: NameTable::entry_count,
+        },
+        user_dir,
+        previous.map(|tables| &tables.names_zh_cn),
+    );
+    statuses.push(status);
+    let (names_en_us, status) = load_table(
+        &TableSpec {
+            file_name: "Names.en-US.json",
+            built_in: None,
+            optional: true,
+            parse: parse_name_table,
+            entries: NameTable::entry_count,
+        },
+        user_dir,
+        previous.map(|tables| &tables.names_en_us),
+    );
+    statuses.push(status);
+    let (enrage_timers, status) = load_table(
+        &TableSpec {
+            file_name: "EnrageTimers.json",
+            built_in: None,
+            optional: true,
+            parse: parse_enrage_timers,
+            entries: HashMap::len,
+        },
+        user_dir,
+        previous.map(|tables| &tables.enrage_timers),
+    );
+    statuses.push(status);
+
+    let tables = DataTables {
+        monster_types,
+        extra_buff_monitored_monsters,
+        skill_level_to_effect,
+        cd_temp_attr_defs,
+        skill_effect_tags,
+        reset_rules,
+        names_zh_cn,
+        names_en_us,
+        enrage_timers,
+    };
+    (tables, statuses)
+}
+
+/// Loads a table from the user override, then the bundled file, then the built-in copy,
+/// taking the first that parses.
+fn load_table<T: Default + Clone>(
+    spec: &TableSpec<T>,
+    user_dir: Option<&Path>,
+    previous: Option<&T>,
+) -> (T, DataTableStatus) {
+    let mut errors = Vec::new();
+    let relative = format!("{}/{}", METER_DATA_DIR, spec.file_name);
+    let files = user_dir
+        .map(|dir| (DataTableSource::UserOverride, dir.join(spec.file_name)))
+        .filter(|(_, path)| path.exists())
+        .into_iter()
+        .chain(locate_meter_data_file(&relative).map(|path| (DataTableSource::Bundled, path)));
+
+    for (source, path) in files {
+        let loaded = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))
+            .and_then(|contents| {
+                (spec.parse)(&contents).with_context(|| format!("invalid {}", path.display()))
+            });
+        match loaded {
+            Ok(table) => {
+                return finish(spec, table, source, Some(&path), errors);
+            }
+            Err(err) => errors.push(format!("{:#}", err)),
+        }
+    }
+
+    if let Some(built_in) = spec.built_in {
+        match (spec.parse)(built_in) {
+            Ok(table) => return finish(spec, table, DataTableSource::BuiltIn, None, errors),
+            Err(err) => errors.push(format!("invalid built-in {}: {:#}", spec.file_name, err)),
+        }
+    }
+
+    if errors.is_empty() {
+        if !spec.optional {
+            warn!(
+                target: "app::live",
+                "[data-tables] {} not found in known locations",
+                relative
+            );
+        }
+        return finish(spec, T::default(), DataTableSource::Missing, None, errors);
+    }
+    match previous {
+        Some(table) => finish(spec, table.clone(), DataTableSource::Previous, None, errors),
+        None => finish(spec, T::default(), DataTableSource::Missing, None, errors),
+    }
+}
+
+fn finish<T>(
+    spec: &TableSpec<T>,
+    table: T,
+    source: DataTableSource,
+    path: Option<&Path>,
+    errors: Vec<String>,
+) -> (T, DataTableStatus) {
+    let status = DataTableStatus {
+        file_name: spec.file_name.to_string(),
+        source,
+        path: path.map(|path| path.display().to_string()),
+        entries: u32::try_from((spec.entries)(&table)).unwrap_or(u32::MAX),
+        errors,
+    };
+    for error in &status.errors {
+        warn!(target: "app::live", "[data-tables] {}: {}", status.file_name, error);
+    }
+    info!(
+        target: "app::live",
+        "[data-tables] {} loaded from {:?} {} ({} entries)",
+        status.file_name,
+        status.source,
+        status.path.as_deref().unwrap_or("-"),
+        status.entries
+    );
+    (table, status)
+}
+
+/// Parses a JSON object keyed by numeric IDs, rejecting keys that aren't IDs.
+pub(crate) fn parse_id_map<K, V>(contents: &str) -> Result<HashMap<K, V>>
+where
+    K: FromStr + Eq + Hash,
+    V: DeserializeOwned,
+{
+    parse_id_keys(serde_json::from_str(contents)?)
+}
+
+/// Converts the string keys of a JSON object to numeric IDs.
+pub(crate) fn parse_id_keys<K, V>(raw: HashMap<String, V>) -> Result<HashMap<K, V>>
+where
+    K: FromStr + Eq + Hash,
+{
+    raw.into_iter()
+        .map(|(key, value)| {
+            let id = key
+                .parse::<K>()
+                .map_err(|_| anyhow!("invalid id key {:?}", key))?;
+            Ok((id, value))
+        })
+        .collect()
+}
+
+fn locate_meter_data_file(relative_path: &str) -> Option<PathBuf> {
+    let mut path = PathBuf::from(relative_path);
+    if path.exists() {
+        return Some(path);
+    }
+
+    path = PathBuf::from(format!("src-tauri/{}", relative_path));
+    if path.exists() {
+        return Some(path);
+    }
+
+    if let Ok(mut exe_dir) = std::env::current_exe() {
+        exe_dir.pop();
+        let candidate = exe_dir.join(relative_path);
+        if candidate.exists() {
+            return Some(candidate);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataTableSource, TableSpec, load_table, parse_id_map};
+    use anyhow::Result;
+    use std::collections::HashMap;
+
+    fn parse_counts(contents: &str) -> Result<HashMap<i32, u32>> {
+        parse_id_map(contents)
+    }
+
+    #[test]
+    fn prefers_valid_user_override() {
+        let dir = std::env::temp_dir().join(format!("meter-data-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let spec = TableSpec {
+            file_name: "Counts.json",
+            built_in: Some(r#"{"1": 10}"#),
+            optional: false,
+            parse: parse_counts,
+            entries: HashMap::len,
+        };
+
+        // No override: the built-in copy is used.
+        let (table, status) = load_table(&spec, Some(&dir), None);
+        assert_eq!(status.source, DataTableSource::BuiltIn);
+        assert_eq!(table.get(&1), Some(&10));
+
+        std::fs::write(dir.join("Counts.json"), r#"{"1": 11, "2": 20}"#).unwrap();
+        let (table, status) = load_table(&spec, Some(&dir), None);
+        assert_eq!(status.source, DataTableSource::UserOverride);
+        assert_eq!(status.entries, 2);
+        assert_eq!(table.get(&1), Some(&11));
+
+        // A broken override falls back and reports why.
+        std::fs::write(dir.join("Counts.json"), r#"{"one": 1}"#).unwrap();
+        let (table, status) = load_table(&spec, Some(&dir), None);
+        assert_eq!(status.source, DataTableSource::BuiltIn);
+        assert_eq!(status.errors.len(), 1);
+        assert_eq!(table.get(&1), Some(&10));
+
+        // Without any usable source a reload keeps the previous table.
+        let spec = TableSpec {
+            built_in: None,
+            ..spec
+        };
+        let previous = HashMap::from([(3, 30)]);
+        let (table, status) = load_table(&spec, Some(&dir), Some(&previous));
+        assert_eq!(status.source, DataTableSource::Previous);
+        assert_eq!(table, previous);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,17 +1,9 @@
+use crate::live::name_registry::{buff_name, labeled};
+use crate::live::reset_rules::ResetRules;
 use log::info;
-use std::collections::{HashMap, HashSet};
-use std::sync::LazyLock;
+use std::collections::HashMap;
 use std::time::Instant;
 
-/// Dungeon target IDs that should not trigger objective-based resets.
-pub static RESET_IGNORE_TARGETS: LazyLock<HashSet<i32>> = LazyLock::new(|| {
-    let data = include_str!("../../meter-data/ResetIgnoreTargets.json");
-    serde_json::from_str::<Vec<i32>>(data)
-        .unwrap_or_default()
-        .into_iter()
-        .collect()
-});
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EncounterResetReason {
     NewObjective,
@@ -30,14 +22,17 @@ impl BattleStateMachine {
         target_id: i32,
         nums: i32,
         complete: i32,
+        rules: &ResetRules,
+        scene_id: Option<i32>,
     ) -> Option<EncounterResetReason> {
         if complete == 0 && nums == 0 {
             self.active_target_id = Some(target_id);
-            if RESET_IGNORE_TARGETS.contains(&target_id) {
+            if rules.ignores_target(scene_id, target_id) {
                 info!(
                     target: "app::live",
-                    "Reset suppressed: target_new_objective ignored target_id={} complete={} nums={}",
+                    "Reset suppressed: target_new_objective ignored target_id={} scene_id={:?} complete={} nums={}",
                     target_id,
+                    scene_id,
                     complete,
                     nums
                 );
@@ -60,12 +55,13 @@ impl BattleStateMachine {
                 target_id
             };
 
-            if RESET_IGNORE_TARGETS.contains(&effective_target_id) {
+            if rules.ignores_target(scene_id, effective_target_id) {
                 info!(
                     target: "app::live",
-                    "Target completed ignored: raw_target_id={} effective_target_id={} complete={} nums={}",
+                    "Target completed ignored: raw_target_id={} effective_target_id={} scene_id={:?} complete={} nums={}",
                     target_id,
                     effective_target_id,
+                    scene_id,
                     complete,
                     nums
                 );
@@ -105,15 +101,18 @@ impl BattleStateMachine {
     pub fn check_for_wipe(
         &mut self,
         active_buffs: &mut HashMap<i32, crate::live::buff_monitor::ActiveBuff>,
+        rules: &ResetRules,
     ) -> Option<EncounterResetReason> {
-        if let Some(buff_uuid) = active_buffs
-            .iter()
-            .find_map(|(uuid, buff)| (buff.base_id == 510072).then_some(*uuid))
-        {
+        if let Some((buff_uuid, base_id)) = active_buffs.iter().find_map(|(uuid, buff)| {
+            rules
+                .is_wipe_buff(buff.base_id)
+                .then_some((*uuid, buff.base_id))
+        }) {
             active_buffs.remove(&buff_uuid);
             info!(
                 target: "app::live",
-                "Reset rule matched: wipe_buff_detected base_id=510072 buff_uuid={} => {:?}",
+                "Reset rule matched: wipe_buff_detected buff={} buff_uuid={} => {:?}",
+                labeled(buff_name(base_id), base_id),
                 buff_uuid,
                 EncounterResetReason::Wipe
             );
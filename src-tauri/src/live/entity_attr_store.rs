@@ -2,7 +2,6 @@ use crate::live::commands_models::{HateEntry, PanelAttrState, ShieldDetailEntry}
 use crate::live::opcodes_models::{AttrType, AttrValue, Entity, PositionAttr};
 use blueprotobuf_lib::blueprotobuf::EActorState;
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone)]
 pub struct DeathEvent {
@@ -10,6 +9,13 @@ pub struct DeathEvent {
     pub timestamp_ms: u128,
 }
 
+/// An entity's actor state leaving Dead.
+#[derive(Debug, Clone)]
+pub struct ReviveEvent {
+    pub uid: i64,
+    pub timestamp_ms: u128,
+}
+
 #[derive(Debug, Default)]
 pub struct EntityAttrStore {
     attrs: HashMap<i64, HashMap<AttrType, AttrValue>>,
@@ -23,6 +29,9 @@ pub struct EntityAttrStore {
     shield_detail_entries: Vec<ShieldDetailEntry>,
     shield_detail_dirty: bool,
     death_events: Vec<DeathEvent>,
+    revive_events: Vec<ReviveEvent>,
+    hp_changed_uids: Vec<i64>,
+    hate_changed_uids: Vec<i64>,
 }
 
 #[derive(Debug, Default)]
@@ -32,6 +41,11 @@ pub struct AttrChanges {
     pub shield_detail_dirty: bool,
     pub shield_detail_entries: Vec<ShieldDetailEntry>,
     pub death_events: Vec<DeathEvent>,
+    pub revive_events: Vec<ReviveEvent>,
+    /// Entities whose current or max HP changed, possibly with duplicates.
+    pub hp_changed_uids: Vec<i64>,
+    /// Entities whose hate list was updated, possibly with duplicates.
+    pub hate_changed_uids: Vec<i64>,
 }
 
 impl EntityAttrStore {
@@ -48,6 +62,9 @@ impl EntityAttrStore {
             shield_detail_entries: Vec::new(),
             shield_detail_dirty: false,
             death_events: Vec::new(),
+            revive_events: Vec::new(),
+            hp_changed_uids: Vec::new(),
+            hate_changed_uids: Vec::new(),
         }
     }
 
@@ -82,20 +99,22 @@ impl EntityAttrStore {
         {
             self.cd_dirty = true;
         }
-        if uid == self.local_player_uid
-            && matches!(attr_type, AttrType::CurrentHp | AttrType::MaxHp)
-        {
-            self.shield_detail_dirty = true;
+        if matches!(attr_type, AttrType::CurrentHp | AttrType::MaxHp) {
+            if uid == self.local_player_uid {
+                self.shield_detail_dirty = true;
+            }
+            self.hp_changed_uids.push(uid);
         }
 
         if matches!(attr_type, AttrType::ActorState) {
             let is_dead_now = self.is_dead(uid);
-            if !was_dead && is_dead_now {
-                let timestamp_ms = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis();
-                self.death_events.push(DeathEvent { uid, timestamp_ms });
+            if was_dead != is_dead_now {
+                let timestamp_ms = crate::live::clock::now_ms() as u128;
+                if is_dead_now {
+                    self.death_events.push(DeathEvent { uid, timestamp_ms });
+                } else {
+                    self.revive_events.push(ReviveEvent { uid, timestamp_ms });
+                }
             }
         }
 
@@ -163,7 +182,9 @@ impl EntityAttrStore {
             .and_then(AttrValue::as_position)
     }
 
+    /// The hate list of `uid`, marked as changed.
     pub fn hate_list_mut(&mut self, uid: i64) -> &mut Vec<HateEntry> {
+        self.hate_changed_uids.push(uid);
         self.hate_lists
             .entry(uid)
             .or_insert_with(|| Vec::with_capacity(8))
@@ -255,6 +276,9 @@ impl EntityAttrStore {
         self.shield_detail_entries.clear();
         self.shield_detail_dirty = true;
         self.death_events.clear();
+        self.revive_events.clear();
+        self.hp_changed_uids.clear();
+        self.hate_changed_uids.clear();
     }
 
     pub fn drain_changes(&mut self) -> AttrChanges {
@@ -270,6 +294,9 @@ impl EntityAttrStore {
             shield_detail_dirty: shield_dirty,
             shield_detail_entries: shield_entries,
             death_events: std::mem::take(&mut self.death_events),
+            revive_events: std::mem::take(&mut self.revive_events),
+            hp_changed_uids: std::mem::take(&mut self.hp_changed_uids),
+            hate_changed_uids: std::mem::take(&mut self.hate_changed_uids),
         }
     }
 }
@@ -1,10 +1,15 @@
+use crate::live::boss_ttk::enrage_timer;
+use crate::live::buff_uptime::BuffUptimeEntry;
 use crate::live::commands_models::{
-    BossHealth, BuffUpdateState, CounterUpdateState, DeathRecord, FightResourceState, HateEntry,
-    HeaderInfo, LiveDataPayload, PanelAttrState, RawEntityData, ShieldDetailEntry, SkillCdState,
-    TrainingDummyState, to_raw_combat_stats, to_raw_skill_stats,
+    BossHealth, BuffUpdateState, CaptureHealth, CounterUpdateState, DeathRecord,
+    FightResourceState, HateEntry, HeaderInfo, LiveDataPayload, PacketDropStats, PanelAttrState,
+    RawEntityData, ShieldDetailEntry, SkillCdState, TrainingDummyState, to_raw_combat_stats,
+    to_raw_phase_stats, to_raw_skill_stats,
 };
+use crate::live::data_tables::data_tables;
 use crate::live::entity_attr_store::EntityAttrStore;
 use crate::live::opcodes_models::{AttrType, Encounter, class};
+use crate::packets::capture_status::CaptureStatus;
 use blueprotobuf_lib::blueprotobuf::EEntityType;
 use log::{trace, warn};
 use serde::{Deserialize, Serialize};
@@ -106,6 +111,10 @@ pub enum OutboundEvent {
     TrainingDummyUpdate(TrainingDummyState),
     LiveData(LiveDataPayload),
     BuffUpdate(Vec<BuffUpdateState>),
+    BuffUptimeUpdate {
+        uid: i64,
+        entries: Vec<BuffUptimeEntry>,
+    },
     BossBuffUpdate(HashMap<i64, Vec<BuffUpdateState>>),
     HateListUpdate(HashMap<i64, Vec<HateEntry>>),
     EntityIdentityMap {
@@ -122,6 +131,9 @@ pub enum OutboundEvent {
         entries: Vec<ShieldDetailEntry>,
     },
     DeathReplay(Vec<DeathRecord>),
+    PacketDrops(PacketDropStats),
+    CaptureHealth(CaptureHealth),
+    CaptureStatus(CaptureStatus),
 }
 
 impl EventManager {
@@ -191,6 +203,11 @@ impl EventManager {
         self.outbound_events.push(OutboundEvent::BuffUpdate(buffs));
     }
 
+    pub fn emit_buff_uptime_update(&mut self, uid: i64, entries: Vec<BuffUptimeEntry>) {
+        self.outbound_events
+            .push(OutboundEvent::BuffUptimeUpdate { uid, entries });
+    }
+
     pub fn emit_boss_buff_update(&mut self, boss_buffs: HashMap<i64, Vec<BuffUpdateState>>) {
         self.outbound_events
             .push(OutboundEvent::BossBuffUpdate(boss_buffs));
@@ -253,6 +270,20 @@ impl EventManager {
             .push(OutboundEvent::DeathReplay(records));
     }
 
+    pub fn emit_packet_drops(&mut self, drops: PacketDropStats) {
+        self.outbound_events.push(OutboundEvent::PacketDrops(drops));
+    }
+
+    pub fn emit_capture_health(&mut self, health: CaptureHealth) {
+        self.outbound_events
+            .push(OutboundEvent::CaptureHealth(health));
+    }
+
+    pub fn emit_capture_status(&mut self, status: CaptureStatus) {
+        self.outbound_events
+            .push(OutboundEvent::CaptureStatus(status));
+    }
+
     pub fn drain_outbound_events(&mut self) -> Vec<OutboundEvent> {
         std::mem::take(&mut self.outbound_events)
     }
@@ -288,7 +319,9 @@ pub type EventManagerMutex = RwLock<EventManager>;
 pub fn generate_live_data_payload(
     encounter: &Encounter,
     attr_store: &EntityAttrStore,
+    rolling_windows_secs: &[u32],
 ) -> LiveDataPayload {
+    let now_ms = crate::live::clock::now_ms() as u128;
     let elapsed_ms = encounter
         .time_last_combat_packet_ms
         .saturating_sub(encounter.time_fight_start_ms);
@@ -351,14 +384,25 @@ pub fn generate_live_data_payload(
                 .iter()
                 .map(|(skill_id, stats)| (*skill_id, to_raw_skill_stats(stats)))
                 .collect(),
+            phases: to_raw_phase_stats(&entity.phase_stats),
+            summon_damage: to_raw_combat_stats(&entity.summon_damage),
+            rolling: rolling_windows_secs
+                .iter()
+                .map(|&window_secs| {
+                    entity
+                        .rolling
+                        .window(window_secs, now_ms, encounter.time_fight_start_ms)
+                })
+                .collect(),
         });
     }
 
+    let tables = data_tables();
     let mut bosses: Vec<BossHealth> = encounter
         .entity_uid_to_entity
         .iter()
         .filter_map(|(&uid, entity)| {
-            if !entity.is_boss() {
+            if !entity.is_boss(&tables) {
                 return None;
             }
 
@@ -376,12 +420,22 @@ pub fn generate_live_data_payload(
                 return None;
             }
 
+            let time_to_kill = entity.hp_history.time_to_kill(now_ms);
+            let enrage = entity
+                .monster_type_id
+                .and_then(|monster_id| tables.enrage_timers.get(&monster_id))
+                .zip(entity.hp_history.engaged_at_ms())
+                .map(|(&enrage_secs, engaged_at_ms)| {
+                    enrage_timer(enrage_secs, engaged_at_ms, now_ms, time_to_kill.as_ref())
+                });
             Some(BossHealth {
                 uid,
                 monster_id: entity.monster_type_id,
                 current_hp,
                 max_hp,
                 is_dead: attr_store.is_dead(uid),
+                time_to_kill,
+                enrage,
             })
         })
         .collect();
@@ -401,5 +455,6 @@ pub fn generate_live_data_payload(
         is_paused: encounter.is_encounter_paused,
         bosses,
         entities,
+        phases: encounter.phases.phases().to_vec(),
     }
 }
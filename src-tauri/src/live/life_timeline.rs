@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LifeEventKind {
+    Death,
+    Revive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LifeEvent {
+    pub kind: LifeEventKind,
+    pub timestamp_ms: u128,
+}
+
+/// Deaths and revives of one player, alternating and in time order.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LifeTimeline {
+    pub events: Vec<LifeEvent>,
+}
+
+/// A stretch of time the player was alive.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AliveInterval {
+    pub start_ms: u128,
+    pub end_ms: u128,
+}
+
+/// Life timeline reduced to an encounter window.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LifeSummary {
+    pub death_count: u32,
+    /// Time spent dead inside the window.
+    pub dead_ms: u128,
+    pub alive_intervals: Vec<AliveInterval>,
+}
+
+impl LifeTimeline {
+    pub fn is_dead(&self) -> bool {
+        self.events
+            .last()
+            .is_some_and(|event| event.kind == LifeEventKind::Death)
+    }
+
+    /// Records a death. Ignored if the player is already dead.
+    pub fn record_death(&mut self, timestamp_ms: u128) -> bool {
+        if self.is_dead() {
+            return false;
+        }
+        self.push(LifeEventKind::Death, timestamp_ms);
+        true
+    }
+
+    /// Records a revive. Ignored unless the player is dead, so the revive notification and
+    /// the actor state update for the same revive only count once.
+    pub fn record_revive(&mut self, timestamp_ms: u128) -> bool {
+        if !self.is_dead() {
+            return false;
+        }
+        self.push(LifeEventKind::Revive, timestamp_ms);
+        true
+    }
+
+    fn push(&mut self, kind: LifeEventKind, timestamp_ms: u128) {
+        // Events come from different packets; never let one go backwards in time.
+        let timestamp_ms = self
+            .events
+            .last()
+            .map_or(timestamp_ms, |last| timestamp_ms.max(last.timestamp_ms));
+        self.events.push(LifeEvent { kind, timestamp_ms });
+    }
+
+    /// Drops the history but keeps a player who is dead right now dead.
+    pub fn clear_keeping_state(&mut self) {
+        let last_death = self.events.last().copied().filter(|_| self.is_dead());
+        self.events.clear();
+        self.events.extend(last_death);
+    }
+
+    /// Summarizes the timeline between `start_ms` and `end_ms`.
+    ///
+    /// The player counts as alive at `start_ms` unless the first recorded event is a revive.
+    pub fn summary(&self, start_ms: u128, end_ms: u128) -> LifeSummary {
+        let end_ms = end_ms.max(start_ms);
+        let mut summary = LifeSummary::default();
+        let starts_dead = self
+            .events
+            .first()
+            .is_some_and(|event| event.kind == LifeEventKind::Revive);
+        let mut alive_since = (!starts_dead).then_some(start_ms);
+        let mut dead_since = starts_dead.then_some(start_ms);
+
+        for event in &self.events {
+            let timestamp_ms = event.timestamp_ms.clamp(start_ms, end_ms);
+            match event.kind {
+                LifeEventKind::Death => {
+                    let Some(since) = alive_since.take() else {
+                        continue;
+                    };
+                    if timestamp_ms > since {
+                        summary.alive_intervals.push(AliveInterval {
+                            start_ms: since,
+                            end_ms: timestamp_ms,
+                        });
+                    }
+                    if (start_ms..=end_ms).contains(&event.timestamp_ms) {
+                        summary.death_count += 1;
+                    }
+                    dead_since = Some(timestamp_ms);
+                }
+                LifeEventKind::Revive => {
+                    let Some(since) = dead_since.take() else {
+                        continue;
+                    };
+                    summary.dead_ms += timestamp_ms - since;
+                    alive_since = Some(timestamp_ms);
+                }
+            }
+        }
+
+        if let Some(since) = alive_since
+            && end_ms > since
+        {
+            summary.alive_intervals.push(AliveInterval {
+                start_ms: since,
+                end_ms,
+            });
+        }
+        if let Some(since) = dead_since {
+            summary.dead_ms += end_ms - since;
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AliveInterval, LifeTimeline};
+
+    #[test]
+    fn pairs_deaths_with_revives() {
+        let mut timeline = LifeTimeline::default();
+        assert!(timeline.record_death(1_000));
+        assert!(!timeline.record_death(1_500));
+        assert!(timeline.record_revive(5_000));
+        assert!(!timeline.record_revive(5_100));
+        assert!(timeline.record_death(8_000));
+
+        let summary = timeline.summary(0, 10_000);
+        assert_eq!(summary.death_count, 2);
+        assert_eq!(summary.dead_ms, 4_000 + 2_000);
+        assert_eq!(
+            summary.alive_intervals,
+            vec![
+                AliveInterval {
+                    start_ms: 0,
+                    end_ms: 1_000
+                },
+                AliveInterval {
+                    start_ms: 5_000,
+                    end_ms: 8_000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn clamps_to_the_encounter_window() {
+        let mut timeline = LifeTimeline::default();
+        timeline.record_death(500);
+        timeline.clear_keeping_state();
+        timeline.record_revive(3_000);
+
+        // Died before the encounter started: dead from the start, but not counted as a death.
+        let summary = timeline.summary(1_000, 4_000);
+        assert_eq!(summary.death_count, 0);
+        assert_eq!(summary.dead_ms, 2_000);
+        assert_eq!(
+            summary.alive_intervals,
+            vec![AliveInterval {
+                start_ms: 3_000,
+                end_ms: 4_000
+            }]
+        );
+
+        let mut alive = LifeTimeline::default();
+        alive.record_death(100);
+        alive.record_revive(200);
+        alive.clear_keeping_state();
+        assert!(alive.events.is_empty());
+    }
+}
@@ -1,19 +1,23 @@
+use crate::live::clock::ReplayTimeGuard;
 use crate::live::state::{AppState, AppStateManager, StateEvent};
 use crate::live::{
     commands_models::{
-        BossBuffUpdatePayload, BuffCounterUpdatePayload, BuffUpdatePayload, DeathReplayPayload,
-        EntityIdentityMapPayload, FightResourceUpdatePayload, HateListUpdatePayload,
-        PanelAttrUpdatePayload, ShieldDetailUpdatePayload, SkillCdUpdatePayload,
+        BossBuffUpdatePayload, BuffCounterUpdatePayload, BuffUpdatePayload,
+        BuffUptimeUpdatePayload, CaptureHealth, DeathReplayPayload, EntityIdentityMapPayload,
+        FightResourceUpdatePayload, HateListUpdatePayload, PacketDropStats, PanelAttrUpdatePayload,
+        ShieldDetailUpdatePayload, SkillCdUpdatePayload,
     },
     event_manager::{EncounterUpdatePayload, SceneChangePayload},
     event_manager::{OutboundEvent, safe_emit_to},
 };
 use crate::packets;
+use crate::packets::capture_stats::capture_stats;
+use crate::packets::packet_queue::PacketItem;
+use crate::packets::stream_table::ConnectionId;
 use blueprotobuf_lib::blueprotobuf;
 use bytes::Bytes;
 use log::{debug, info, trace, warn};
 use prost::Message;
-use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Manager};
 use tokio::sync::mpsc::UnboundedReceiver;
@@ -22,24 +26,20 @@ const QUEUE_DEPTH_WARN_THRESHOLD: usize = 100;
 const QUEUE_DEPTH_ERROR_THRESHOLD: usize = 500;
 const QUEUE_DEPTH_CRITICAL_THRESHOLD: usize = 2000;
 const QUEUE_DEPTH_LOG_INTERVAL: Duration = Duration::from_millis(500);
+const CAPTURE_HEALTH_EMIT_INTERVAL: Duration = Duration::from_secs(5);
 
-fn log_queue_depth_if_needed(
-    queue_depth: &std::sync::atomic::AtomicUsize,
-    warn_counter: &mut usize,
-    last_log_at: &mut Instant,
-) {
+fn log_queue_depth_if_needed(current: usize, warn_counter: &mut usize, last_log_at: &mut Instant) {
     if last_log_at.elapsed() < QUEUE_DEPTH_LOG_INTERVAL {
         return;
     }
     *last_log_at = Instant::now();
 
-    let current = queue_depth.load(Ordering::Relaxed);
     if current >= QUEUE_DEPTH_CRITICAL_THRESHOLD {
         *warn_counter += 1;
         if *warn_counter % 5 == 1 {
             warn!(
                 target: "app::live",
-                "queue_depth_critical depth={} - consumer severely behind, risk of OOM",
+                "queue_depth_critical depth={} - consumer severely behind, low-priority packets may be dropped",
                 current
             );
         }
@@ -67,7 +67,7 @@ fn log_queue_depth_if_needed(
 }
 
 /// Decodes packet payload into a state event.
-fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEvent> {
+pub(crate) fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEvent> {
     match op {
         packets::opcodes::Pkt::ServerChangeInfo => Some(StateEvent::ServerChange),
         packets::opcodes::Pkt::EnterScene => {
@@ -76,6 +76,7 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
                 Ok(v) => Some(StateEvent::EnterScene(v)),
                 Err(e) => {
                     warn!("Error decoding EnterScene.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
                     None
                 }
             }
@@ -85,6 +86,7 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
                 Ok(v) => Some(StateEvent::SyncNearEntities(v)),
                 Err(e) => {
                     warn!("Error decoding SyncNearEntities.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
                     None
                 }
             }
@@ -94,6 +96,7 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
                 Ok(v) => Some(StateEvent::SyncContainerData(v)),
                 Err(e) => {
                     warn!("Error decoding SyncContainerData.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
                     None
                 }
             }
@@ -103,6 +106,7 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
                 Ok(v) => Some(StateEvent::SyncContainerDirtyData(v)),
                 Err(e) => {
                     warn!("Error decoding SyncContainerDirtyData.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
                     None
                 }
             }
@@ -111,6 +115,7 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
             Ok(v) => Some(StateEvent::SyncServerTime(v)),
             Err(e) => {
                 warn!("Error decoding SyncServerTime.. ignoring: {e}");
+                capture_stats().record_decode_failure(op);
                 None
             }
         },
@@ -139,6 +144,7 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
                 }
                 Err(e) => {
                     warn!("Error decoding SyncDungeonData.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
                     None
                 }
             }
@@ -162,6 +168,7 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
                 }
                 Err(e) => {
                     warn!("Error decoding SyncDungeonDirtyData.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
                     None
                 }
             }
@@ -171,6 +178,7 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
                 Ok(v) => Some(StateEvent::SyncToMeDeltaInfo(v)),
                 Err(e) => {
                     warn!("Error decoding SyncToMeDeltaInfo.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
                     None
                 }
             }
@@ -180,29 +188,33 @@ fn decode_state_event(op: packets::opcodes::Pkt, data: Bytes) -> Option<StateEve
                 Ok(v) => Some(StateEvent::SyncNearDeltaInfo(v)),
                 Err(e) => {
                     warn!("Error decoding SyncNearDeltaInfo.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
+                    None
+                }
+            }
+        }
+        packets::opcodes::Pkt::NotifyReviveUser => {
+            match blueprotobuf::NotifyReviveUser::decode(data) {
+                Ok(v) => Some(StateEvent::NotifyReviveUser(v)),
+                Err(e) => {
+                    warn!("Error decoding NotifyReviveUser.. ignoring: {e}");
+                    capture_stats().record_decode_failure(op);
                     None
                 }
             }
         }
         packets::opcodes::Pkt::BuffInfoSync => match blueprotobuf::BuffInfoSync::decode(data) {
             Ok(v) => {
-                // Dump the packet as JSON for debugging
-                match serde_json::to_string_pretty(&v) {
-                    Ok(json) => {
-                        debug!(target: "app::live", "BuffInfoSync packet received:\n{}", json);
-                    }
-                    Err(e) => {
-                        debug!(
-                            target: "app::live",
-                            "BuffInfoSync packet received (JSON serialization failed: {}): {:?}",
-                            e, v
-                        );
-                    }
-                }
-                None // Not processed further for now
+                debug!(
+                    target: "app::live",
+                    "BuffInfoSync packet received (buffs={})",
+                    v.buff_infos.len()
+                );
+                Some(StateEvent::BuffInfoSync(v))
             }
             Err(e) => {
                 warn!("Error decoding BuffInfoSync.. ignoring: {e}");
+                capture_stats().record_decode_failure(op);
                 None
             }
         },
@@ -234,6 +246,9 @@ pub async fn start(
 
     // Get the state manager from app state
     let state_manager = app_handle.state::<AppStateManager>().inner().clone();
+    if let Ok(app_data_dir) = app_handle.path().app_data_dir() {
+        crate::live::data_tables::init_data_tables(app_data_dir.join("meter-data"));
+    }
     let mut state = AppState::new();
     if let Some(snapshot) =
         crate::live::bootstrap_snapshot::load_monitor_runtime_snapshot(&app_handle)
@@ -249,15 +264,18 @@ pub async fn start(
     let heartbeat_duration = Duration::from_secs(2);
 
     // 1. Start capturing packets and send to rx
-    let method = get_capture_method(&app_handle);
-    let (mut rx, queue_depth) = packets::packet_capture::start_capture(method);
+    let (method, queue_config) = get_capture_settings(&app_handle);
+    let mut rx = packets::packet_capture::start_capture(method, queue_config);
     let mut queue_depth_warn_counter = 0usize;
     let mut queue_depth_last_log_at = Instant::now();
+    let mut reported_packet_losses = 0u64;
+    let mut last_capture_health_at = Instant::now();
+    let mut capture_status_generation = 0u64;
 
     // 2. Use channels to receive packets and control commands, and process whichever arrives first
     loop {
         log_queue_depth_if_needed(
-            queue_depth.as_ref(),
+            rx.len(),
             &mut queue_depth_warn_counter,
             &mut queue_depth_last_log_at,
         );
@@ -270,15 +288,13 @@ pub async fn start(
                 flush_outbound_events(&app_handle, &mut state);
             }
             packet = rx.recv() => match packet {
-            Some((op, data)) => {
-                queue_depth
-                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |depth| {
-                        Some(depth.saturating_sub(1))
-                    })
-                    .ok();
+            Some(item) => {
+                record_session_packet(&mut state, &item);
                 // Process the first packet immediately (low-latency path)
                 let mut batch_events = Vec::new();
-                if let Some(event) = decode_state_event(op, data) {
+                if accept_connection_packet(&mut state, item.connection_id, item.op)
+                    && let Some(event) = decode_state_event(item.op, item.data)
+                {
                     batch_events.push(event);
                 }
 
@@ -297,13 +313,13 @@ pub async fn start(
                     }
 
                     match rx.try_recv() {
-                        Ok((op, data)) => {
-                            queue_depth
-                                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |depth| {
-                                    Some(depth.saturating_sub(1))
-                                })
-                                .ok();
-                            if let Some(event) = decode_state_event(op, data) {
+                        Ok(item) => {
+                            record_session_packet(&mut state, &item);
+                            if !accept_connection_packet(&mut state, item.connection_id, item.op) {
+                                drained += 1;
+                                continue;
+                            }
+                            if let Some(event) = decode_state_event(item.op, item.data) {
                                 let is_server_change = matches!(event, StateEvent::ServerChange);
                                 batch_events.push(event);
                                 drained += 1;
@@ -337,6 +353,9 @@ pub async fn start(
                 if now.duration_since(last_emit_time) >= emit_throttle_duration {
                     last_emit_time = now;
                     state_manager.update_and_emit_events_with_state(&mut state);
+                    report_packet_losses(&mut state, &mut reported_packet_losses);
+                    report_capture_health(&mut state, &mut last_capture_health_at);
+                    report_capture_status(&mut state, &mut capture_status_generation);
                 }
                 flush_outbound_events(&app_handle, &mut state);
             }
@@ -356,6 +375,9 @@ pub async fn start(
                 if now.duration_since(last_emit_time) >= emit_throttle_duration {
                     last_emit_time = now;
                     state_manager.update_and_emit_events_with_state(&mut state);
+                    report_packet_losses(&mut state, &mut reported_packet_losses);
+                    report_capture_health(&mut state, &mut last_capture_health_at);
+                    report_capture_status(&mut state, &mut capture_status_generation);
                 }
                 flush_outbound_events(&app_handle, &mut state);
             }
@@ -363,6 +385,133 @@ pub async fn start(
     }
 }
 
+/// Pushes a `packet-drops` event whenever the capture queue has lost packets since the last
+/// report, so the UI can flag the numbers as possibly incomplete.
+fn report_packet_losses(state: &mut AppState, reported: &mut u64) {
+    let stats = packets::packet_queue::queue_stats();
+    let total_lost = stats.total_lost();
+    if total_lost == *reported {
+        return;
+    }
+    *reported = total_lost;
+    let drops = PacketDropStats::from(stats);
+    warn!(
+        target: "app::live",
+        "packet_queue_losses dropped={} coalesced={}",
+        drops.total_dropped,
+        drops.total_coalesced
+    );
+    state.event_manager.emit_packet_drops(drops);
+}
+
+/// Pushes a `capture-health` snapshot every few seconds.
+fn report_capture_health(state: &mut AppState, last_emit_at: &mut Instant) {
+    if last_emit_at.elapsed() < CAPTURE_HEALTH_EMIT_INTERVAL {
+        return;
+    }
+    *last_emit_at = Instant::now();
+    state
+        .event_manager
+        .emit_capture_health(CaptureHealth::current());
+}
+
+/// Pushes a `capture-status` event when the capture thread starts, recovers or fails.
+fn report_capture_status(state: &mut AppState, generation: &mut u64) {
+    if let Some(status) = packets::capture_status::capture_status().changed_since(generation) {
+        state.event_manager.emit_capture_status(status);
+    }
+}
+
+/// Decides whether a packet from `connection_id` should reach the encounter.
+///
+/// A server change makes its stream the active one; until the first one arrives, the first
+/// stream seen is adopted. Packets from any other tracked stream are dropped.
+pub(crate) fn accept_connection_packet(
+    state: &mut AppState,
+    connection_id: ConnectionId,
+    op: packets::opcodes::Pkt,
+) -> bool {
+    if op == packets::opcodes::Pkt::ServerChangeInfo || state.active_connection_id.is_none() {
+        if state.active_connection_id != Some(connection_id) {
+            info!(
+                target: "app::live",
+                "active_connection_changed from={:?} to={}",
+                state.active_connection_id,
+                connection_id
+            );
+            state.active_connection_id = Some(connection_id);
+        }
+        return true;
+    }
+    if state.active_connection_id == Some(connection_id) {
+        return true;
+    }
+    trace!(
+        target: "app::live",
+        "dropping packet from inactive connection_id={} op={:?}",
+        connection_id,
+        op
+    );
+    false
+}
+
+/// Tees a received packet into the active session recording, if one is running.
+fn record_session_packet(state: &mut AppState, item: &PacketItem) {
+    let Some(recorder) = state.session_recorder.as_mut() else {
+        return;
+    };
+    if let Err(e) = recorder.record(item.connection_id, item.op, &item.data, item.captured_at_ms) {
+        warn!(
+            target: "app::live",
+            "session_record_failed err={}; stopping recording",
+            e
+        );
+        state.session_recorder = None;
+    }
+}
+
+/// Feeds one replayed packet into `state` with the meter clock pinned to its capture time, so
+/// timings follow the capture rather than the replay speed. UI events are discarded.
+pub(crate) fn replay_packet_into_state(
+    state_manager: &AppStateManager,
+    state: &mut AppState,
+    clock: &ReplayTimeGuard,
+    item: PacketItem,
+) {
+    clock.advance_to(item.captured_at_ms);
+    if accept_connection_packet(state, item.connection_id, item.op)
+        && let Some(event) = decode_state_event(item.op, item.data)
+    {
+        state_manager.handle_events_batch_with_state(state, vec![event]);
+    }
+    // Nobody listens for UI events during a replay.
+    state.event_manager.drain_outbound_events();
+}
+
+/// Replays a recorded session directly into `state`, bypassing capture and the packet channel.
+///
+/// Returns the number of packets replayed.
+pub(crate) fn replay_session_into_state(
+    state_manager: &AppStateManager,
+    state: &mut AppState,
+    clock: &ReplayTimeGuard,
+    path: &std::path::Path,
+) -> Result<u64, String> {
+    let mut reader = packets::session_file::SessionReader::open(path)?;
+    let mut replayed = 0u64;
+    while let Some(record) = reader.next_record()? {
+        replayed += 1;
+        let item = PacketItem::new(
+            record.connection_id,
+            record.op,
+            record.payload,
+            record.captured_at_ms,
+        );
+        replay_packet_into_state(state_manager, state, clock, item);
+    }
+    Ok(replayed)
+}
+
 fn flush_outbound_events(app_handle: &AppHandle, state: &mut AppState) {
     for event in state.event_manager.drain_outbound_events() {
         match event {
@@ -424,6 +573,14 @@ fn flush_outbound_events(app_handle: &AppHandle, state: &mut AppState) {
                     BuffUpdatePayload { buffs },
                 );
             }
+            OutboundEvent::BuffUptimeUpdate { uid, entries } => {
+                safe_emit_to(
+                    app_handle,
+                    crate::WINDOW_LIVE_LABEL,
+                    "buff-uptime-update",
+                    BuffUptimeUpdatePayload { uid, entries },
+                );
+            }
             OutboundEvent::BossBuffUpdate(boss_buffs) => {
                 safe_emit_to(
                     app_handle,
@@ -502,6 +659,25 @@ fn flush_outbound_events(app_handle: &AppHandle, state: &mut AppState) {
                     },
                 );
             }
+            OutboundEvent::CaptureHealth(health) => {
+                safe_emit_to(
+                    app_handle,
+                    crate::WINDOW_LIVE_LABEL,
+                    "capture-health",
+                    health,
+                );
+            }
+            OutboundEvent::CaptureStatus(status) => {
+                safe_emit_to(
+                    app_handle,
+                    crate::WINDOW_LIVE_LABEL,
+                    "capture-status",
+                    status,
+                );
+            }
+            OutboundEvent::PacketDrops(drops) => {
+                safe_emit_to(app_handle, crate::WINDOW_LIVE_LABEL, "packet-drops", drops);
+            }
             OutboundEvent::DeathReplay(records) => {
                 safe_emit_to(
                     app_handle,
@@ -514,9 +690,12 @@ fn flush_outbound_events(app_handle: &AppHandle, state: &mut AppState) {
     }
 }
 
-fn get_capture_method(app: &AppHandle) -> packets::packet_capture::CaptureMethod {
-    use packets::packet_capture::CaptureMethod;
-
+fn get_capture_settings(
+    app: &AppHandle,
+) -> (
+    packets::packet_capture::CaptureMethod,
+    packets::packet_queue::PacketQueueConfig,
+) {
     let filename_candidates = ["packetCapture.json", "packetCapture.bin", "packetCapture"];
     let mut dir_candidates = Vec::new();
     if let Some(dir) = app.path().app_data_dir().ok() {
@@ -536,30 +715,7 @@ fn get_capture_method(app: &AppHandle) -> packets::packet_capture::CaptureMethod
             }
             if let Ok(file) = std::fs::File::open(&path) {
                 if let Ok(json) = serde_json::from_reader::<_, serde_json::Value>(file) {
-                    let method = json
-                        .get("method")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("WinDivert");
-                    let device = json
-                        .get("npcapDevice")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("");
-
-                    info!(
-                        target: "app::capture",
-                        "Packet capture config found at {} (method={}, device={})",
-                        path.display(),
-                        method,
-                        device
-                    );
-
-                    if method == "Npcap" {
-                        info!(target: "app::capture", "Using Npcap capture method device={}", device);
-                        return CaptureMethod::Npcap(device.to_string());
-                    } else {
-                        info!(target: "app::capture", "Using WinDivert capture method (from config)");
-                        return CaptureMethod::WinDivert;
-                    }
+                    return capture_settings_from_config(&path, &json);
                 } else {
                     warn!(
                         "Failed to parse packet capture config at {}",
@@ -580,30 +736,7 @@ fn get_capture_method(app: &AppHandle) -> packets::packet_capture::CaptureMethod
                 }
                 if let Ok(file) = std::fs::File::open(&path) {
                     if let Ok(json) = serde_json::from_reader::<_, serde_json::Value>(file) {
-                        let method = json
-                            .get("method")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("WinDivert");
-                        let device = json
-                            .get("npcapDevice")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("");
-
-                        info!(
-                            target: "app::capture",
-                            "Packet capture config found at {} (method={}, device={})",
-                            path.display(),
-                            method,
-                            device
-                        );
-
-                        if method == "Npcap" {
-                            info!(target: "app::capture", "Using Npcap capture method device={}", device);
-                            return CaptureMethod::Npcap(device.to_string());
-                        } else {
-                            info!(target: "app::capture", "Using WinDivert capture method (from config)");
-                            return CaptureMethod::WinDivert;
-                        }
+                        return capture_settings_from_config(&path, &json);
                     } else {
                         warn!(
                             "Failed to parse packet capture config at {}",
@@ -615,8 +748,133 @@ fn get_capture_method(app: &AppHandle) -> packets::packet_capture::CaptureMethod
         }
     }
 
-    warn!(target: "app::capture", "No packetCapture config found in app data dirs; falling back to WinDivert");
+    warn!(target: "app::capture", "No packetCapture config found in app data dirs; using platform default");
+
+    let method = platform_default_capture_method();
+    info!(target: "app::capture", "Using {:?} capture method (default)", method);
+    (method, packets::packet_queue::PacketQueueConfig::default())
+}
 
-    info!(target: "app::capture", "Using WinDivert capture method (default)");
-    CaptureMethod::WinDivert
+fn capture_settings_from_config(
+    path: &std::path::Path,
+    json: &serde_json::Value,
+) -> (
+    packets::packet_capture::CaptureMethod,
+    packets::packet_queue::PacketQueueConfig,
+) {
+    (
+        capture_method_from_config(path, json),
+        packet_queue_config_from_config(json),
+    )
+}
+
+/// Reads the optional `packetQueueCapacity` / `packetOverflowPolicy` keys.
+fn packet_queue_config_from_config(
+    json: &serde_json::Value,
+) -> packets::packet_queue::PacketQueueConfig {
+    use packets::packet_queue::{DEFAULT_PACKET_QUEUE_CAPACITY, OverflowPolicy, PacketQueueConfig};
+
+    let capacity = json
+        .get("packetQueueCapacity")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| usize::try_from(v).ok())
+        .unwrap_or(DEFAULT_PACKET_QUEUE_CAPACITY);
+    let policy = json
+        .get("packetOverflowPolicy")
+        .and_then(|v| v.as_str())
+        .map(OverflowPolicy::from_config)
+        .unwrap_or_default();
+    PacketQueueConfig::new(capacity, policy)
+}
+
+/// WinDivert only exists on Windows; elsewhere capture through libpcap on the `any` device.
+fn platform_default_capture_method() -> packets::packet_capture::CaptureMethod {
+    use packets::packet_capture::CaptureMethod;
+
+    if cfg!(windows) {
+        CaptureMethod::WinDivert
+    } else {
+        CaptureMethod::Npcap(packets::npcap::LIBPCAP_ANY_DEVICE.to_string())
+    }
+}
+
+fn capture_method_from_config(
+    path: &std::path::Path,
+    json: &serde_json::Value,
+) -> packets::packet_capture::CaptureMethod {
+    use packets::packet_capture::{CaptureMethod, ReplayPacing};
+
+    let method = json
+        .get("method")
+        .and_then(|v| v.as_str())
+        .unwrap_or("WinDivert");
+    let device = json
+        .get("npcapDevice")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    info!(
+        target: "app::capture",
+        "Packet capture config found at {} (method={}, device={})",
+        path.display(),
+        method,
+        device
+    );
+
+    match method {
+        "Npcap" => {
+            let device = if device.is_empty() && !cfg!(windows) {
+                packets::npcap::LIBPCAP_ANY_DEVICE
+            } else {
+                device
+            };
+            info!(target: "app::capture", "Using Npcap capture method device={}", device);
+            CaptureMethod::Npcap(device.to_string())
+        }
+        "SessionFile" => {
+            let file = json
+                .get("sessionFile")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let pacing = json
+                .get("replayPacing")
+                .and_then(|v| v.as_str())
+                .map(ReplayPacing::from_config)
+                .unwrap_or_default();
+            info!(
+                target: "app::capture",
+                "Using session file replay path={} pacing={:?}",
+                file,
+                pacing
+            );
+            CaptureMethod::SessionFile(std::path::PathBuf::from(file), pacing)
+        }
+        "PcapFile" => {
+            let file = json.get("pcapFile").and_then(|v| v.as_str()).unwrap_or("");
+            let pacing = json
+                .get("replayPacing")
+                .and_then(|v| v.as_str())
+                .map(ReplayPacing::from_config)
+                .unwrap_or_default();
+            info!(
+                target: "app::capture",
+                "Using pcap file replay path={} pacing={:?}",
+                file,
+                pacing
+            );
+            CaptureMethod::PcapFile(std::path::PathBuf::from(file), pacing)
+        }
+        _ if cfg!(windows) => {
+            info!(target: "app::capture", "Using WinDivert capture method (from config)");
+            CaptureMethod::WinDivert
+        }
+        _ => {
+            warn!(
+                target: "app::capture",
+                "Capture method {} is not available on this platform; using libpcap",
+                method
+            );
+            platform_default_capture_method()
+        }
+    }
 }
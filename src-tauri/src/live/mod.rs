@@ -1,17 +1,29 @@
+pub mod aggro_history;
 pub mod bootstrap_snapshot;
+pub mod boss_ttk;
 pub mod buff_monitor;
+pub mod buff_uptime;
+pub mod clock;
 pub mod commands;
 pub mod commands_models;
 pub mod counter_tracker;
 pub mod damage_id;
+pub mod data_tables;
 pub mod dungeon_dirty_blob;
 pub mod dungeon_log;
 pub mod entity_attr_store;
 pub mod event_manager;
+pub mod life_timeline;
 pub mod live_main;
 pub mod monster_registry;
+pub mod name_registry;
 pub mod opcodes_models;
 pub mod opcodes_process;
+pub mod phases;
+pub mod reset_rules;
+pub mod rolling_window;
+pub mod rotation_log;
 pub mod skill_cd_monitor;
 pub mod state;
+pub mod time_series;
 pub mod training_dummy;
@@ -0,0 +1,163 @@
+use crate::live::data_tables::{data_tables, parse_id_keys};
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Language of the names returned by the lookups. English names fall back to Chinese ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum NameLocale {
+    ZhCn = 0,
+    EnUs = 1,
+}
+
+impl NameLocale {
+    /// Parses an app locale such as `zh-CN` or `en-US`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "zh-CN" => Some(Self::ZhCn),
+            "en-US" => Some(Self::EnUs),
+            _ => None,
+        }
+    }
+}
+
+static NAME_LOCALE: AtomicU8 = AtomicU8::new(NameLocale::ZhCn as u8);
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct RawNameTable {
+    #[serde(default)]
+    skills: HashMap<String, String>,
+    #[serde(default)]
+    buffs: HashMap<String, String>,
+    #[serde(default)]
+    monsters: HashMap<String, String>,
+    #[serde(default)]
+    scenes: HashMap<String, String>,
+}
+
+/// Names of one locale from `meter-data/Names.<locale>.json`.
+#[derive(Debug, Default, Clone)]
+pub struct NameTable {
+    /// By skill key from `damage_id::compute_damage_id`.
+    skills: HashMap<i64, String>,
+    /// By buff base ID.
+    buffs: HashMap<i32, String>,
+    /// By monster type ID.
+    monsters: HashMap<i32, String>,
+    scenes: HashMap<i32, String>,
+}
+
+impl NameTable {
+    pub fn entry_count(&self) -> usize {
+        self.skills.len() + self.buffs.len() + self.monsters.len() + self.scenes.len()
+    }
+}
+
+pub(crate) fn parse_name_table(contents: &str) -> Result<NameTable> {
+    let raw: RawNameTable = serde_json::from_str(contents)?;
+    Ok(NameTable {
+        skills: parse_id_keys(raw.skills)?,
+        buffs: parse_id_keys(raw.buffs)?,
+        monsters: parse_id_keys(raw.monsters)?,
+        scenes: parse_id_keys(raw.scenes)?,
+    })
+}
+
+pub fn set_name_locale(locale: NameLocale) {
+    NAME_LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn name_locale() -> NameLocale {
+    if NAME_LOCALE.load(Ordering::Relaxed) == NameLocale::EnUs as u8 {
+        NameLocale::EnUs
+    } else {
+        NameLocale::ZhCn
+    }
+}
+
+fn resolve(
+    locale: NameLocale,
+    zh_cn: &NameTable,
+    en_us: &NameTable,
+    pick: impl Fn(&NameTable) -> Option<&String>,
+) -> Option<String> {
+    let localized = match locale {
+        NameLocale::EnUs => pick(en_us),
+        NameLocale::ZhCn => None,
+    };
+    localized.or_else(|| pick(zh_cn)).cloned()
+}
+
+fn lookup(pick: impl Fn(&NameTable) -> Option<&String>) -> Option<String> {
+    let tables = data_tables();
+    resolve(
+        name_locale(),
+        &tables.names_zh_cn,
+        &tables.names_en_us,
+        pick,
+    )
+}
+
+pub fn skill_name(skill_key: i64) -> Option<String> {
+    lookup(|table| table.skills.get(&skill_key))
+}
+
+pub fn buff_name(base_id: i32) -> Option<String> {
+    lookup(|table| table.buffs.get(&base_id))
+}
+
+pub fn monster_name(monster_id: i32) -> Option<String> {
+    lookup(|table| table.monsters.get(&monster_id))
+}
+
+pub fn scene_name(scene_id: i32) -> Option<String> {
+    lookup(|table| table.scenes.get(&scene_id))
+}
+
+/// `name(id)` for logs, or just the ID when there is no name.
+pub fn labeled(name: Option<String>, id: impl Display) -> String {
+    match name {
+        Some(name) => format!("{}({})", name, id),
+        None => id.to_string(),
+    }
+}
+
+/// Labeled scene for logs, `-` when unknown.
+pub fn scene_label(scene_id: Option<i32>) -> String {
+    scene_id.map_or_else(|| "-".to_string(), |id| labeled(scene_name(id), id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NameLocale, labeled, parse_name_table, resolve};
+
+    #[test]
+    fn english_names_fall_back_to_chinese() {
+        let zh_cn = parse_name_table(
+            r#"{
+                "skills": { "1220100101": "居合" },
+                "scenes": { "10": "蒙特诺尔溪谷" }
+            }"#,
+        )
+        .unwrap();
+        let en_us = parse_name_table(r#"{ "scenes": { "10": "Muku Valley" } }"#).unwrap();
+        assert_eq!(zh_cn.entry_count(), 2);
+
+        let scene = |locale| resolve(locale, &zh_cn, &en_us, |table| table.scenes.get(&10));
+        assert_eq!(scene(NameLocale::EnUs).as_deref(), Some("Muku Valley"));
+        assert_eq!(scene(NameLocale::ZhCn).as_deref(), Some("蒙特诺尔溪谷"));
+        let skill = resolve(NameLocale::EnUs, &zh_cn, &en_us, |table| {
+            table.skills.get(&1_220_100_101)
+        });
+        assert_eq!(skill.as_deref(), Some("居合"));
+
+        assert_eq!(labeled(Some("居合".to_string()), 1), "居合(1)");
+        assert_eq!(labeled(None, 1), "1");
+        assert!(parse_name_table(r#"{ "buffs": { "x": "bad" } }"#).is_err());
+        assert!(parse_name_table(r#"{ "skill": {} }"#).is_err());
+    }
+}
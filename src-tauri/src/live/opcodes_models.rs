@@ -1,6 +1,15 @@
+use crate::live::aggro_history::AggroTimeline;
+use crate::live::boss_ttk::BossHpHistory;
+use crate::live::buff_uptime::{BuffApplication, BuffUptimeEntry};
 use crate::live::commands_models::{DamageSnapshot, DeathRecord};
+use crate::live::data_tables::DataTables;
+use crate::live::life_timeline::LifeTimeline;
 use crate::live::monster_registry::{self, MonsterType};
 use crate::live::opcodes_models::class::ClassSpec;
+use crate::live::phases::{PhaseCombatStats, PhaseTracker};
+use crate::live::rolling_window::RollingTotals;
+use crate::live::rotation_log::RotationEntry;
+use crate::live::time_series::CombatTimeSeries;
 use blueprotobuf_lib::blueprotobuf::{EEntityType, SyncContainerData};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
@@ -15,6 +24,10 @@ pub struct Encounter {
     pub active_combat_time_ms: u128,
     /// Timestamp of the last damage event used to compute global active time.
     pub last_combat_timestamp_ms: Option<u128>,
+    /// Active time the local player spent damaging the locked training dummy, in milliseconds.
+    pub locked_target_damage_time_ms: u128,
+    /// Timestamp of the local player's last hit on the locked training dummy.
+    pub last_locked_target_hit_ms: Option<u128>,
     pub total_dmg: u128,
     pub total_dmg_boss_only: u128,
     pub total_heal: u128,
@@ -24,6 +37,9 @@ pub struct Encounter {
     pub local_player: SyncContainerData,
     pub current_scene_id: Option<i32>,
     pub current_dungeon_difficulty: Option<i32>,
+    /// Phase boundaries of the current encounter.
+    #[serde(skip)]
+    pub phases: PhaseTracker,
 }
 
 // Use an async-aware RwLock so readers don't block the tokio runtime threads.
@@ -339,6 +355,22 @@ pub struct CombatStats {
     pub hits: u128,
 }
 
+impl CombatStats {
+    /// Adds one hit to the totals and the crit/lucky breakdown.
+    pub fn add_hit(&mut self, value: u128, is_crit: bool, is_lucky: bool) {
+        self.hits += 1;
+        self.total += value;
+        if is_crit {
+            self.crit_hits += 1;
+            self.crit_total += value;
+        }
+        if is_lucky {
+            self.lucky_hits += 1;
+            self.lucky_total += value;
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Entity {
     pub name: String,
@@ -371,7 +403,46 @@ pub struct Entity {
     /// Rolling 2s window of damage taken events; used to build death-replay snapshots.
     #[serde(skip)]
     pub recent_taken_events: VecDeque<DamageSnapshot>,
+    /// Damage, healing and taken of the last seconds, for rolling-window DPS in the live view.
+    #[serde(skip)]
+    pub rolling: RollingTotals,
     pub deaths: Vec<DeathRecord>,
+    /// Deaths and revives, for time-spent-dead in history.
+    #[serde(default)]
+    pub life_timeline: LifeTimeline,
+    /// Per-second damage, healing and damage taken, for over-time graphs in history.
+    #[serde(default)]
+    pub time_series: CombatTimeSeries,
+    /// Damage, healing and taken per encounter phase, indexed like `Encounter::phases`.
+    /// Entries after the last phase the entity was active in are left out.
+    #[serde(default)]
+    pub phase_stats: Vec<PhaseCombatStats>,
+    /// Uptime of the monitored buffs, filled in when the encounter is saved. Only known for
+    /// the local player.
+    #[serde(default)]
+    pub buff_uptime: Vec<BuffUptimeEntry>,
+    /// Uptime of the monitored debuffs on a boss, filled in when the encounter is saved.
+    #[serde(default)]
+    pub debuff_uptime: Vec<BuffUptimeEntry>,
+    /// Who applied the monitored debuffs on a boss, filled in when the encounter is saved.
+    #[serde(default)]
+    pub debuff_applications: Vec<BuffApplication>,
+    /// Skill casts in order, filled in when the encounter is saved. Only known for the local
+    /// player.
+    #[serde(default)]
+    pub rotation: Vec<RotationEntry>,
+    /// Whether casts past `ROTATION_LOG_CAP` were left out of `rotation`.
+    #[serde(default)]
+    pub rotation_truncated: bool,
+    /// Part of `damage` dealt through summons; the rest was dealt directly.
+    #[serde(default)]
+    pub summon_damage: CombatStats,
+    /// Top-threat holders of a boss over time.
+    #[serde(default)]
+    pub aggro: AggroTimeline,
+    /// Recent HP of a boss, for the kill time estimate and enrage timer in the live view.
+    #[serde(skip)]
+    pub hp_history: BossHpHistory,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -401,6 +472,52 @@ pub struct Skill {
     pub property: Option<i32>,
     #[serde(default)]
     pub damage_mode: Option<i32>,
+    /// Part of the damage dealt through summons, by summon kind.
+    #[serde(default)]
+    pub summons: Vec<SummonDamage>,
+}
+
+/// Damage a skill dealt through one kind of summon rather than by its owner directly.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SummonDamage {
+    /// Entity type of the summon, as an `EEntityType` value.
+    pub entity_type: i32,
+    /// Monster template of the summon, when known.
+    pub monster_id: Option<i32>,
+    /// UID of the summon that hit last.
+    pub last_uid: i64,
+    pub hits: u128,
+    pub total_value: u128,
+}
+
+impl Skill {
+    /// Adds a hit that came from a summon. The hit must also be counted in the skill totals.
+    pub fn add_summon_hit(
+        &mut self,
+        entity_type: EEntityType,
+        monster_id: Option<i32>,
+        uid: i64,
+        value: u128,
+    ) {
+        let entity_type = entity_type as i32;
+        if let Some(summon) = self
+            .summons
+            .iter_mut()
+            .find(|summon| summon.entity_type == entity_type && summon.monster_id == monster_id)
+        {
+            summon.last_uid = uid;
+            summon.hits += 1;
+            summon.total_value += value;
+            return;
+        }
+        self.summons.push(SummonDamage {
+            entity_type,
+            monster_id,
+            last_uid: uid,
+            hits: 1,
+            total_value: value,
+        });
+    }
 }
 
 impl Encounter {
@@ -413,18 +530,21 @@ impl Encounter {
     /// - entity_uid_to_entity identity fields (name, class, spec, ability score, level, type)
     ///
     /// Clears:
-    /// - encounter totals and timestamps
-    /// - per-entity combat counters and per-encounter skill maps
+    /// - encounter totals, timestamps and phases
+    /// - per-entity combat counters, per-encounter skill maps and time series
     pub fn reset_combat_state(&mut self) {
         // Reset encounter-level combat state
         self.time_last_combat_packet_ms = 0;
         self.time_fight_start_ms = 0;
         self.active_combat_time_ms = 0;
         self.last_combat_timestamp_ms = None;
+        self.locked_target_damage_time_ms = 0;
+        self.last_locked_target_hit_ms = None;
         self.total_dmg = 0;
         self.total_dmg_boss_only = 0;
         self.total_heal = 0;
         self.total_effective_heal = 0;
+        self.phases.clear();
 
         // Reset per-entity combat stats while preserving identity
         for entity in self.entity_uid_to_entity.values_mut() {
@@ -447,6 +567,18 @@ impl Encounter {
             // Death replay window
             entity.recent_taken_events.clear();
             entity.deaths.clear();
+            entity.life_timeline.clear_keeping_state();
+            entity.time_series.clear();
+            entity.rolling.clear();
+            entity.hp_history.clear();
+            entity.aggro.clear_keeping_holder();
+            entity.phase_stats.clear();
+            entity.buff_uptime.clear();
+            entity.debuff_uptime.clear();
+            entity.debuff_applications.clear();
+            entity.rotation.clear();
+            entity.rotation_truncated = false;
+            entity.summon_damage = CombatStats::default();
         }
     }
 }
@@ -657,19 +789,27 @@ pub mod class {
 }
 
 impl Entity {
+    /// Stats of this entity in the phase at `index`.
+    pub fn phase_stats_mut(&mut self, index: usize) -> &mut PhaseCombatStats {
+        if self.phase_stats.len() <= index {
+            self.phase_stats.resize_with(index + 1, Default::default);
+        }
+        &mut self.phase_stats[index]
+    }
+
     /// Assign monster type id for backend classification and frontend display lookup.
     pub fn set_monster_type(&mut self, monster_id: i32) {
         self.monster_type_id = Some(monster_id);
     }
 
     /// Determine whether this entity is a boss based on game data categorization.
-    pub fn is_boss(&self) -> bool {
+    pub fn is_boss(&self, tables: &DataTables) -> bool {
         if self.entity_type != EEntityType::EntMonster {
             return false;
         }
 
         self.monster_type_id
-            .and_then(monster_registry::monster_type)
+            .and_then(|id| monster_registry::monster_type(tables, id))
             .map(|monster_type| monster_type == MonsterType::Boss)
             .unwrap_or(false)
     }
@@ -772,4 +912,18 @@ mod tests {
         let deserialized: AttrType = serde_json::from_str(&json).unwrap();
         assert_eq!(attr_type, deserialized);
     }
+
+    #[test]
+    fn skill_groups_summon_hits_by_kind() {
+        let mut skill = Skill::default();
+        skill.add_summon_hit(EEntityType::EntMonster, Some(7), 100, 50);
+        skill.add_summon_hit(EEntityType::EntMonster, Some(7), 101, 30);
+        skill.add_summon_hit(EEntityType::EntMonster, None, 102, 5);
+
+        assert_eq!(skill.summons.len(), 2);
+        assert_eq!(skill.summons[0].hits, 2);
+        assert_eq!(skill.summons[0].total_value, 80);
+        assert_eq!(skill.summons[0].last_uid, 101);
+        assert_eq!(skill.summons[1].monster_id, None);
+    }
 }
@@ -1,7 +1,9 @@
 // NOTE: opcodes_process works on Encounter directly; avoid importing opcodes_models at top-level.
-use crate::database::{flush_playerdata, now_ms};
+use crate::database::flush_playerdata;
+use crate::live::clock::now_ms;
 use crate::live::commands_models::{DamageSnapshot, HateEntry, ShieldDetailEntry};
 use crate::live::damage_id;
+use crate::live::data_tables::DataTables;
 use crate::live::dungeon_log::{BattleStateMachine, EncounterResetReason};
 use crate::live::entity_attr_store::EntityAttrStore;
 use crate::live::opcodes_models::class::{
@@ -10,6 +12,8 @@ use crate::live::opcodes_models::class::{
 use crate::live::opcodes_models::{
     AttrType, AttrValue, Encounter, Entity, PositionAttr, Skill, attr_type,
 };
+use crate::live::reset_rules::ResetRules;
+use crate::live::time_series::SeriesKind;
 use blueprotobuf_lib::blueprotobuf;
 use blueprotobuf_lib::blueprotobuf::{Attr, EDamageType, EEntityType};
 use bytes::Buf;
@@ -146,15 +150,23 @@ pub struct ParsedSkillCd {
 pub(crate) struct EnterSceneResult {
     pub scene_id: Option<i32>,
 }
-use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Increment global active combat time used for True DPS calculations.
-/// Adds a small grace window for single hits and ignores long idle gaps.
 fn update_active_damage_time(encounter: &mut Encounter, timestamp_ms: u128) {
+    accrue_active_time(
+        &mut encounter.active_combat_time_ms,
+        &mut encounter.last_combat_timestamp_ms,
+        timestamp_ms,
+    );
+}
+
+/// Adds the time since the previous hit to `active_ms`.
+/// Adds a small grace window for single hits and ignores long idle gaps.
+fn accrue_active_time(active_ms: &mut u128, last_hit_ms: &mut Option<u128>, timestamp_ms: u128) {
     const INACTIVITY_CUTOFF_MS: u128 = 3_000;
     const HIT_GRACE_MS: u128 = 500;
 
-    let additional = if let Some(last) = encounter.last_combat_timestamp_ms {
+    let additional = if let Some(last) = *last_hit_ms {
         let delta = timestamp_ms.saturating_sub(last);
         if delta <= INACTIVITY_CUTOFF_MS {
             delta
@@ -165,8 +177,8 @@ fn update_active_damage_time(encounter: &mut Encounter, timestamp_ms: u128) {
         HIT_GRACE_MS
     };
 
-    encounter.active_combat_time_ms = encounter.active_combat_time_ms.saturating_add(additional);
-    encounter.last_combat_timestamp_ms = Some(timestamp_ms);
+    *active_ms = active_ms.saturating_add(additional);
+    *last_hit_ms = Some(timestamp_ms);
 }
 
 pub fn on_server_change(encounter: &mut Encounter) {
@@ -331,6 +343,8 @@ pub fn process_sync_dungeon_data(
     battle_state: &mut BattleStateMachine,
     sync_dungeon_data: blueprotobuf::SyncDungeonData,
     encounter_has_stats: bool,
+    reset_rules: &ResetRules,
+    scene_id: Option<i32>,
 ) -> Option<EncounterResetReason> {
     let mut reset_reason = None;
     info!(
@@ -357,8 +371,13 @@ pub fn process_sync_dungeon_data(
                     complete,
                     nums
                 );
-                if let Some(reason) = battle_state.record_dungeon_target(target_id, nums, complete)
-                {
+                if let Some(reason) = battle_state.record_dungeon_target(
+                    target_id,
+                    nums,
+                    complete,
+                    reset_rules,
+                    scene_id,
+                ) {
                     reset_reason = Some(reason);
                 }
             }
@@ -375,6 +394,8 @@ pub fn process_sync_dungeon_dirty_data(
     battle_state: &mut BattleStateMachine,
     sync_dungeon_dirty_data: blueprotobuf::SyncDungeonDirtyData,
     encounter_has_stats: bool,
+    reset_rules: &ResetRules,
+    scene_id: Option<i32>,
 ) -> Option<EncounterResetReason> {
     info!(
         target: "app::live",
@@ -427,7 +448,9 @@ pub fn process_sync_dungeon_dirty_data(
             complete,
             nums
         );
-        if let Some(reason) = battle_state.record_dungeon_target(target_id, nums, complete) {
+        if let Some(reason) =
+            battle_state.record_dungeon_target(target_id, nums, complete, reset_rules, scene_id)
+        {
             reset_reason = Some(reason);
         }
     }
@@ -448,6 +471,7 @@ pub fn process_sync_to_me_delta_info(
     sync_to_me_delta_info: blueprotobuf::SyncToMeDeltaInfo,
     monitored_panel_attr_ids: &[i32],
     combat_target_filter: Option<i64>,
+    tables: &DataTables,
 ) -> SyncToMeDeltaResult {
     use crate::live::opcodes_models::attr_type::{ATTR_FIGHT_RESOURCES, ATTR_SKILL_ID};
 
@@ -509,6 +533,7 @@ pub fn process_sync_to_me_delta_info(
             base_delta,
             combat_target_filter,
             true,
+            tables,
         ) {
             result.local_damage_events = damage_events;
             result.local_damage_taken_events = damage_taken_events;
@@ -645,6 +670,7 @@ pub fn process_aoi_sync_delta(
     aoi_sync_delta: blueprotobuf::AoiSyncDelta,
     combat_target_filter: Option<i64>,
     collect_taken: bool,
+    tables: &DataTables,
 ) -> Option<(Vec<LocalDamageEvent>, Vec<LocalDamageTakenEvent>)> {
     let target_uuid = aoi_sync_delta.uuid?; // UUID =/= uid (have to >> 16)
     let target_uid = target_uuid >> 16;
@@ -684,14 +710,13 @@ pub fn process_aoi_sync_delta(
         return Some((Vec::new(), Vec::new())); // return ok since this variable usually doesn't exist
     };
 
-    let timestamp_ms = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
+    let timestamp_ms = now_ms() as u128;
+    let phase_index = encounter.phases.current_index();
     let mut target_hp_state = TargetHpState::from_attr_store(attr_store, target_uid);
     let mut local_damage_events = Vec::new();
     let mut local_damage_taken_events = Vec::new();
     let mut had_player_damage = false;
+    let mut had_local_player_damage = false;
     let mut had_allowed_combat = false;
     // Process Damage
     for sync_damage_info in skill_effect.damages {
@@ -717,10 +742,15 @@ pub fn process_aoi_sync_delta(
             .top_summoner_id
             .or(sync_damage_info.attacker_uuid)?;
         let attacker_uid = attacker_uuid >> 16;
+        // Summon damage counts for the summoner; the summon itself is kept as a sub-source.
+        let summon_uuid = sync_damage_info
+            .attacker_uuid
+            .filter(|&uuid| uuid != attacker_uuid);
 
         // Local copies of fields needed later (avoid holding map borrows across operations)
         let owner_id = sync_damage_info.owner_id?;
         let damage_id = damage_id::compute_damage_id(
+            tables,
             sync_damage_info.damage_source,
             owner_id,
             sync_damage_info.owner_level,
@@ -744,7 +774,7 @@ pub fn process_aoi_sync_delta(
         let is_boss_target = encounter
             .entity_uid_to_entity
             .get(&target_uid)
-            .map(|e| e.is_boss())
+            .map(|e| e.is_boss(tables))
             .unwrap_or(false);
 
         let target_monster_id = encounter
@@ -752,6 +782,14 @@ pub fn process_aoi_sync_delta(
             .get(&target_uid)
             .and_then(|entity| entity.monster_type_id);
 
+        let summon = summon_uuid.map(|uuid| {
+            let monster_id = encounter
+                .entity_uid_to_entity
+                .get(&(uuid >> 16))
+                .and_then(|entity| entity.monster_type_id);
+            (EEntityType::from(uuid), monster_id, uuid >> 16)
+        });
+
         // First update attacker-side state in its own scope (single mutable borrow)
         let (is_crit, is_lucky, attacker_entity_type_copy, was_heal_event) = {
             let attacker_entity = encounter
@@ -802,11 +840,23 @@ pub fn process_aoi_sync_delta(
                 encounter.total_effective_heal += effective_heal_value;
                 attacker_entity.healing.hits += 1;
                 attacker_entity.healing.total += actual_value;
+                attacker_entity
+                    .time_series
+                    .record(SeriesKind::Healing, timestamp_ms, actual_value);
+                attacker_entity
+                    .rolling
+                    .record(SeriesKind::Healing, timestamp_ms, actual_value);
                 attacker_entity.healing.effective_total += effective_heal_value;
                 skill.hits += 1;
                 skill.total_value += actual_value;
                 skill.effective_total_value += effective_heal_value;
 
+                let phase_stats = attacker_entity.phase_stats_mut(phase_index);
+                phase_stats
+                    .healing
+                    .add_hit(actual_value, is_crit_local, is_lucky_local);
+                phase_stats.healing.effective_total += effective_heal_value;
+
                 // Track per-skill per-target stats for healing
                 let key = (skill_key, target_uid);
                 let stats = attacker_entity.skill_heal_to_target.entry(key).or_default();
@@ -857,8 +907,22 @@ pub fn process_aoi_sync_delta(
                 }
                 attacker_entity.damage.hits += 1;
                 attacker_entity.damage.total += actual_value;
+                attacker_entity
+                    .time_series
+                    .record(SeriesKind::Damage, timestamp_ms, actual_value);
+                attacker_entity
+                    .rolling
+                    .record(SeriesKind::Damage, timestamp_ms, actual_value);
                 skill.hits += 1;
                 skill.total_value += actual_value;
+                if let Some((entity_type, monster_id, summon_uid)) = summon {
+                    skill.add_summon_hit(entity_type, monster_id, summon_uid, actual_value);
+                    attacker_entity.summon_damage.add_hit(
+                        actual_value,
+                        is_crit_local,
+                        is_lucky_local,
+                    );
+                }
 
                 if is_boss_target {
                     if is_crit_local {
@@ -876,6 +940,12 @@ pub fn process_aoi_sync_delta(
                     attacker_entity.damage_boss_only.total += actual_value;
                 }
 
+                attacker_entity.phase_stats_mut(phase_index).damage.add_hit(
+                    actual_value,
+                    is_crit_local,
+                    is_lucky_local,
+                );
+
                 // Track per-target totals
                 use std::collections::hash_map::Entry;
                 match attacker_entity.dmg_to_target.entry(target_uid) {
@@ -920,6 +990,7 @@ pub fn process_aoi_sync_delta(
 
         if allow_combat && !was_heal_event && attacker_entity_type_copy == EEntityType::EntChar {
             had_player_damage = true;
+            had_local_player_damage |= attacker_uid == encounter.local_player_uid;
         }
 
         if allow_combat && !was_heal_event {
@@ -974,8 +1045,21 @@ pub fn process_aoi_sync_delta(
                 }
                 defender_entity.taken.hits += 1;
                 defender_entity.taken.total += effective_value;
+                defender_entity.time_series.record(
+                    SeriesKind::Taken,
+                    timestamp_ms,
+                    effective_value,
+                );
+                defender_entity
+                    .rolling
+                    .record(SeriesKind::Taken, timestamp_ms, effective_value);
                 taken_skill.hits += 1;
                 taken_skill.total_value += effective_value;
+                defender_entity.phase_stats_mut(phase_index).taken.add_hit(
+                    effective_value,
+                    is_crit,
+                    is_lucky,
+                );
 
                 // Maintain a 2s sliding window of recent damage taken for death replay.
                 const REPLAY_WINDOW_MS: u128 = 2000;
@@ -1004,6 +1088,14 @@ pub fn process_aoi_sync_delta(
     if had_player_damage {
         update_active_damage_time(encounter, timestamp_ms);
     }
+    // With a target filter set, this delta belongs to the locked training dummy.
+    if had_local_player_damage && combat_target_filter.is_some() {
+        accrue_active_time(
+            &mut encounter.locked_target_damage_time_ms,
+            &mut encounter.last_locked_target_hit_ms,
+            timestamp_ms,
+        );
+    }
 
     if had_allowed_combat {
         if encounter.time_fight_start_ms == Default::default() {
@@ -1011,6 +1103,9 @@ pub fn process_aoi_sync_delta(
         }
 
         encounter.time_last_combat_packet_ms = timestamp_ms;
+        encounter
+            .phases
+            .ensure_started(encounter.time_fight_start_ms);
     }
     Some((local_damage_events, local_damage_taken_events))
 }
@@ -0,0 +1,193 @@
+use crate::live::opcodes_models::CombatStats;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Boss HP percentages that start a new phase unless configured otherwise.
+pub const DEFAULT_PHASE_HP_THRESHOLDS: [u8; 3] = [75, 50, 25];
+
+/// Why a phase started.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PhaseStartReason {
+    /// First phase of the encounter.
+    #[default]
+    EncounterStart,
+    /// A boss dropped below one of the configured HP thresholds.
+    BossHp,
+    /// The dungeon objective changed without ending the encounter.
+    Objective,
+}
+
+/// Start of one encounter phase. A phase lasts until the next one starts.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterPhase {
+    pub start_ms: u128,
+    pub reason: PhaseStartReason,
+    /// Boss that crossed the HP threshold, for `BossHp` phases.
+    pub boss_uid: Option<i64>,
+    pub boss_monster_id: Option<i32>,
+    /// Threshold the boss dropped below, in percent.
+    pub hp_percent: Option<u8>,
+    /// New dungeon target, for `Objective` phases.
+    pub target_id: Option<i32>,
+}
+
+/// Damage, healing and damage taken of one entity within one phase.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PhaseCombatStats {
+    pub damage: CombatStats,
+    pub healing: CombatStats,
+    pub taken: CombatStats,
+}
+
+/// Splits an encounter into phases.
+#[derive(Debug, Clone)]
+pub struct PhaseTracker {
+    /// Boss HP percentages that start a new phase, highest first.
+    thresholds: Vec<u8>,
+    phases: Vec<EncounterPhase>,
+    /// Per boss, how many thresholds it is already below.
+    crossed_thresholds: HashMap<i64, usize>,
+}
+
+impl Default for PhaseTracker {
+    fn default() -> Self {
+        Self {
+            thresholds: DEFAULT_PHASE_HP_THRESHOLDS.to_vec(),
+            phases: Vec::new(),
+            crossed_thresholds: HashMap::new(),
+        }
+    }
+}
+
+/// Keeps percentages in 1..=99, highest first, without duplicates.
+pub fn normalize_phase_hp_thresholds(thresholds: &mut Vec<u8>) {
+    thresholds.retain(|percent| (1..=99).contains(percent));
+    thresholds.sort_unstable_by(|a, b| b.cmp(a));
+    thresholds.dedup();
+}
+
+impl PhaseTracker {
+    pub fn set_thresholds(&mut self, mut thresholds: Vec<u8>) {
+        normalize_phase_hp_thresholds(&mut thresholds);
+        self.thresholds = thresholds;
+        self.crossed_thresholds.clear();
+    }
+
+    pub fn phases(&self) -> &[EncounterPhase] {
+        &self.phases
+    }
+
+    /// Index of the phase that combat is attributed to right now.
+    pub fn current_index(&self) -> usize {
+        self.phases.len().saturating_sub(1)
+    }
+
+    /// Opens the first phase once combat has started.
+    pub fn ensure_started(&mut self, timestamp_ms: u128) {
+        if self.phases.is_empty() {
+            self.phases.push(EncounterPhase {
+                start_ms: timestamp_ms,
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Tracks a boss HP update and starts a new phase when it drops below a threshold.
+    ///
+    /// Thresholds a boss is already below when first seen, or crossed before combat starts,
+    /// don't start a phase. Returns whether a phase was started.
+    pub fn observe_boss_hp(
+        &mut self,
+        boss_uid: i64,
+        boss_monster_id: Option<i32>,
+        current_hp: u128,
+        max_hp: u128,
+        timestamp_ms: u128,
+    ) -> bool {
+        if max_hp == 0 {
+            return false;
+        }
+        let current_hp = current_hp.min(max_hp);
+        let crossed = self
+            .thresholds
+            .iter()
+            .take_while(|&&percent| current_hp * 100 < u128::from(percent) * max_hp)
+            .count();
+        let previous = self.crossed_thresholds.insert(boss_uid, crossed);
+        let Some(previous) = previous else {
+            return false;
+        };
+        if crossed <= previous {
+            // Keep the furthest crossing so a boss healing back up doesn't re-trigger.
+            self.crossed_thresholds.insert(boss_uid, previous);
+            return false;
+        }
+        if self.phases.is_empty() {
+            return false;
+        }
+        self.phases.push(EncounterPhase {
+            start_ms: timestamp_ms,
+            reason: PhaseStartReason::BossHp,
+            boss_uid: Some(boss_uid),
+            boss_monster_id,
+            hp_percent: Some(self.thresholds[crossed - 1]),
+            target_id: None,
+        });
+        true
+    }
+
+    /// Starts a new phase for a dungeon objective change. Returns whether a phase was started.
+    pub fn start_objective_phase(&mut self, target_id: i32, timestamp_ms: u128) -> bool {
+        if self.phases.is_empty() {
+            return false;
+        }
+        self.phases.push(EncounterPhase {
+            start_ms: timestamp_ms,
+            reason: PhaseStartReason::Objective,
+            target_id: Some(target_id),
+            ..Default::default()
+        });
+        true
+    }
+
+    /// Drops all phases, keeping the configured thresholds.
+    pub fn clear(&mut self) {
+        self.phases.clear();
+        self.crossed_thresholds.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PhaseStartReason, PhaseTracker};
+
+    #[test]
+    fn starts_phases_at_hp_thresholds() {
+        let mut tracker = PhaseTracker::default();
+        tracker.set_thresholds(vec![25, 75, 50, 0, 75]);
+
+        // Seen before combat at 60%: 75% is already behind it.
+        assert!(!tracker.observe_boss_hp(7, Some(100), 60, 100, 0));
+        tracker.ensure_started(1_000);
+        assert!(!tracker.observe_boss_hp(7, Some(100), 55, 100, 2_000));
+        // Skipping past two thresholds at once starts a single phase.
+        assert!(tracker.observe_boss_hp(7, Some(100), 20, 100, 3_000));
+        assert!(!tracker.observe_boss_hp(7, Some(100), 40, 100, 4_000));
+        assert!(!tracker.observe_boss_hp(7, Some(100), 10, 100, 5_000));
+
+        let phases = tracker.phases();
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[0].reason, PhaseStartReason::EncounterStart);
+        assert_eq!(phases[1].reason, PhaseStartReason::BossHp);
+        assert_eq!(phases[1].hp_percent, Some(25));
+        assert_eq!(phases[1].start_ms, 3_000);
+        assert_eq!(tracker.current_index(), 1);
+
+        assert!(tracker.start_objective_phase(42, 6_000));
+        tracker.clear();
+        assert!(tracker.phases().is_empty());
+        assert!(!tracker.start_objective_phase(43, 7_000));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::live::dungeon_log::EncounterResetReason;
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+pub(crate) const BUILT_IN_RESET_RULES: &str = include_str!("../../meter-data/ResetRules.json");
+/// Longest deferred reset delay a rule file may configure.
+const MAX_RESET_DELAY_MS: u64 = 60_000;
+
+/// When encounters are split automatically: wipe detection, dungeon objectives to ignore and
+/// how long to wait before resetting.
+///
+/// Loaded from `meter-data/ResetRules.json` through the data table registry.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ResetRules {
+    /// Buffs on the local player that mean the party wiped.
+    pub wipe_buff_ids: HashSet<i32>,
+    /// Dungeon target IDs that never trigger objective-based resets.
+    #[serde(default)]
+    pub ignored_targets: HashSet<i32>,
+    pub new_objective_reset_delay_ms: u64,
+    pub wipe_reset_delay_ms: u64,
+    #[serde(default)]
+    pub scenes: HashMap<i32, SceneResetRules>,
+}
+
+/// Overrides for one scene.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SceneResetRules {
+    /// Set to false to never reset automatically in this scene, e.g. for world bosses.
+    #[serde(default = "default_true")]
+    pub auto_reset: bool,
+    /// Ignored in this scene on top of the global `ignored_targets`.
+    #[serde(default)]
+    pub ignored_targets: HashSet<i32>,
+    pub new_objective_reset_delay_ms: Option<u64>,
+    pub wipe_reset_delay_ms: Option<u64>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ResetRules {
+    /// The rules compiled into the binary.
+    fn default() -> Self {
+        parse_reset_rules(BUILT_IN_RESET_RULES).expect("invalid built-in ResetRules.json")
+    }
+}
+
+impl ResetRules {
+    pub fn is_wipe_buff(&self, base_id: i32) -> bool {
+        self.wipe_buff_ids.contains(&base_id)
+    }
+
+    pub fn ignores_target(&self, scene_id: Option<i32>, target_id: i32) -> bool {
+        self.ignored_targets.contains(&target_id)
+            || self
+                .scene(scene_id)
+                .is_some_and(|scene| scene.ignored_targets.contains(&target_id))
+    }
+
+    pub fn auto_reset_enabled(&self, scene_id: Option<i32>) -> bool {
+        self.scene(scene_id).is_none_or(|scene| scene.auto_reset)
+    }
+
+    pub fn reset_delay(&self, scene_id: Option<i32>, reason: EncounterResetReason) -> Duration {
+        let scene = self.scene(scene_id);
+        let delay_ms = match reason {
+            EncounterResetReason::NewObjective => scene
+                .and_then(|scene| scene.new_objective_reset_delay_ms)
+                .unwrap_or(self.new_objective_reset_delay_ms),
+            EncounterResetReason::Wipe => scene
+                .and_then(|scene| scene.wipe_reset_delay_ms)
+                .unwrap_or(self.wipe_reset_delay_ms),
+        };
+        Duration::from_millis(delay_ms)
+    }
+
+    fn scene(&self, scene_id: Option<i32>) -> Option<&SceneResetRules> {
+        scene_id.and_then(|scene_id| self.scenes.get(&scene_id))
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.wipe_buff_ids.iter().any(|&id| id <= 0) {
+            bail!("wipeBuffIds must be positive");
+        }
+        let delays = [
+            (None, self.new_objective_reset_delay_ms),
+            (None, self.wipe_reset_delay_ms),
+        ]
+        .into_iter()
+        .chain(self.scenes.iter().flat_map(|(&scene_id, scene)| {
+            [
+                scene.new_objective_reset_delay_ms,
+                scene.wipe_reset_delay_ms,
+            ]
+            .into_iter()
+            .flatten()
+            .map(move |delay_ms| (Some(scene_id), delay_ms))
+        }));
+        for (scene_id, delay_ms) in delays {
+            if delay_ms > MAX_RESET_DELAY_MS {
+                match scene_id {
+                    Some(scene_id) => bail!(
+                        "scene {} reset delay {}ms exceeds {}ms",
+                        scene_id,
+                        delay_ms,
+                        MAX_RESET_DELAY_MS
+                    ),
+                    None => bail!(
+                        "reset delay {}ms exceeds {}ms",
+                        delay_ms,
+                        MAX_RESET_DELAY_MS
+                    ),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_reset_rules(contents: &str) -> Result<ResetRules> {
+    let rules: ResetRules = serde_json::from_str(contents)?;
+    rules.validate()?;
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResetRules, parse_reset_rules};
+    use crate::live::dungeon_log::EncounterResetReason;
+    use std::time::Duration;
+
+    #[test]
+    fn applies_scene_overrides() {
+        let rules = parse_reset_rules(
+            r#"{
+                "wipeBuffIds": [510072],
+                "ignoredTargets": [1],
+                "newObjectiveResetDelayMs": 3000,
+                "wipeResetDelayMs": 2000,
+                "scenes": {
+                    "10": { "autoReset": false },
+                    "20": { "ignoredTargets": [2], "wipeResetDelayMs": 500 }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert!(rules.is_wipe_buff(510072));
+        assert!(rules.ignores_target(None, 1));
+        assert!(!rules.ignores_target(Some(10), 2));
+        assert!(rules.ignores_target(Some(20), 2));
+        assert!(!rules.auto_reset_enabled(Some(10)));
+        assert!(rules.auto_reset_enabled(Some(20)));
+        assert!(rules.auto_reset_enabled(None));
+        assert_eq!(
+            rules.reset_delay(Some(20), EncounterResetReason::Wipe),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            rules.reset_delay(Some(20), EncounterResetReason::NewObjective),
+            Duration::from_millis(3000)
+        );
+
+        // Typos and out-of-range delays are rejected.
+        assert!(parse_reset_rules(r#"{"wipeBuffId": [1]}"#).is_err());
+        assert!(
+            parse_reset_rules(
+                r#"{"wipeBuffIds": [1], "newObjectiveResetDelayMs": 3000, "wipeResetDelayMs": 3000,
+                    "scenes": {"5": {"wipeResetDelayMs": 600000}}}"#
+            )
+            .is_err()
+        );
+        assert!(ResetRules::default().is_wipe_buff(510072));
+    }
+}
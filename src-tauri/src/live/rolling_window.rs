@@ -0,0 +1,158 @@
+use crate::live::time_series::SeriesKind;
+use serde::{Deserialize, Serialize};
+
+/// Longest rolling window that can be configured, in seconds.
+pub const MAX_ROLLING_WINDOW_SECS: u32 = 60;
+/// Rolling windows shown in the live payload unless configured otherwise.
+pub const DEFAULT_ROLLING_WINDOWS_SECS: [u32; 3] = [5, 15, 30];
+
+/// Damage, healing and damage taken per second over one rolling window.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingWindowStats {
+    pub window_secs: u32,
+    pub dps: f64,
+    pub hps: f64,
+    /// Damage taken per second.
+    pub dtps: f64,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SecondTotals {
+    /// Unix second the totals belong to; older seconds are stale.
+    second: u64,
+    damage: u64,
+    healing: u64,
+    taken: u64,
+}
+
+/// Per-second totals of the last `MAX_ROLLING_WINDOW_SECS` seconds, kept in a ring indexed by
+/// unix second.
+#[derive(Debug, Default, Clone)]
+pub struct RollingTotals {
+    /// Allocated on the first recorded value, so idle entities stay small.
+    slots: Vec<SecondTotals>,
+}
+
+/// Keeps windows in 1..=`MAX_ROLLING_WINDOW_SECS`, shortest first, without duplicates.
+pub fn normalize_rolling_windows(windows_secs: &mut Vec<u32>) {
+    windows_secs.retain(|secs| (1..=MAX_ROLLING_WINDOW_SECS).contains(secs));
+    windows_secs.sort_unstable();
+    windows_secs.dedup();
+}
+
+impl RollingTotals {
+    pub fn record(&mut self, kind: SeriesKind, timestamp_ms: u128, value: u128) {
+        if self.slots.is_empty() {
+            self.slots = vec![SecondTotals::default(); MAX_ROLLING_WINDOW_SECS as usize];
+        }
+        let second = u64::try_from(timestamp_ms / 1000).unwrap_or(u64::MAX);
+        let slot = &mut self.slots[(second % u64::from(MAX_ROLLING_WINDOW_SECS)) as usize];
+        if slot.second != second {
+            if slot.second > second {
+                // Too old to fit in the ring anymore.
+                return;
+            }
+            *slot = SecondTotals {
+                second,
+                ..Default::default()
+            };
+        }
+        let value = u64::try_from(value).unwrap_or(u64::MAX);
+        let total = match kind {
+            SeriesKind::Damage => &mut slot.damage,
+            SeriesKind::Healing => &mut slot.healing,
+            SeriesKind::Taken => &mut slot.taken,
+        };
+        *total = total.saturating_add(value);
+    }
+
+    /// Per-second rates over the last `window_secs` seconds up to and including the second of
+    /// `now_ms`.
+    ///
+    /// Early in a fight the window reaches back before `fight_start_ms`, so the totals are
+    /// divided by the seconds fought so far instead. A `fight_start_ms` of 0 means no fight.
+    pub fn window(
+        &self,
+        window_secs: u32,
+        now_ms: u128,
+        fight_start_ms: u128,
+    ) -> RollingWindowStats {
+        let window_secs = window_secs.clamp(1, MAX_ROLLING_WINDOW_SECS);
+        let now_second = u64::try_from(now_ms / 1000).unwrap_or(u64::MAX);
+        let fought_secs = if fight_start_ms == 0 {
+            u64::from(window_secs)
+        } else {
+            let fight_start_second = u64::try_from(fight_start_ms / 1000).unwrap_or(u64::MAX);
+            now_second.saturating_sub(fight_start_second) + 1
+        };
+        let first_second = now_second.saturating_sub(u64::from(window_secs) - 1);
+        let (mut damage, mut healing, mut taken) = (0u64, 0u64, 0u64);
+        for slot in &self.slots {
+            if (first_second..=now_second).contains(&slot.second) {
+                damage = damage.saturating_add(slot.damage);
+                healing = healing.saturating_add(slot.healing);
+                taken = taken.saturating_add(slot.taken);
+            }
+        }
+        let secs = u64::from(window_secs).min(fought_secs) as f64;
+        RollingWindowStats {
+            window_secs,
+            dps: damage as f64 / secs,
+            hps: healing as f64 / secs,
+            dtps: taken as f64 / secs,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_ROLLING_WINDOW_SECS, RollingTotals, normalize_rolling_windows};
+    use crate::live::time_series::SeriesKind;
+
+    #[test]
+    fn sums_the_last_seconds() {
+        let mut totals = RollingTotals::default();
+        totals.record(SeriesKind::Damage, 100_000, 500);
+        totals.record(SeriesKind::Damage, 104_500, 1_000);
+        totals.record(SeriesKind::Healing, 104_900, 300);
+        totals.record(SeriesKind::Taken, 103_000, 50);
+
+        let last_5s = totals.window(5, 104_999, 90_000);
+        assert_eq!(last_5s.dps, 300.0);
+        assert_eq!(last_5s.hps, 60.0);
+        assert_eq!(last_5s.dtps, 10.0);
+        assert_eq!(totals.window(2, 103_000, 90_000).dps, 0.0);
+        // Nothing recorded lately: the burst decays to zero.
+        assert_eq!(totals.window(5, 200_000, 90_000).dps, 0.0);
+
+        // A second that wraps around the ring replaces the stale one.
+        let wrapped_ms = 100_000 + u128::from(MAX_ROLLING_WINDOW_SECS) * 1000;
+        totals.record(SeriesKind::Damage, wrapped_ms, 60);
+        assert_eq!(totals.window(1, wrapped_ms, 90_000).dps, 60.0);
+        // An event older than the ring is dropped.
+        totals.record(SeriesKind::Damage, 100_000, 1);
+        assert_eq!(totals.window(1, wrapped_ms, 90_000).dps, 60.0);
+
+        let mut windows = vec![30, 0, 5, 90, 15, 5];
+        normalize_rolling_windows(&mut windows);
+        assert_eq!(windows, vec![5, 15, 30]);
+    }
+
+    #[test]
+    fn divides_by_the_time_fought_when_the_fight_is_shorter_than_the_window() {
+        let mut totals = RollingTotals::default();
+        totals.record(SeriesKind::Damage, 100_200, 600);
+        totals.record(SeriesKind::Damage, 101_800, 600);
+
+        // Two seconds into the fight a 30s window covers only those two seconds.
+        assert_eq!(totals.window(30, 101_900, 100_200).dps, 600.0);
+        assert_eq!(totals.window(1, 101_900, 100_200).dps, 600.0);
+        // Without a fight start the full window is used.
+        assert_eq!(totals.window(30, 101_900, 0).dps, 40.0);
+    }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of casts kept per encounter.
+pub const ROTATION_LOG_CAP: usize = 1000;
+/// Casts this long before the first hit of an encounter are kept as part of the opener.
+pub const PRE_PULL_MS: u128 = 10_000;
+
+/// One skill cast of the local player.
+#[derive(specta::Type, Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RotationEntry {
+    pub skill_id: i32,
+    pub cast_ms: u128,
+    /// First hit of the local player after the cast and before the next one.
+    pub first_hit_ms: Option<u128>,
+    /// Skill key of that hit, as used in the skill breakdowns.
+    pub first_hit_skill_key: Option<i64>,
+    /// Monitored buffs that were up when the skill was cast.
+    pub active_buff_ids: Vec<i32>,
+}
+
+/// Ordered casts of the local player in the current encounter.
+#[derive(Debug, Default)]
+pub struct RotationLog {
+    entries: Vec<RotationEntry>,
+    /// Whether casts were dropped because the log hit `ROTATION_LOG_CAP`.
+    truncated: bool,
+    /// Fight start the entries belong to; 0 while no encounter is running.
+    window_start_ms: u128,
+}
+
+impl RotationLog {
+    /// Keeps the log in step with the encounter: a new fight keeps only the casts of its
+    /// opener, and an encounter reset clears the log.
+    fn sync_window(&mut self, window_start_ms: u128) {
+        if window_start_ms == self.window_start_ms {
+            return;
+        }
+        if window_start_ms == 0 {
+            self.entries.clear();
+        } else {
+            let opener_start_ms = window_start_ms.saturating_sub(PRE_PULL_MS);
+            self.entries
+                .retain(|entry| entry.cast_ms >= opener_start_ms);
+        }
+        self.truncated = false;
+        self.window_start_ms = window_start_ms;
+    }
+
+    pub fn record_cast(
+        &mut self,
+        skill_id: i32,
+        timestamp_ms: u128,
+        window_start_ms: u128,
+        active_buff_ids: Vec<i32>,
+    ) {
+        self.sync_window(window_start_ms);
+        if window_start_ms == 0 {
+            // Out of combat only the latest casts can still become an opener.
+            let opener_start_ms = timestamp_ms.saturating_sub(PRE_PULL_MS);
+            self.entries
+                .retain(|entry| entry.cast_ms >= opener_start_ms);
+        }
+        if self.entries.len() >= ROTATION_LOG_CAP {
+            self.truncated = true;
+            return;
+        }
+        self.entries.push(RotationEntry {
+            skill_id,
+            cast_ms: timestamp_ms,
+            active_buff_ids,
+            ..Default::default()
+        });
+    }
+
+    /// Marks the first hit after the latest cast.
+    pub fn record_hit(&mut self, skill_key: i64, timestamp_ms: u128, window_start_ms: u128) {
+        self.sync_window(window_start_ms);
+        if let Some(entry) = self.entries.last_mut()
+            && entry.first_hit_ms.is_none()
+        {
+            entry.first_hit_ms = Some(timestamp_ms);
+            entry.first_hit_skill_key = Some(skill_key);
+        }
+    }
+
+    pub fn entries(&self) -> &[RotationEntry] {
+        &self.entries
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PRE_PULL_MS, RotationLog};
+
+    #[test]
+    fn keeps_opener_and_first_hits() {
+        let mut log = RotationLog::default();
+        // Long before the pull: dropped once newer casts come in.
+        log.record_cast(1, 1_000, 0, Vec::new());
+        log.record_cast(2, 50_000, 0, vec![7]);
+        log.record_hit(111, 50_100, 0);
+        assert_eq!(log.entries().len(), 1);
+
+        // The first hit starts the fight; the pre-pull cast stays.
+        log.record_hit(222, 55_000, 55_000);
+        log.record_cast(3, 56_000, 55_000, Vec::new());
+        log.record_hit(333, 56_300, 55_000);
+        log.record_hit(444, 56_500, 55_000);
+
+        let entries = log.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].skill_id, 2);
+        assert_eq!(entries[0].active_buff_ids, vec![7]);
+        assert_eq!(entries[0].first_hit_ms, Some(50_100));
+        assert_eq!(entries[1].first_hit_skill_key, Some(333));
+
+        // A new fight far away from the old one starts a fresh log.
+        log.record_cast(
+            4,
+            56_000 + PRE_PULL_MS * 2,
+            56_000 + PRE_PULL_MS * 2,
+            Vec::new(),
+        );
+        assert_eq!(log.entries().len(), 1);
+        log.record_hit(1, 0, 0);
+        assert!(log.entries().is_empty());
+    }
+}
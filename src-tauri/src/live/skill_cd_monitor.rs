@@ -1,16 +1,13 @@
-use crate::database::now_ms;
+use crate::live::clock::now_ms;
 use crate::live::commands_models::SkillCdState;
+use crate::live::data_tables::{data_tables, parse_id_map};
 use crate::live::entity_attr_store::EntityAttrStore;
 use crate::live::opcodes_process::ParsedSkillCd;
-use log::{info, warn};
+use anyhow::Result;
+use log::info;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::path::PathBuf;
-use std::sync::LazyLock;
 
-const TEMP_ATTR_TABLE_RELATIVE: &str = "meter-data/TempAttrTable.json";
-const SKILL_EFFECT_TABLE_RELATIVE: &str = "meter-data/SkillEffectTable.json";
 const TAG_NO_CD_REDUCE: i32 = 103;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -26,7 +23,7 @@ struct RawTempAttrDef {
 }
 
 #[derive(Debug, Clone)]
-struct CdTempAttrDef {
+pub(crate) struct CdTempAttrDef {
     attr_type: i32,
     logic_type: i32,
     attr_params: Vec<i32>,
@@ -38,20 +35,6 @@ struct RawSkillEffectEntry {
     tags: Vec<i32>,
 }
 
-static CD_TEMP_ATTR_DEFS: LazyLock<HashMap<i32, CdTempAttrDef>> = LazyLock::new(|| {
-    load_cd_temp_attr_defs().unwrap_or_else(|err| {
-        warn!("[skill-cd] failed to load TempAttrTable.json: {}", err);
-        HashMap::new()
-    })
-});
-
-static SKILL_EFFECT_TAGS: LazyLock<HashMap<i32, Vec<i32>>> = LazyLock::new(|| {
-    load_skill_effect_tags().unwrap_or_else(|err| {
-        warn!("[skill-cd] failed to load SkillEffectTable.json: {}", err);
-        HashMap::new()
-    })
-});
-
 #[derive(Debug, Default)]
 pub struct SkillCdMonitor {
     /// Skill cooldown map keyed by skill level ID.
@@ -155,33 +138,8 @@ impl SkillCdMonitor {
     }
 }
 
-fn locate_meter_data_file(relative_path: &str) -> Option<PathBuf> {
-    let mut p = PathBuf::from(relative_path);
-    if p.exists() {
-        return Some(p);
-    }
-
-    p = PathBuf::from(format!("src-tauri/{}", relative_path));
-    if p.exists() {
-        return Some(p);
-    }
-
-    if let Ok(mut exe_dir) = std::env::current_exe() {
-        exe_dir.pop();
-        let candidate = exe_dir.join(relative_path);
-        if candidate.exists() {
-            return Some(candidate);
-        }
-    }
-
-    None
-}
-
-fn load_cd_temp_attr_defs() -> Result<HashMap<i32, CdTempAttrDef>, Box<dyn std::error::Error>> {
-    let path = locate_meter_data_file(TEMP_ATTR_TABLE_RELATIVE)
-        .ok_or_else(|| format!("{} not found in known locations", TEMP_ATTR_TABLE_RELATIVE))?;
-    let contents = fs::read_to_string(path)?;
-    let raw_map: HashMap<String, RawTempAttrDef> = serde_json::from_str(&contents)?;
+pub(crate) fn parse_cd_temp_attr_defs(contents: &str) -> Result<HashMap<i32, CdTempAttrDef>> {
+    let raw_map: HashMap<i32, RawTempAttrDef> = parse_id_map(contents)?;
 
     let mut result = HashMap::new();
     for raw in raw_map.into_values() {
@@ -201,23 +159,12 @@ fn load_cd_temp_attr_defs() -> Result<HashMap<i32, CdTempAttrDef>, Box<dyn std::
     Ok(result)
 }
 
-fn load_skill_effect_tags() -> Result<HashMap<i32, Vec<i32>>, Box<dyn std::error::Error>> {
-    let path = locate_meter_data_file(SKILL_EFFECT_TABLE_RELATIVE).ok_or_else(|| {
-        format!(
-            "{} not found in known locations",
-            SKILL_EFFECT_TABLE_RELATIVE
-        )
-    })?;
-    let contents = fs::read_to_string(path)?;
-    let raw_map: HashMap<String, RawSkillEffectEntry> = serde_json::from_str(&contents)?;
-
-    let mut result = HashMap::new();
-    for (key, value) in raw_map {
-        if let Ok(skill_level_id) = key.parse::<i32>() {
-            result.insert(skill_level_id, value.tags);
-        }
-    }
-    Ok(result)
+pub(crate) fn parse_skill_effect_tags(contents: &str) -> Result<HashMap<i32, Vec<i32>>> {
+    let raw_map: HashMap<i32, RawSkillEffectEntry> = parse_id_map(contents)?;
+    Ok(raw_map
+        .into_iter()
+        .map(|(skill_level_id, entry)| (skill_level_id, entry.tags))
+        .collect())
 }
 
 fn temp_attr_matches(def: &CdTempAttrDef, skill_id: i32, skill_tags: &HashSet<i32>) -> bool {
@@ -259,7 +206,9 @@ fn calculate_skill_cd(
 
     let skill_id = skill_level_id / 100;
     let tag_lookup_skill_level_id = skill_id * 100 + 1;
-    let skill_tags_vec = SKILL_EFFECT_TAGS
+    let tables = data_tables();
+    let skill_tags_vec = tables
+        .skill_effect_tags
         .get(&tag_lookup_skill_level_id)
         .cloned()
         .unwrap_or_default();
@@ -289,9 +238,9 @@ fn calculate_skill_cd(
         if *value == 0 {
             continue;
         }
-        let Some(def) = CD_TEMP_ATTR_DEFS.get(temp_attr_id) else {
+        let Some(def) = tables.cd_temp_attr_defs.get(temp_attr_id) else {
             info!(
-                "[skill-cd]   temp_attr {} value={} def_found=false (not in CD_TEMP_ATTR_DEFS), skip",
+                "[skill-cd]   temp_attr {} value={} def_found=false (not in TempAttrTable), skip",
                 temp_attr_id, value
             );
             continue;
@@ -1,26 +1,41 @@
-use crate::database::{EncounterMetadata, PlayerNameEntry, now_ms, save_encounter};
+use crate::database::{
+    EncounterMetadata, PlayerNameEntry, TrainingDummyRunMetadata, save_encounter,
+};
+use crate::live::aggro_history::top_threat;
 use crate::live::bootstrap_snapshot::MonitorRuntimeSnapshot;
 use crate::live::buff_monitor::{BossBuffMonitors, BuffMonitor};
+use crate::live::buff_uptime::{BuffUptimeEntry, BuffUptimeTracker, UptimeWindow};
+use crate::live::clock::now_ms;
 use crate::live::commands_models::{
     CounterUpdateState, DeathRecord, FightResourceEntry, FightResourceState, PanelAttrState,
     ShieldDetailEntry, SkillCdState, TrainingDummyState,
 };
 use crate::live::counter_tracker::{BuffCounterTracker, CounterRule};
+use crate::live::data_tables::{DataTables, data_tables};
 use crate::live::dungeon_log::{BattleStateMachine, EncounterResetReason};
 use crate::live::entity_attr_store::EntityAttrStore;
 use crate::live::event_manager::EventManager;
 use crate::live::monster_registry;
+use crate::live::name_registry::{
+    NameLocale, labeled, monster_name, scene_label, scene_name, set_name_locale,
+};
 use crate::live::opcodes_models::{AttrType, AttrValue, Encounter, Entity};
+use crate::live::opcodes_process::LocalDamageEvent;
+use crate::live::rolling_window::{DEFAULT_ROLLING_WINDOWS_SECS, normalize_rolling_windows};
+use crate::live::rotation_log::RotationLog;
 use crate::live::skill_cd_monitor::SkillCdMonitor;
 use crate::live::training_dummy::{
-    TrainingDummyMonsterId, TrainingDummyRuntime, inspect_aoi_delta,
+    TrainingDummyMonsterId, TrainingDummyOptions, TrainingDummyPhase, TrainingDummyRuntime,
+    inspect_aoi_delta,
 };
+use crate::packets::session_file::SessionFileWriter;
+use crate::packets::stream_table::ConnectionId;
 use blueprotobuf_lib::blueprotobuf;
 use blueprotobuf_lib::blueprotobuf::AoiSyncDelta;
 use blueprotobuf_lib::blueprotobuf::EEntityType;
-use log::{info, warn};
+use log::{debug, info, warn};
 use std::collections::{HashMap, HashSet};
-use std::time::{Duration, Instant};
+use std::path::PathBuf;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
 
 /// Represents the possible events that can be handled by the state manager.
@@ -46,6 +61,10 @@ pub enum StateEvent {
     SyncToMeDeltaInfo(blueprotobuf::SyncToMeDeltaInfo),
     /// A sync near delta info event.
     SyncNearDeltaInfo(blueprotobuf::SyncNearDeltaInfo),
+    /// The full buff list of one entity, sent on scene entry.
+    BuffInfoSync(blueprotobuf::BuffInfoSync),
+    /// A player was revived.
+    NotifyReviveUser(blueprotobuf::NotifyReviveUser),
     /// A reset encounter event. Contains whether this was a manual reset by the user.
     #[allow(dead_code)]
     ResetEncounter {
@@ -54,6 +73,9 @@ pub enum StateEvent {
     },
 }
 
+/// Nearby characters' `BuffInfoSync`s kept until the local player is known.
+const MAX_PENDING_BUFF_INFO_SYNCS: usize = 32;
+
 /// Represents the state of the application.
 #[derive(Debug)]
 pub struct AppState {
@@ -69,6 +91,8 @@ pub struct AppState {
     pub initial_scene_change_handled: bool,
     /// Event update rate in milliseconds (default: 200ms). Controls how often events are emitted to frontend.
     pub event_update_rate_ms: u64,
+    /// Rolling DPS windows in the live payload, in seconds.
+    pub rolling_windows_secs: Vec<u32>,
     /// Centralized store for all parsed Attr / TempAttr values.
     pub attr_store: EntityAttrStore,
     /// Estimated offset: local_ms - server_ms. Used to convert server buff
@@ -76,8 +100,8 @@ pub struct AppState {
     pub server_clock_offset: i64,
     /// battle state machine for objective/state driven resets.
     pub battle_state: BattleStateMachine,
-    /// If set, automatic reset can execute only after this timestamp.
-    pub pending_auto_reset: Option<Instant>,
+    /// If set, automatic reset can execute only after this Unix timestamp in milliseconds.
+    pub pending_auto_reset: Option<i64>,
     /// Runtime state for training dummy mode.
     pub training_dummy: TrainingDummyRuntime,
     /// UIDs whose display names have already been pushed to the monster overlay.
@@ -85,12 +109,25 @@ pub struct AppState {
     /// Set to true whenever a new DeathRecord has been appended to an Entity, signalling that
     /// the next emit cycle should push a full death-replay snapshot.
     pub death_snapshot_dirty: bool,
+    /// Active recorder teeing the decoded packet stream into a session file, if any.
+    pub session_recorder: Option<SessionFileWriter>,
+    /// Capture stream the meter is currently following. Switches on every server change so
+    /// packets from a stale handover stream or a second client don't leak into the encounter.
+    pub active_connection_id: Option<ConnectionId>,
+    /// Character `BuffInfoSync`s that arrived before the local player was known, newest per
+    /// character, at most `MAX_PENDING_BUFF_INFO_SYNCS`.
+    pub pending_buff_info_syncs: Vec<blueprotobuf::BuffInfoSync>,
+    /// When set, finished encounters are collected here instead of being saved to the
+    /// database. Used by headless replays.
+    pub finished_encounters: Option<Vec<Encounter>>,
 }
 
 #[derive(Debug)]
 pub struct EntityMonitor {
     pub uid: i64,
     pub buff_monitor: BuffMonitor,
+    pub buff_uptime: BuffUptimeTracker,
+    pub rotation_log: RotationLog,
     pub skill_cd_monitor: SkillCdMonitor,
     pub monitored_panel_attr_ids: Vec<i32>,
     pub fight_res_state: Option<FightResourceState>,
@@ -102,6 +139,8 @@ impl EntityMonitor {
         Self {
             uid,
             buff_monitor: BuffMonitor::new(),
+            buff_uptime: BuffUptimeTracker::default(),
+            rotation_log: RotationLog::default(),
             skill_cd_monitor: SkillCdMonitor::new(),
             monitored_panel_attr_ids: Vec::new(),
             fight_res_state: None,
@@ -115,18 +154,75 @@ impl EntityMonitor {
         self.fight_res_state = None;
         self.counter_tracker.reset_counts();
     }
+
+    /// Credits buff uptime up to the last combat packet. Call before the buff monitor's active
+    /// buffs change.
+    fn advance_buff_uptime(&mut self, encounter: &Encounter) {
+        self.buff_uptime
+            .advance(&self.buff_monitor.active_buffs, UptimeWindow::of(encounter));
+    }
+
+    /// Uptime of the monitored buffs in the current encounter.
+    fn buff_uptime_entries(&mut self, encounter: &Encounter) -> Vec<BuffUptimeEntry> {
+        self.advance_buff_uptime(encounter);
+        self.buff_uptime.entries(
+            |base_id| self.buff_monitor.is_monitored(base_id),
+            UptimeWindow::of(encounter),
+        )
+    }
+
+    /// Adds a cast to the rotation log, along with the monitored buffs that are up.
+    fn record_rotation_cast(&mut self, skill_id: i32, encounter: &Encounter) {
+        let mut active_buff_ids: Vec<i32> = self
+            .buff_monitor
+            .active_buffs
+            .values()
+            .map(|buff| buff.base_id)
+            .filter(|&base_id| self.buff_monitor.is_monitored(base_id))
+            .collect();
+        active_buff_ids.sort_unstable();
+        active_buff_ids.dedup();
+        self.rotation_log.record_cast(
+            skill_id,
+            now_ms() as u128,
+            encounter.time_fight_start_ms,
+            active_buff_ids,
+        );
+    }
+
+    fn record_rotation_hits(&mut self, events: &[LocalDamageEvent], encounter: &Encounter) {
+        if let Some(event) = events.first() {
+            self.rotation_log.record_hit(
+                event.skill_key,
+                now_ms() as u128,
+                encounter.time_fight_start_ms,
+            );
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+/// Encounter duration and active combat time, capped at the duration.
+fn combat_spans(encounter: &Encounter) -> (u128, u128) {
+    let elapsed_ms = encounter
+        .time_last_combat_packet_ms
+        .saturating_sub(encounter.time_fight_start_ms);
+    (elapsed_ms, encounter.active_combat_time_ms.min(elapsed_ms))
+}
+
+#[derive(Debug)]
 pub enum LiveControlCommand {
     StateEvent(StateEvent),
     TogglePauseEncounter,
     ApplyMonitorRuntimeSnapshot(MonitorRuntimeSnapshot),
     StartTrainingDummy {
         monster_id: TrainingDummyMonsterId,
+        options: TrainingDummyOptions,
     },
     StopTrainingDummy,
     SetEventUpdateRateMs(u64),
+    SetPhaseHpThresholds(Vec<u8>),
+    SetRollingWindows(Vec<u32>),
+    SetNameLocale(NameLocale),
     SetMonitoredBuffs(Vec<i32>),
     SetBossMonitoredBuffs {
         global_ids: Vec<i32>,
@@ -136,6 +232,9 @@ pub enum LiveControlCommand {
     SetMonitoredSkills(Vec<i32>),
     SetMonitorAllBuff(bool),
     SetBuffCounterRules(Vec<CounterRule>),
+    /// Starts writing captured packets to an already created session file.
+    StartSessionRecording(Box<SessionFileWriter>),
+    StopSessionRecording,
 }
 
 impl AppState {
@@ -151,6 +250,7 @@ impl AppState {
             boss_buff_monitors: BossBuffMonitors::new(),
             initial_scene_change_handled: false,
             event_update_rate_ms: 200,
+            rolling_windows_secs: DEFAULT_ROLLING_WINDOWS_SECS.to_vec(),
             attr_store: EntityAttrStore::with_capacity(256),
             server_clock_offset: 0,
             battle_state: BattleStateMachine::default(),
@@ -158,6 +258,10 @@ impl AppState {
             training_dummy: TrainingDummyRuntime::default(),
             sent_overlay_uids: HashSet::new(),
             death_snapshot_dirty: false,
+            session_recorder: None,
+            active_connection_id: None,
+            pending_buff_info_syncs: Vec::new(),
+            finished_encounters: None,
         }
     }
 
@@ -298,6 +402,121 @@ fn encounter_has_stats(encounter: &Encounter) -> bool {
             .any(|e| e.damage.hits > 0 || e.healing.hits > 0 || e.taken.hits > 0)
 }
 
+/// Closes the player's current death on the life timeline, if there is one.
+fn record_player_revive(encounter: &mut Encounter, uid: i64, timestamp_ms: u128) {
+    if let Some(entity) = encounter.entity_uid_to_entity.get_mut(&uid)
+        && entity.entity_type == EEntityType::EntChar
+        && entity.life_timeline.record_revive(timestamp_ms)
+    {
+        info!(target: "app::live", "Player revived uid={}", uid);
+    }
+}
+
+/// Starts a new phase when a boss drops below one of the configured HP thresholds.
+fn observe_boss_hp(state: &mut AppState, mut uids: Vec<i64>, tables: &DataTables) {
+    uids.sort_unstable();
+    uids.dedup();
+    let timestamp_ms = now_ms() as u128;
+    for uid in uids {
+        let Some(monster_id) = state
+            .encounter
+            .entity_uid_to_entity
+            .get(&uid)
+            .filter(|entity| entity.is_boss(tables))
+            .map(|entity| entity.monster_type_id)
+        else {
+            continue;
+        };
+        let hp = |attr_type| {
+            state
+                .attr_store
+                .attr(uid, attr_type)
+                .and_then(AttrValue::as_int)
+                .and_then(|value| u128::try_from(value).ok())
+        };
+        let (Some(current_hp), Some(max_hp)) = (hp(AttrType::CurrentHp), hp(AttrType::MaxHp))
+        else {
+            continue;
+        };
+        if let Some(entity) = state.encounter.entity_uid_to_entity.get_mut(&uid) {
+            entity.hp_history.record(current_hp, max_hp, timestamp_ms);
+        }
+        if state
+            .encounter
+            .phases
+            .observe_boss_hp(uid, monster_id, current_hp, max_hp, timestamp_ms)
+        {
+            info!(
+                target: "app::live",
+                "Phase started: boss uid={} hp={}/{}",
+                uid,
+                current_hp,
+                max_hp
+            );
+        }
+    }
+}
+
+/// Records who is at the top of each updated boss hate list.
+fn observe_boss_aggro(state: &mut AppState, mut uids: Vec<i64>, tables: &DataTables) {
+    uids.sort_unstable();
+    uids.dedup();
+    let timestamp_ms = now_ms() as u128;
+    for uid in uids {
+        let holder = state
+            .attr_store
+            .hate_lists()
+            .get(&uid)
+            .and_then(|entries| top_threat(entries));
+        let Some(entity) = state
+            .encounter
+            .entity_uid_to_entity
+            .get_mut(&uid)
+            .filter(|entity| entity.is_boss(tables))
+        else {
+            continue;
+        };
+        if entity.aggro.record(holder, timestamp_ms) {
+            debug!(
+                target: "app::live",
+                "Aggro changed: boss uid={} holder={:?}",
+                uid,
+                holder
+            );
+        }
+    }
+}
+
+/// Starts a new phase when the dungeon objective changed without ending the encounter.
+fn record_objective_phase(state: &mut AppState, previous_target_id: Option<i32>) {
+    let Some(target_id) = state.battle_state.active_target_id else {
+        return;
+    };
+    if previous_target_id == Some(target_id) {
+        return;
+    }
+    if state
+        .encounter
+        .phases
+        .start_objective_phase(target_id, now_ms() as u128)
+    {
+        info!(target: "app::live", "Phase started: objective target_id={}", target_id);
+    }
+}
+
+/// Bosses and a few extra monsters get their own buff monitor.
+fn should_monitor_monster_buffs(encounter: &Encounter, uid: i64, tables: &DataTables) -> bool {
+    encounter
+        .entity_uid_to_entity
+        .get(&uid)
+        .is_some_and(|entity| {
+            entity.is_boss(tables)
+                || entity
+                    .monster_type_id
+                    .is_some_and(|id| monster_registry::is_extra_buff_monitored_monster(tables, id))
+        })
+}
+
 fn build_training_dummy_state(runtime: &TrainingDummyRuntime) -> TrainingDummyState {
     TrainingDummyState {
         phase: runtime.phase,
@@ -335,51 +554,175 @@ fn build_encounter_metadata(
         is_manually_reset: is_manual,
         boss_monster_ids,
         player_names,
+        phases: encounter.phases.phases().to_vec(),
+        training_dummy_run: None,
+    }
+}
+
+/// The local player's run, if the encounter is a training dummy segment that ran its full
+/// length.
+fn completed_training_dummy_run(state: &AppState) -> Option<TrainingDummyRunMetadata> {
+    let runtime = &state.training_dummy;
+    if runtime.phase != TrainingDummyPhase::PendingRollover {
+        return None;
     }
+    let monster_id = runtime.selected_monster_id?;
+    let player_id = state.encounter.local_player_uid;
+    let entity = state
+        .encounter
+        .entity_uid_to_entity
+        .get(&player_id)
+        .filter(|entity| entity.damage.hits > 0)?;
+    let (elapsed_ms, active_ms) = combat_spans(&state.encounter);
+    let to_i64 = |value: u128| i64::try_from(value).unwrap_or(i64::MAX);
+    Some(TrainingDummyRunMetadata {
+        monster_id: monster_id.id(),
+        segment_secs: runtime.options.segment_secs,
+        stop_after_damage_secs: runtime.options.stop_after_damage_secs,
+        duration_ms: to_i64(elapsed_ms),
+        active_ms: to_i64(active_ms),
+        total_dmg: to_i64(entity.damage.total),
+        player_id,
+        class_id: entity.class_id,
+        class_spec: entity.class_spec as i32,
+        ability_score: entity.ability_score,
+    })
 }
 
-fn persist_and_save_encounter(state: &mut AppState, is_manual: bool, source: &str) {
+/// Copies the local player's buff uptime and rotation and the bosses' debuff coverage onto the
+/// encounter entities so they are saved with it.
+fn store_encounter_extras(state: &mut AppState) {
+    if state.encounter.time_fight_start_ms == 0 {
+        return;
+    }
+    let buff_uptime = state.local_monitor.buff_uptime_entries(&state.encounter);
+    let local_player_uid = state.encounter.local_player_uid;
+    if let Some(entity) = state
+        .encounter
+        .entity_uid_to_entity
+        .get_mut(&local_player_uid)
+    {
+        entity.buff_uptime = buff_uptime;
+        entity.rotation = state.local_monitor.rotation_log.entries().to_vec();
+        entity.rotation_truncated = state.local_monitor.rotation_log.is_truncated();
+    }
+
+    let window = UptimeWindow::of(&state.encounter);
+    let boss_uids: Vec<i64> = state
+        .boss_buff_monitors
+        .debuff_trackers
+        .keys()
+        .copied()
+        .collect();
+    for boss_uid in boss_uids {
+        state
+            .boss_buff_monitors
+            .advance_debuff_uptime(boss_uid, window);
+        let monitors = &state.boss_buff_monitors;
+        let (Some(monitor), Some(tracker)) = (
+            monitors.monitors.get(&boss_uid),
+            monitors.debuff_trackers.get(&boss_uid),
+        ) else {
+            continue;
+        };
+        let debuff_uptime = tracker.entries(|base_id| monitor.is_monitored(base_id), window);
+        let debuff_applications = tracker.applications(
+            |base_id| monitor.is_monitored(base_id),
+            |caster_uid| {
+                state
+                    .encounter
+                    .entity_uid_to_entity
+                    .get(&caster_uid)
+                    .filter(|entity| {
+                        entity.entity_type == EEntityType::EntChar && !entity.name.is_empty()
+                    })
+                    .map(|entity| entity.name.clone())
+            },
+        );
+        if let Some(entity) = state.encounter.entity_uid_to_entity.get_mut(&boss_uid) {
+            entity.debuff_uptime = debuff_uptime;
+            entity.debuff_applications = debuff_applications;
+        }
+    }
+}
+
+pub(crate) fn persist_and_save_encounter(state: &mut AppState, is_manual: bool, source: &str) {
     hydrate_entities_from_attr_store(state);
+    store_encounter_extras(state);
+    let tables = data_tables();
     let mut boss_monster_ids: Vec<i32> = state
         .encounter
         .entity_uid_to_entity
         .values()
-        .filter(|entity| entity.is_boss())
+        .filter(|entity| entity.is_boss(&tables))
         .filter_map(|entity| entity.monster_type_id)
         .collect();
     boss_monster_ids.sort_unstable();
     boss_monster_ids.dedup();
     let player_names = collect_player_names(&state.encounter);
-    let metadata =
+    let mut metadata =
         build_encounter_metadata(&state.encounter, boss_monster_ids, player_names, is_manual);
+    metadata.training_dummy_run = completed_training_dummy_run(state);
+    if let Some(run) = &metadata.training_dummy_run {
+        info!(
+            target: "app::live",
+            "training_dummy_run_completed monster={} duration_ms={} active_ms={} total_dmg={}",
+            labeled(monster_name(run.monster_id), run.monster_id),
+            run.duration_ms,
+            run.active_ms,
+            run.total_dmg
+        );
+    }
 
     if metadata.started_at_ms > 0 {
         info!(
             target: "app::live",
-            "persist_encounter_on_{} started_at_ms={} ended_at_ms={:?} total_dmg={} total_heal={} scene_id={:?} players={} bosses={} is_manual={}",
+            "persist_encounter_on_{} started_at_ms={} ended_at_ms={:?} total_dmg={} total_heal={} scene={} players={} bosses={:?} is_manual={}",
             source,
             metadata.started_at_ms,
             metadata.ended_at_ms,
             metadata.total_dmg,
             metadata.total_heal,
-            metadata.scene_id,
+            scene_label(metadata.scene_id),
             metadata.player_names.len(),
-            metadata.boss_monster_ids.len(),
+            metadata
+                .boss_monster_ids
+                .iter()
+                .map(|&id| labeled(monster_name(id), id))
+                .collect::<Vec<_>>(),
             metadata.is_manually_reset
         );
-        save_encounter(&state.encounter, &metadata);
+        match state.finished_encounters.as_mut() {
+            Some(finished) => finished.push(state.encounter.clone()),
+            None => save_encounter(&state.encounter, &metadata),
+        }
     } else {
         warn!(
             target: "app::live",
-            "persist_encounter_on_{}_skipped reason=time_fight_start_ms_zero total_dmg={} total_heal={} scene_id={:?}",
+            "persist_encounter_on_{}_skipped reason=time_fight_start_ms_zero total_dmg={} total_heal={} scene={}",
             source,
             metadata.total_dmg,
             metadata.total_heal,
-            metadata.scene_id
+            scene_label(metadata.scene_id)
         );
     }
 }
 
+fn stop_session_recording(state: &mut AppState) {
+    let Some(writer) = state.session_recorder.take() else {
+        return;
+    };
+    let records = writer.records();
+    match writer.finish() {
+        Ok(_) => info!(
+            target: "app::live",
+            "session_record_stopped records={}",
+            records
+        ),
+        Err(e) => warn!(target: "app::live", "session_record_finish_failed err={}", e),
+    }
+}
+
 /// Manages the state of the application.
 #[derive(Clone)]
 pub struct AppStateManager {
@@ -403,8 +746,10 @@ impl AppStateManager {
         if events.is_empty() {
             return;
         }
+        // One lookup per batch rather than per hit.
+        let tables = data_tables();
         for event in events {
-            self.apply_event(state, event);
+            self.apply_event(state, event, &tables);
         }
     }
 
@@ -429,7 +774,7 @@ impl AppStateManager {
         self.send_control(LiveControlCommand::TogglePauseEncounter)
     }
 
-    fn apply_event(&self, state: &mut AppState, event: StateEvent) {
+    fn apply_event(&self, state: &mut AppState, event: StateEvent, tables: &DataTables) {
         // Check if encounter is paused for events that should be dropped
         if state.is_encounter_paused()
             && matches!(
@@ -485,21 +830,39 @@ impl AppStateManager {
                 self.apply_battle_state_resets_if_needed(state);
             }
             StateEvent::SyncToMeDeltaInfo(data) => {
-                counter_dirty |= self.process_sync_to_me_delta_info(state, data);
+                counter_dirty |= self.process_sync_to_me_delta_info(state, data, tables);
                 self.apply_battle_state_resets_if_needed(state);
                 // Note: Player names are automatically stored in the database via UpsertEntity tasks
                 // No need to maintain a separate cache anymore
             }
             StateEvent::SyncNearDeltaInfo(data) => {
-                counter_dirty |= self.process_sync_near_delta_info(state, data);
+                counter_dirty |= self.process_sync_near_delta_info(state, data, tables);
                 // Note: Player names are automatically stored in the database via UpsertEntity tasks
                 // No need to maintain a separate cache anymore
             }
+            StateEvent::BuffInfoSync(data) => {
+                counter_dirty |= self.process_buff_info_sync(state, data, tables);
+            }
+            StateEvent::NotifyReviveUser(data) => {
+                if let Some(uuid) = data.v_actor_uuid {
+                    record_player_revive(&mut state.encounter, uuid >> 16, now_ms() as u128);
+                }
+            }
             StateEvent::ResetEncounter { is_manual } => {
                 state.pending_auto_reset = None;
                 self.reset_encounter(state, is_manual);
             }
         }
+        if state.encounter.local_player_uid != 0 && !state.pending_buff_info_syncs.is_empty() {
+            let local_player_uid = state.encounter.local_player_uid;
+            // Only the local player's sync is used; other characters' buffs aren't tracked.
+            let local_sync = std::mem::take(&mut state.pending_buff_info_syncs)
+                .into_iter()
+                .find(|sync| sync.uuid.is_some_and(|uuid| uuid >> 16 == local_player_uid));
+            if let Some(data) = local_sync {
+                counter_dirty |= self.process_buff_info_sync(state, data, tables);
+            }
+        }
         if counter_dirty {
             emit_buff_counter_update_if_needed(
                 state,
@@ -509,13 +872,13 @@ impl AppStateManager {
                     .build_payload(&state.attr_store, state.encounter.local_player_uid),
             );
         }
-        self.apply_attr_store_changes(state);
+        self.apply_attr_store_changes(state, tables);
     }
 
     pub(crate) fn apply_control_command(&self, state: &mut AppState, command: LiveControlCommand) {
         match command {
             LiveControlCommand::StateEvent(event) => {
-                self.apply_event(state, event);
+                self.apply_event(state, event, &data_tables());
             }
             LiveControlCommand::TogglePauseEncounter => {
                 let paused = state.encounter.is_encounter_paused;
@@ -524,9 +887,12 @@ impl AppStateManager {
             LiveControlCommand::ApplyMonitorRuntimeSnapshot(snapshot) => {
                 self.apply_monitor_runtime_snapshot_with_state(state, snapshot);
             }
-            LiveControlCommand::StartTrainingDummy { monster_id } => {
+            LiveControlCommand::StartTrainingDummy {
+                monster_id,
+                options,
+            } => {
                 let previous = build_training_dummy_state(&state.training_dummy);
-                state.training_dummy.arm(monster_id);
+                state.training_dummy.arm(monster_id, options);
                 emit_training_dummy_update_if_changed(state, previous);
             }
             LiveControlCommand::StopTrainingDummy => {
@@ -542,6 +908,14 @@ impl AppStateManager {
             LiveControlCommand::SetEventUpdateRateMs(rate_ms) => {
                 state.event_update_rate_ms = rate_ms;
             }
+            LiveControlCommand::SetPhaseHpThresholds(thresholds) => {
+                state.encounter.phases.set_thresholds(thresholds);
+            }
+            LiveControlCommand::SetRollingWindows(mut windows_secs) => {
+                normalize_rolling_windows(&mut windows_secs);
+                state.rolling_windows_secs = windows_secs;
+            }
+            LiveControlCommand::SetNameLocale(locale) => set_name_locale(locale),
             LiveControlCommand::SetMonitoredBuffs(buff_base_ids) => {
                 state.local_monitor.buff_monitor.monitored_buff_ids =
                     buff_base_ids.into_iter().collect();
@@ -590,6 +964,13 @@ impl AppStateManager {
             LiveControlCommand::SetBuffCounterRules(rules) => {
                 state.local_monitor.counter_tracker.set_rules(rules);
             }
+            LiveControlCommand::StartSessionRecording(writer) => {
+                stop_session_recording(state);
+                state.session_recorder = Some(*writer);
+            }
+            LiveControlCommand::StopSessionRecording => {
+                stop_session_recording(state);
+            }
         }
     }
 
@@ -606,8 +987,11 @@ impl AppStateManager {
 
         info!(
             target: "app::live",
-            "[runtime-monitor] applying snapshot: event_update_rate_ms={} skill_enabled={} monster_enabled={}",
+            "[runtime-monitor] applying snapshot: event_update_rate_ms={} phase_hp_thresholds={:?} rolling_windows_secs={:?} locale={} skill_enabled={} monster_enabled={}",
             live.event_update_rate_ms,
+            live.phase_hp_thresholds,
+            live.rolling_windows_secs,
+            live.locale,
             skill.enabled,
             monster.enabled
         );
@@ -647,6 +1031,20 @@ impl AppStateManager {
             state,
             LiveControlCommand::SetEventUpdateRateMs(live.event_update_rate_ms),
         );
+        self.apply_control_command(
+            state,
+            LiveControlCommand::SetPhaseHpThresholds(live.phase_hp_thresholds),
+        );
+        self.apply_control_command(
+            state,
+            LiveControlCommand::SetRollingWindows(live.rolling_windows_secs),
+        );
+        self.apply_control_command(
+            state,
+            LiveControlCommand::SetNameLocale(
+                NameLocale::parse(&live.locale).unwrap_or(NameLocale::ZhCn),
+            ),
+        );
         self.apply_control_command(
             state,
             LiveControlCommand::SetMonitorAllBuff(skill.monitor_all_buff),
@@ -713,7 +1111,10 @@ impl AppStateManager {
             state.encounter.current_scene_id = Some(scene_id);
             state.encounter.current_dungeon_difficulty = None;
 
-            info!("Scene changed to ID: {}", scene_id);
+            info!(
+                "Scene changed to: {}",
+                labeled(scene_name(scene_id), scene_id)
+            );
 
             // Emit scene change event
             if state.event_manager.should_emit_events() {
@@ -816,11 +1217,14 @@ impl AppStateManager {
         }
 
         let encounter_has_stats = encounter_has_stats(&state.encounter);
+        let previous_target_id = state.battle_state.active_target_id;
 
         if let Some(reason) = process_sync_dungeon_data(
             &mut state.battle_state,
             sync_dungeon_data,
             encounter_has_stats,
+            &data_tables().reset_rules,
+            state.encounter.current_scene_id,
         ) {
             info!(
                 target: "app::live",
@@ -828,6 +1232,8 @@ impl AppStateManager {
                 reason
             );
             self.apply_reset_reason(state, reason);
+        } else {
+            record_objective_phase(state, previous_target_id);
         }
     }
 
@@ -839,11 +1245,14 @@ impl AppStateManager {
         use crate::live::opcodes_process::process_sync_dungeon_dirty_data;
 
         let encounter_has_stats = encounter_has_stats(&state.encounter);
+        let previous_target_id = state.battle_state.active_target_id;
 
         if let Some(reason) = process_sync_dungeon_dirty_data(
             &mut state.battle_state,
             sync_dungeon_dirty_data,
             encounter_has_stats,
+            &data_tables().reset_rules,
+            state.encounter.current_scene_id,
         ) {
             info!(
                 target: "app::live",
@@ -851,6 +1260,8 @@ impl AppStateManager {
                 reason
             );
             self.apply_reset_reason(state, reason);
+        } else {
+            record_objective_phase(state, previous_target_id);
         }
     }
 
@@ -858,6 +1269,7 @@ impl AppStateManager {
         &self,
         state: &mut AppState,
         sync_to_me_delta_info: blueprotobuf::SyncToMeDeltaInfo,
+        tables: &DataTables,
     ) -> bool {
         use crate::live::opcodes_process::{
             aoi_delta_has_player_damage, process_sync_to_me_delta_info,
@@ -896,6 +1308,7 @@ impl AppStateManager {
             sync_to_me_delta_info,
             &state.local_monitor.monitored_panel_attr_ids,
             combat_target_filter,
+            tables,
         );
 
         if state.local_monitor.uid != state.encounter.local_player_uid {
@@ -922,7 +1335,7 @@ impl AppStateManager {
                 .attr_store
                 .fight_resource_ids(state.encounter.local_player_uid);
             if !ids.is_empty() {
-                let now = crate::database::now_ms();
+                let now = now_ms();
                 let new_state = FightResourceState {
                     entries: ids
                         .iter()
@@ -942,6 +1355,9 @@ impl AppStateManager {
         }
 
         if !result.local_damage_events.is_empty() {
+            state
+                .local_monitor
+                .record_rotation_hits(&result.local_damage_events, &state.encounter);
             counter_dirty |= state.local_monitor.counter_tracker.on_damage_events(
                 &result.local_damage_events,
                 state.encounter.local_player_uid,
@@ -957,6 +1373,9 @@ impl AppStateManager {
         }
 
         if let Some(skill_base_id) = result.attr_skill_id {
+            state
+                .local_monitor
+                .record_rotation_cast(skill_base_id, &state.encounter);
             counter_dirty |= state
                 .local_monitor
                 .counter_tracker
@@ -972,6 +1391,7 @@ impl AppStateManager {
         }
 
         if let Some(raw_bytes) = result.buff_effect_bytes {
+            state.local_monitor.advance_buff_uptime(&state.encounter);
             let buff_process_result = state.local_monitor.buff_monitor.process_buff_effect_bytes(
                 &raw_bytes,
                 &mut state.server_clock_offset,
@@ -999,6 +1419,7 @@ impl AppStateManager {
         &self,
         state: &mut AppState,
         sync_near_delta_info: blueprotobuf::SyncNearDeltaInfo,
+        tables: &DataTables,
     ) -> bool {
         use crate::live::opcodes_process::{
             aoi_delta_has_player_damage, apply_panel_attrs, process_aoi_sync_delta,
@@ -1048,35 +1469,28 @@ impl AppStateManager {
                 aoi_sync_delta,
                 combat_target_filter,
                 false,
+                tables,
             ) {
                 aggregated_damage_events.extend(events);
             }
 
             if let (Some(target_uid), Some(raw_bytes)) = (target_uid, buff_bytes) {
-                let should_monitor_monster_buffs = state
-                    .encounter
-                    .entity_uid_to_entity
-                    .get(&target_uid)
-                    .map(|entity| {
-                        entity.is_boss()
-                            || entity
-                                .monster_type_id
-                                .is_some_and(monster_registry::is_extra_buff_monitored_monster)
-                    })
-                    .unwrap_or(false);
-                if should_monitor_monster_buffs {
-                    let local_player_uid = state.encounter.local_player_uid;
-                    let monitor = state.boss_buff_monitors.monitor_for(target_uid);
-                    monitor.process_buff_effect_bytes(
+                if should_monitor_monster_buffs(&state.encounter, target_uid, tables) {
+                    state.boss_buff_monitors.process_buff_effect_bytes(
+                        target_uid,
                         &raw_bytes,
                         &mut state.server_clock_offset,
-                        local_player_uid,
+                        state.encounter.local_player_uid,
+                        UptimeWindow::of(&state.encounter),
                     );
                 }
             }
         }
 
         if !aggregated_damage_events.is_empty() {
+            state
+                .local_monitor
+                .record_rotation_hits(&aggregated_damage_events, &state.encounter);
             counter_dirty |= state.local_monitor.counter_tracker.on_damage_events(
                 &aggregated_damage_events,
                 state.encounter.local_player_uid,
@@ -1092,10 +1506,68 @@ impl AppStateManager {
         counter_dirty
     }
 
+    /// Seeds buff tracking from a full buff list, so buffs that were already up before the
+    /// meter started (or before zoning in) show up without waiting for their next change.
+    fn process_buff_info_sync(
+        &self,
+        state: &mut AppState,
+        buff_info_sync: blueprotobuf::BuffInfoSync,
+        tables: &DataTables,
+    ) -> bool {
+        let Some(uuid) = buff_info_sync.uuid else {
+            return false;
+        };
+        let target_uid = uuid >> 16;
+        let local_player_uid = state.encounter.local_player_uid;
+
+        if local_player_uid == 0 && EEntityType::from(uuid) == EEntityType::EntChar {
+            let pending = &mut state.pending_buff_info_syncs;
+            pending.retain(|sync| sync.uuid != Some(uuid));
+            if pending.len() >= MAX_PENDING_BUFF_INFO_SYNCS {
+                pending.remove(0);
+            }
+            pending.push(buff_info_sync);
+            return false;
+        }
+
+        if target_uid == local_player_uid {
+            state.local_monitor.advance_buff_uptime(&state.encounter);
+            let buff_process_result = state.local_monitor.buff_monitor.apply_buff_info_sync(
+                &buff_info_sync.buff_infos,
+                &mut state.server_clock_offset,
+                local_player_uid,
+            );
+            info!(
+                target: "app::live",
+                "BuffInfoSync seeded local player buffs (count={})",
+                state.local_monitor.buff_monitor.active_buffs.len()
+            );
+            if let Some(payload) = buff_process_result.update_payload {
+                state.event_manager.emit_buff_update(payload);
+            }
+            return state.local_monitor.counter_tracker.on_buff_changes(
+                &buff_process_result.changes,
+                &state.attr_store,
+                local_player_uid,
+            );
+        }
+
+        if should_monitor_monster_buffs(&state.encounter, target_uid, tables) {
+            state.boss_buff_monitors.apply_buff_info_sync(
+                target_uid,
+                &buff_info_sync.buff_infos,
+                &mut state.server_clock_offset,
+                local_player_uid,
+                UptimeWindow::of(&state.encounter),
+            );
+        }
+        false
+    }
+
     fn try_deferred_reset(&self, state: &mut AppState, has_damage: bool, source: &str) {
         if !state
             .pending_auto_reset
-            .is_some_and(|trigger_at| Instant::now() >= trigger_at)
+            .is_some_and(|trigger_at_ms| now_ms() >= trigger_at_ms)
         {
             return;
         }
@@ -1131,20 +1603,29 @@ impl AppStateManager {
             state.encounter.total_dmg,
             state.encounter.total_heal
         );
-        match reason {
-            EncounterResetReason::NewObjective | EncounterResetReason::Wipe => {
-                let trigger_at = Instant::now() + Duration::from_secs(3);
-                state.pending_auto_reset = Some(trigger_at);
-                info!(
-                    target: "app::live",
-                    "Deferred auto-reset armed (3s): {:?}",
-                    reason
-                );
-            }
+        let scene_id = state.encounter.current_scene_id;
+        let tables = data_tables();
+        if !tables.reset_rules.auto_reset_enabled(scene_id) {
+            info!(
+                target: "app::live",
+                "Reset suppressed: auto reset disabled for scene_id={:?} ({:?})",
+                scene_id,
+                reason
+            );
+            return;
         }
+        let delay = tables.reset_rules.reset_delay(scene_id, reason);
+        state.pending_auto_reset = Some(now_ms() + delay.as_millis() as i64);
+        info!(
+            target: "app::live",
+            "Deferred auto-reset armed ({}ms, scene_id={:?}): {:?}",
+            delay.as_millis(),
+            scene_id,
+            reason
+        );
     }
 
-    fn apply_attr_store_changes(&self, state: &mut AppState) {
+    fn apply_attr_store_changes(&self, state: &mut AppState, tables: &DataTables) {
         let changes = state.attr_store.drain_changes();
 
         if !changes.panel_dirty_attrs.is_empty() {
@@ -1169,6 +1650,9 @@ impl AppStateManager {
 
         for death in changes.death_events {
             if let Some(entity) = state.encounter.entity_uid_to_entity.get_mut(&death.uid) {
+                if entity.entity_type == EEntityType::EntChar {
+                    entity.life_timeline.record_death(death.timestamp_ms);
+                }
                 let recent_damages: Vec<_> = entity.recent_taken_events.drain(..).collect();
                 if recent_damages.is_empty() {
                     continue;
@@ -1181,6 +1665,17 @@ impl AppStateManager {
                 state.death_snapshot_dirty = true;
             }
         }
+
+        for revive in changes.revive_events {
+            record_player_revive(&mut state.encounter, revive.uid, revive.timestamp_ms);
+        }
+
+        if !changes.hp_changed_uids.is_empty() {
+            observe_boss_hp(state, changes.hp_changed_uids, tables);
+        }
+        if !changes.hate_changed_uids.is_empty() {
+            observe_boss_aggro(state, changes.hate_changed_uids, tables);
+        }
     }
 
     fn apply_battle_state_resets_if_needed(&self, state: &mut AppState) {
@@ -1189,10 +1684,10 @@ impl AppStateManager {
             return;
         }
 
-        if let Some(reason) = state
-            .battle_state
-            .check_for_wipe(&mut state.local_monitor.buff_monitor.active_buffs)
-        {
+        if let Some(reason) = state.battle_state.check_for_wipe(
+            &mut state.local_monitor.buff_monitor.active_buffs,
+            &data_tables().reset_rules,
+        ) {
             self.apply_reset_reason(state, reason);
         }
     }
@@ -1306,13 +1801,31 @@ impl AppStateManager {
         self.send_control(LiveControlCommand::ApplyMonitorRuntimeSnapshot(snapshot))
     }
 
-    pub fn start_training_dummy(&self, monster_id: TrainingDummyMonsterId) -> Result<(), String> {
-        self.send_control(LiveControlCommand::StartTrainingDummy { monster_id })
+    pub fn start_training_dummy(
+        &self,
+        monster_id: TrainingDummyMonsterId,
+        options: TrainingDummyOptions,
+    ) -> Result<(), String> {
+        self.send_control(LiveControlCommand::StartTrainingDummy {
+            monster_id,
+            options,
+        })
     }
 
     pub fn stop_training_dummy(&self) -> Result<(), String> {
         self.send_control(LiveControlCommand::StopTrainingDummy)
     }
+
+    /// Creates the session file up front so open errors reach the caller, then hands the
+    /// writer to the live loop.
+    pub fn start_session_recording(&self, path: PathBuf) -> Result<(), String> {
+        let writer = SessionFileWriter::create(&path, now_ms().max(0) as u64)?;
+        self.send_control(LiveControlCommand::StartSessionRecording(Box::new(writer)))
+    }
+
+    pub fn stop_session_recording(&self) -> Result<(), String> {
+        self.send_control(LiveControlCommand::StopSessionRecording)
+    }
 }
 
 impl AppStateManager {
@@ -1325,6 +1838,7 @@ impl AppStateManager {
         let payload = crate::live::event_manager::generate_live_data_payload(
             &state.encounter,
             &state.attr_store,
+            &state.rolling_windows_secs,
         );
 
         state.event_manager.emit_live_data(payload);
@@ -1340,16 +1854,25 @@ impl AppStateManager {
             state.event_manager.emit_death_replay(records);
             state.death_snapshot_dirty = false;
         }
+        if state.encounter.time_fight_start_ms > 0 {
+            let entries = state.local_monitor.buff_uptime_entries(&state.encounter);
+            if !entries.is_empty() {
+                state
+                    .event_manager
+                    .emit_buff_uptime_update(state.encounter.local_player_uid, entries);
+            }
+        }
         let mut boss_buff_snapshot = state
             .boss_buff_monitors
             .build_all_buff_snapshots(state.server_clock_offset);
         boss_buff_snapshot.retain(|&uid, _| !state.attr_store.is_dead(uid));
 
+        let tables = data_tables();
         let boss_count = state
             .encounter
             .entity_uid_to_entity
             .values()
-            .filter(|entity| entity.is_boss())
+            .filter(|entity| entity.is_boss(&tables))
             .count();
         let mut all_hate_lists = HashMap::with_capacity(boss_count);
         let mut player_names =
@@ -1358,7 +1881,7 @@ impl AppStateManager {
             HashMap::with_capacity(boss_count.saturating_add(boss_buff_snapshot.len()));
 
         for (&boss_uid, entity) in &state.encounter.entity_uid_to_entity {
-            if !entity.is_boss() {
+            if !entity.is_boss(&tables) {
                 continue;
             }
             if state.attr_store.is_dead(boss_uid) {
@@ -1435,7 +1958,9 @@ impl AppStateManager {
         }
 
         let previous = build_training_dummy_state(&state.training_dummy);
-        state.training_dummy.maybe_enter_pending_rollover();
+        state
+            .training_dummy
+            .maybe_enter_pending_rollover(state.encounter.locked_target_damage_time_ms);
         emit_training_dummy_update_if_changed(state, previous);
         let matched = inspect_aoi_delta(&state.encounter, delta, local_player_uid);
 
@@ -1446,10 +1971,10 @@ impl AppStateManager {
                 if encounter_has_stats(&state.encounter) {
                     info!(
                         target: "app::live",
-                        "training_dummy_reset_before_lock source={} target_uid={} monster_id={}",
+                        "training_dummy_reset_before_lock source={} target_uid={} monster={}",
                         source,
                         matched.target_uid,
-                        matched.monster_id.id()
+                        labeled(monster_name(matched.monster_id.id()), matched.monster_id.id())
                     );
                     self.reset_encounter(state, false);
                 }
@@ -1458,10 +1983,10 @@ impl AppStateManager {
                 emit_training_dummy_update_if_changed(state, previous);
                 info!(
                     target: "app::live",
-                    "training_dummy_locked source={} target_uid={} monster_id={}",
+                    "training_dummy_locked source={} target_uid={} monster={}",
                     source,
                     matched.target_uid,
-                    matched.monster_id.id()
+                    labeled(monster_name(matched.monster_id.id()), matched.monster_id.id())
                 );
             }
         }
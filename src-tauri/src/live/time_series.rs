@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+/// Width of one time series bucket in milliseconds.
+pub const TIME_SERIES_BUCKET_MS: u128 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesKind {
+    Damage,
+    Healing,
+    Taken,
+}
+
+/// Damage, healing and damage taken of one entity, bucketed into fixed time slices.
+///
+/// Buckets are aligned to unix time, so the series of different entities line up.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CombatTimeSeries {
+    /// Index of the first bucket, counted from the unix epoch.
+    pub first_bucket: u64,
+    pub damage: Vec<u64>,
+    pub healing: Vec<u64>,
+    pub taken: Vec<u64>,
+}
+
+pub fn bucket_of(timestamp_ms: u128) -> u64 {
+    u64::try_from(timestamp_ms / TIME_SERIES_BUCKET_MS).unwrap_or(u64::MAX)
+}
+
+impl CombatTimeSeries {
+    pub fn is_empty(&self) -> bool {
+        self.damage.is_empty()
+    }
+
+    pub fn record(&mut self, kind: SeriesKind, timestamp_ms: u128, value: u128) {
+        let index = self.ensure_bucket(bucket_of(timestamp_ms));
+        let series = match kind {
+            SeriesKind::Damage => &mut self.damage,
+            SeriesKind::Healing => &mut self.healing,
+            SeriesKind::Taken => &mut self.taken,
+        };
+        let value = u64::try_from(value).unwrap_or(u64::MAX);
+        series[index] = series[index].saturating_add(value);
+    }
+
+    /// Grows all three series to cover `bucket` and returns its index.
+    fn ensure_bucket(&mut self, bucket: u64) -> usize {
+        if self.is_empty() {
+            self.first_bucket = bucket;
+        } else if bucket < self.first_bucket {
+            // Events from different packets can arrive slightly out of order.
+            let missing = (self.first_bucket - bucket) as usize;
+            for series in [&mut self.damage, &mut self.healing, &mut self.taken] {
+                series.splice(0..0, std::iter::repeat_n(0, missing));
+            }
+            self.first_bucket = bucket;
+        }
+        let index = (bucket - self.first_bucket) as usize;
+        if index >= self.damage.len() {
+            for series in [&mut self.damage, &mut self.healing, &mut self.taken] {
+                series.resize(index + 1, 0);
+            }
+        }
+        index
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns `count` buckets of one series starting at `first_bucket`, zero filled where
+    /// nothing was recorded.
+    pub fn window(&self, kind: SeriesKind, first_bucket: u64, count: usize) -> Vec<u64> {
+        let series = match kind {
+            SeriesKind::Damage => &self.damage,
+            SeriesKind::Healing => &self.healing,
+            SeriesKind::Taken => &self.taken,
+        };
+        (0..count as u64)
+            .map(|offset| {
+                (first_bucket + offset)
+                    .checked_sub(self.first_bucket)
+                    .and_then(|index| series.get(index as usize))
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CombatTimeSeries, SeriesKind};
+
+    #[test]
+    fn buckets_values_by_second() {
+        let mut series = CombatTimeSeries::default();
+        series.record(SeriesKind::Damage, 10_200, 100);
+        series.record(SeriesKind::Damage, 10_900, 50);
+        series.record(SeriesKind::Healing, 12_000, 30);
+        // Slightly out of order: grows the series at the front.
+        series.record(SeriesKind::Taken, 9_500, 7);
+
+        assert_eq!(series.first_bucket, 9);
+        assert_eq!(series.damage, vec![0, 150, 0, 0]);
+        assert_eq!(series.healing, vec![0, 0, 0, 30]);
+        assert_eq!(series.taken, vec![7, 0, 0, 0]);
+
+        assert_eq!(series.window(SeriesKind::Damage, 8, 4), vec![0, 0, 150, 0]);
+        assert_eq!(series.window(SeriesKind::Healing, 11, 3), vec![0, 30, 0]);
+    }
+}
@@ -1,8 +1,13 @@
+use crate::live::clock::now_ms;
+use crate::live::data_tables::data_tables;
+use crate::live::monster_registry;
 use crate::live::opcodes_models::{Encounter, attr_type};
 use blueprotobuf_lib::blueprotobuf::{AoiSyncDelta, EDamageType};
-use std::time::{Duration, Instant};
 
-pub const TRAINING_SEGMENT_DURATION: Duration = Duration::from_secs(183);
+/// Segment length used unless configured otherwise.
+pub const DEFAULT_TRAINING_SEGMENT_SECS: u32 = 183;
+pub const MIN_TRAINING_SEGMENT_SECS: u32 = 10;
+pub const MAX_TRAINING_SEGMENT_SECS: u32 = 3_600;
 
 #[derive(
     Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type,
@@ -16,20 +21,17 @@ pub enum TrainingDummyPhase {
     PendingRollover,
 }
 
+/// Monster type ID of a training target, known to the monster registry.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(i32)]
-pub enum TrainingDummyMonsterId {
-    EliteEnemy = 115,
-    EliteGuardian = 122,
-}
+pub struct TrainingDummyMonsterId(i32);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
-#[error("unsupported training dummy monster id: {0}")]
+#[error("unknown training dummy monster id: {0}")]
 pub struct InvalidTrainingDummyMonsterId(pub i32);
 
 impl TrainingDummyMonsterId {
     pub fn id(self) -> i32 {
-        self as i32
+        self.0
     }
 }
 
@@ -37,11 +39,132 @@ impl TryFrom<i32> for TrainingDummyMonsterId {
     type Error = InvalidTrainingDummyMonsterId;
 
     fn try_from(value: i32) -> Result<Self, Self::Error> {
-        match value {
-            115 => Ok(Self::EliteEnemy),
-            122 => Ok(Self::EliteGuardian),
-            _ => Err(InvalidTrainingDummyMonsterId(value)),
+        if value > 0 && monster_registry::monster_type(&data_tables(), value).is_some() {
+            Ok(Self(value))
+        } else {
+            Err(InvalidTrainingDummyMonsterId(value))
+        }
+    }
+}
+
+/// How a training dummy run is split into segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrainingDummyOptions {
+    /// Wall-clock length of a segment, counted from the first hit.
+    pub segment_secs: u32,
+    /// Ends the segment early once this much active damage time was dealt.
+    pub stop_after_damage_secs: Option<u32>,
+}
+
+impl Default for TrainingDummyOptions {
+    fn default() -> Self {
+        Self {
+            segment_secs: DEFAULT_TRAINING_SEGMENT_SECS,
+            stop_after_damage_secs: None,
+        }
+    }
+}
+
+impl TrainingDummyOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_TRAINING_SEGMENT_SECS..=MAX_TRAINING_SEGMENT_SECS).contains(&self.segment_secs) {
+            return Err(format!(
+                "segment length must be between {} and {} seconds",
+                MIN_TRAINING_SEGMENT_SECS, MAX_TRAINING_SEGMENT_SECS
+            ));
+        }
+        if self
+            .stop_after_damage_secs
+            .is_some_and(|secs| secs == 0 || secs > self.segment_secs)
+        {
+            return Err("damage time limit must be between 1 second and the segment length".into());
         }
+        Ok(())
+    }
+}
+
+/// A saved training dummy segment.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrainingDummyRun {
+    pub id: i32,
+    /// `None` once the encounter was deleted.
+    pub encounter_id: Option<i32>,
+    pub monster_id: i32,
+    pub segment_secs: u32,
+    pub stop_after_damage_secs: Option<u32>,
+    pub started_at_ms: i64,
+    pub duration_ms: i64,
+    pub active_ms: i64,
+    pub total_damage: i64,
+    /// Damage per second over the segment's duration.
+    pub dps: f64,
+    pub player_uid: i64,
+    pub class_id: i32,
+    pub class_spec: i32,
+    pub ability_score: i32,
+}
+
+/// Narrows the runs used for training dummy statistics; unset fields match everything.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrainingDummyRunFilter {
+    pub monster_id: Option<i32>,
+    pub class_spec: Option<i32>,
+    pub segment_secs: Option<u32>,
+    /// Earliest run start, inclusive.
+    pub from_ms: Option<i64>,
+    /// Latest run start, inclusive.
+    pub to_ms: Option<i64>,
+}
+
+/// DPS statistics across training dummy runs.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrainingDummyRunStats {
+    pub count: u32,
+    pub mean_dps: f64,
+    /// Sample standard deviation; 0 with fewer than two runs.
+    pub std_dev_dps: f64,
+    pub best: Option<TrainingDummyRun>,
+    /// DPS change per run from a least-squares fit over the runs in start order; `None` with
+    /// fewer than two runs.
+    pub trend_dps_per_run: Option<f64>,
+}
+
+/// Summarizes runs ordered by start time.
+pub fn summarize_training_runs(runs: &[TrainingDummyRun]) -> TrainingDummyRunStats {
+    if runs.is_empty() {
+        return TrainingDummyRunStats::default();
+    }
+    let n = runs.len() as f64;
+    let mean_dps = runs.iter().map(|run| run.dps).sum::<f64>() / n;
+    let (std_dev_dps, trend_dps_per_run) = if runs.len() < 2 {
+        (0.0, None)
+    } else {
+        let variance = runs
+            .iter()
+            .map(|run| (run.dps - mean_dps).powi(2))
+            .sum::<f64>()
+            / (n - 1.0);
+        let mean_index = (n - 1.0) / 2.0;
+        let (sxx, sxy) = runs
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(sxx, sxy), (index, run)| {
+                let dx = index as f64 - mean_index;
+                (sxx + dx * dx, sxy + dx * (run.dps - mean_dps))
+            });
+        (variance.sqrt(), Some(sxy / sxx))
+    };
+    let best = runs.iter().max_by(|a, b| a.dps.total_cmp(&b.dps)).cloned();
+    TrainingDummyRunStats {
+        count: u32::try_from(runs.len()).unwrap_or(u32::MAX),
+        mean_dps,
+        std_dev_dps,
+        best,
+        trend_dps_per_run,
     }
 }
 
@@ -56,16 +179,19 @@ pub struct TrainingDummyMatch {
 pub struct TrainingDummyRuntime {
     pub phase: TrainingDummyPhase,
     pub selected_monster_id: Option<TrainingDummyMonsterId>,
+    pub options: TrainingDummyOptions,
     pub locked_target_uid: Option<i64>,
-    pub rollover_ready_at: Option<Instant>,
+    /// Unix time in milliseconds the running segment is due to end.
+    pub rollover_ready_at_ms: Option<i64>,
 }
 
 impl TrainingDummyRuntime {
-    pub fn arm(&mut self, monster_id: TrainingDummyMonsterId) {
+    pub fn arm(&mut self, monster_id: TrainingDummyMonsterId, options: TrainingDummyOptions) {
         self.phase = TrainingDummyPhase::Armed;
         self.selected_monster_id = Some(monster_id);
+        self.options = options;
         self.locked_target_uid = None;
-        self.rollover_ready_at = None;
+        self.rollover_ready_at_ms = None;
     }
 
     pub fn clear(&mut self) {
@@ -78,7 +204,7 @@ impl TrainingDummyRuntime {
 
     pub fn rearm_selected(&mut self) {
         if let Some(monster_id) = self.selected_monster_id {
-            self.arm(monster_id);
+            self.arm(monster_id, self.options);
         } else {
             self.clear();
         }
@@ -93,14 +219,20 @@ impl TrainingDummyRuntime {
         }
     }
 
-    pub fn maybe_enter_pending_rollover(&mut self) {
+    /// Ends the running segment once its time is up or, if configured, once the local
+    /// player's damage time on the locked target reached the damage time limit.
+    pub fn maybe_enter_pending_rollover(&mut self, local_damage_ms: u128) {
         if self.phase != TrainingDummyPhase::Running {
             return;
         }
-        if self
-            .rollover_ready_at
-            .is_some_and(|trigger_at| Instant::now() >= trigger_at)
-        {
+        let time_up = self
+            .rollover_ready_at_ms
+            .is_some_and(|trigger_at_ms| now_ms() >= trigger_at_ms);
+        let damage_time_up = self
+            .options
+            .stop_after_damage_secs
+            .is_some_and(|secs| local_damage_ms >= u128::from(secs) * 1000);
+        if time_up || damage_time_up {
             self.phase = TrainingDummyPhase::PendingRollover;
         }
     }
@@ -118,11 +250,11 @@ impl TrainingDummyRuntime {
     }
 
     pub fn lock_target(&mut self, matched: TrainingDummyMatch) {
-        let now = Instant::now();
+        let now = now_ms();
         self.phase = TrainingDummyPhase::Running;
         self.selected_monster_id = Some(matched.monster_id);
         self.locked_target_uid = Some(matched.target_uid);
-        self.rollover_ready_at = Some(now + TRAINING_SEGMENT_DURATION);
+        self.rollover_ready_at_ms = Some(now + i64::from(self.options.segment_secs) * 1000);
     }
 }
 
@@ -205,3 +337,79 @@ fn is_local_player_damage(
         .map(|uuid| (uuid >> 16) == local_player_uid)
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        TrainingDummyOptions, TrainingDummyPhase, TrainingDummyRun, TrainingDummyRuntime,
+        summarize_training_runs,
+    };
+
+    #[test]
+    fn ends_segment_after_damage_time_limit() {
+        assert!(TrainingDummyOptions::default().validate().is_ok());
+        let options = TrainingDummyOptions {
+            segment_secs: 60,
+            stop_after_damage_secs: Some(30),
+        };
+        assert!(options.validate().is_ok());
+        assert!(
+            TrainingDummyOptions {
+                segment_secs: 5,
+                ..options
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            TrainingDummyOptions {
+                stop_after_damage_secs: Some(90),
+                ..options
+            }
+            .validate()
+            .is_err()
+        );
+
+        let mut runtime = TrainingDummyRuntime {
+            phase: TrainingDummyPhase::Running,
+            options,
+            ..Default::default()
+        };
+        runtime.maybe_enter_pending_rollover(29_999);
+        assert_eq!(runtime.phase, TrainingDummyPhase::Running);
+        runtime.maybe_enter_pending_rollover(30_000);
+        assert_eq!(runtime.phase, TrainingDummyPhase::PendingRollover);
+    }
+
+    #[test]
+    fn summarizes_runs() {
+        let run = |id: i32, dps: f64| TrainingDummyRun {
+            id,
+            encounter_id: Some(id),
+            monster_id: 122,
+            segment_secs: 183,
+            stop_after_damage_secs: None,
+            started_at_ms: i64::from(id) * 1_000,
+            duration_ms: 183_000,
+            active_ms: 180_000,
+            total_damage: (dps * 183.0) as i64,
+            dps,
+            player_uid: 1,
+            class_id: 1,
+            class_spec: 1,
+            ability_score: 20_000,
+        };
+        assert_eq!(summarize_training_runs(&[]).count, 0);
+
+        let single = summarize_training_runs(&[run(1, 100.0)]);
+        assert_eq!((single.mean_dps, single.std_dev_dps), (100.0, 0.0));
+        assert_eq!(single.trend_dps_per_run, None);
+
+        let stats = summarize_training_runs(&[run(1, 100.0), run(2, 140.0), run(3, 120.0)]);
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.mean_dps, 120.0);
+        assert_eq!(stats.std_dev_dps, 20.0);
+        assert_eq!(stats.best.map(|best| best.id), Some(2));
+        assert_eq!(stats.trend_dps_per_run, Some(10.0));
+    }
+}
@@ -1,9 +1,15 @@
 // https://doc.rust-lang.org/reference/items/modules.html#module-source-filenames
 // Preferred way is to name modules with their subfolder name now (no longer mod.rs)
+pub mod capture_stats;
+pub mod capture_status;
 pub mod npcap;
 pub mod opcodes;
 pub mod packet_capture;
 mod packet_process;
+pub mod packet_queue;
 pub mod parser;
+pub mod pcap_file;
 pub mod reassembler;
+pub mod session_file;
+pub mod stream_table;
 pub mod utils;
@@ -0,0 +1,159 @@
+use crate::packets::opcodes::Pkt;
+use crate::packets::utils::GapSkipReason;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+/// Process-wide capture quality counters, cumulative across capture restarts.
+static CAPTURE_STATS: LazyLock<CaptureStats> = LazyLock::new(CaptureStats::default);
+
+/// Capture quality counters.
+pub fn capture_stats() -> &'static CaptureStats {
+    &CAPTURE_STATS
+}
+
+/// Counters describing how lossy the capture is, as opposed to how the meter interprets it.
+///
+/// Updated from the capture thread and the live loop; read through [`CaptureStats::snapshot`].
+#[derive(Debug, Default)]
+pub struct CaptureStats {
+    packets_seen: AtomicU64,
+    bytes_captured: AtomicU64,
+    gaps_buffered_bytes: AtomicU64,
+    gaps_wait_time: AtomicU64,
+    gaps_hard_limit: AtomicU64,
+    sequence_regression_resets: AtomicU64,
+    syn_resets: AtomicU64,
+    fin_resets: AtomicU64,
+    zstd_failures: AtomicU64,
+    service_uuid_mismatches: AtomicU64,
+    decode_failures: Mutex<HashMap<Pkt, u64>>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GapSkipCounts {
+    pub buffered_bytes: u64,
+    pub wait_time: u64,
+    pub hard_limit: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CaptureStatsSnapshot {
+    pub packets_seen: u64,
+    pub bytes_captured: u64,
+    pub gaps_skipped: GapSkipCounts,
+    pub sequence_regression_resets: u64,
+    pub syn_resets: u64,
+    pub fin_resets: u64,
+    pub zstd_failures: u64,
+    pub service_uuid_mismatches: u64,
+    /// Protobuf decode failures per opcode, ordered by method id.
+    pub decode_failures: Vec<(Pkt, u64)>,
+}
+
+impl CaptureStats {
+    /// A raw packet handed over by the capture source, before any filtering.
+    pub fn record_packet(&self, bytes: usize) {
+        self.packets_seen.fetch_add(1, Ordering::Relaxed);
+        self.bytes_captured
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_gap_skipped(&self, reason: GapSkipReason) {
+        let counter = match reason {
+            GapSkipReason::BufferedBytes => &self.gaps_buffered_bytes,
+            GapSkipReason::WaitTime => &self.gaps_wait_time,
+            GapSkipReason::HardLimit => &self.gaps_hard_limit,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_sequence_regression_reset(&self) {
+        self.sequence_regression_resets
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_syn_reset(&self) {
+        self.syn_resets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// FIN or RST closing a tracked stream.
+    pub fn record_fin_reset(&self) {
+        self.fin_resets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_zstd_failure(&self) {
+        self.zstd_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_service_uuid_mismatch(&self) {
+        self.service_uuid_mismatches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_decode_failure(&self, op: Pkt) {
+        if let Ok(mut decode_failures) = self.decode_failures.lock() {
+            *decode_failures.entry(op).or_default() += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> CaptureStatsSnapshot {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let mut decode_failures: Vec<(Pkt, u64)> = self
+            .decode_failures
+            .lock()
+            .map(|failures| failures.iter().map(|(op, n)| (*op, *n)).collect())
+            .unwrap_or_default();
+        decode_failures.sort_by_key(|(op, _)| op.method_id());
+        CaptureStatsSnapshot {
+            packets_seen: load(&self.packets_seen),
+            bytes_captured: load(&self.bytes_captured),
+            gaps_skipped: GapSkipCounts {
+                buffered_bytes: load(&self.gaps_buffered_bytes),
+                wait_time: load(&self.gaps_wait_time),
+                hard_limit: load(&self.gaps_hard_limit),
+            },
+            sequence_regression_resets: load(&self.sequence_regression_resets),
+            syn_resets: load(&self.syn_resets),
+            fin_resets: load(&self.fin_resets),
+            zstd_failures: load(&self.zstd_failures),
+            service_uuid_mismatches: load(&self.service_uuid_mismatches),
+            decode_failures,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaptureStats, GapSkipCounts};
+    use crate::packets::opcodes::Pkt;
+    use crate::packets::utils::GapSkipReason;
+
+    #[test]
+    fn snapshot_reflects_recorded_events() {
+        let stats = CaptureStats::default();
+        stats.record_packet(100);
+        stats.record_packet(60);
+        stats.record_gap_skipped(GapSkipReason::WaitTime);
+        stats.record_gap_skipped(GapSkipReason::WaitTime);
+        stats.record_gap_skipped(GapSkipReason::HardLimit);
+        stats.record_decode_failure(Pkt::SyncNearDeltaInfo);
+        stats.record_decode_failure(Pkt::EnterScene);
+        stats.record_decode_failure(Pkt::SyncNearDeltaInfo);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.packets_seen, 2);
+        assert_eq!(snapshot.bytes_captured, 160);
+        assert_eq!(
+            snapshot.gaps_skipped,
+            GapSkipCounts {
+                buffered_bytes: 0,
+                wait_time: 2,
+                hard_limit: 1,
+            }
+        );
+        assert_eq!(
+            snapshot.decode_failures,
+            vec![(Pkt::EnterScene, 1), (Pkt::SyncNearDeltaInfo, 2)]
+        );
+    }
+}
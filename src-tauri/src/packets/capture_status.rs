@@ -0,0 +1,203 @@
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+/// Status of the capture thread, shared with the live loop and commands.
+static CAPTURE_STATUS: LazyLock<CaptureStatusTracker> =
+    LazyLock::new(CaptureStatusTracker::default);
+
+/// Current capture status.
+pub fn capture_status() -> &'static CaptureStatusTracker {
+    &CAPTURE_STATUS
+}
+
+/// Delay before the first restart attempt after the capture source fails.
+const RESTART_BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+/// Upper bound for the restart delay.
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// A source that ran at least this long counts as recovered, so the next failure starts the
+/// backoff from scratch.
+pub const CAPTURE_STABLE_AFTER: Duration = Duration::from_secs(30);
+/// Consecutive failed restarts before the capture is reported as failed. Restarts continue
+/// at the maximum delay after that.
+pub const FAILED_AFTER_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum CaptureState {
+    /// Opening the capture source.
+    #[default]
+    Starting,
+    /// The source is open and delivering packets.
+    Running,
+    /// The source failed and a restart is scheduled.
+    Degraded,
+    /// Restarts keep failing, or the capture method can't work at all.
+    Failed,
+    /// A capture or session file was replayed to the end.
+    Finished,
+}
+
+/// Capture thread status as shown in the UI.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureStatus {
+    pub state: CaptureState,
+    /// Why the capture is degraded or failed.
+    pub reason: Option<String>,
+    /// Capture device in use, for Npcap/libpcap captures.
+    pub device: Option<String>,
+    /// Restarts since the app started, requested or automatic.
+    pub restarts: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct CaptureStatusTracker {
+    inner: Mutex<(CaptureStatus, u64)>,
+}
+
+impl CaptureStatusTracker {
+    fn update(&self, apply: impl FnOnce(&mut CaptureStatus)) {
+        if let Ok(mut inner) = self.inner.lock() {
+            let (status, generation) = &mut *inner;
+            let before = status.clone();
+            apply(status);
+            if *status != before {
+                *generation += 1;
+            }
+        }
+    }
+
+    pub fn set_starting(&self) {
+        self.update(|status| {
+            status.state = CaptureState::Starting;
+            status.reason = None;
+        });
+    }
+
+    pub fn set_running(&self, device: Option<String>) {
+        self.update(|status| {
+            status.state = CaptureState::Running;
+            status.reason = None;
+            status.device = device;
+        });
+    }
+
+    pub fn set_degraded(&self, reason: String) {
+        self.update(|status| {
+            status.state = CaptureState::Degraded;
+            status.reason = Some(reason);
+        });
+    }
+
+    pub fn set_failed(&self, reason: String) {
+        self.update(|status| {
+            status.state = CaptureState::Failed;
+            status.reason = Some(reason);
+        });
+    }
+
+    pub fn set_finished(&self) {
+        self.update(|status| {
+            status.state = CaptureState::Finished;
+            status.reason = None;
+        });
+    }
+
+    pub fn record_restart(&self) {
+        self.update(|status| status.restarts += 1);
+    }
+
+    pub fn snapshot(&self) -> CaptureStatus {
+        self.inner
+            .lock()
+            .map(|inner| inner.0.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the status if it changed since `generation`, updating `generation`.
+    pub fn changed_since(&self, generation: &mut u64) -> Option<CaptureStatus> {
+        let inner = self.inner.lock().ok()?;
+        if inner.1 == *generation {
+            return None;
+        }
+        *generation = inner.1;
+        Some(inner.0.clone())
+    }
+}
+
+/// Exponential restart delay for the capture supervisor.
+#[derive(Debug)]
+pub struct RestartBackoff {
+    next_delay: Duration,
+    attempts: u32,
+}
+
+impl RestartBackoff {
+    pub fn new() -> Self {
+        Self {
+            next_delay: RESTART_BACKOFF_INITIAL,
+            attempts: 0,
+        }
+    }
+
+    /// Delay before the next attempt; doubles on every call up to the maximum.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next_delay;
+        self.next_delay = (self.next_delay * 2).min(RESTART_BACKOFF_MAX);
+        self.attempts += 1;
+        delay
+    }
+
+    /// Consecutive failed attempts since the last reset.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CaptureState, CaptureStatusTracker, RESTART_BACKOFF_INITIAL, RESTART_BACKOFF_MAX,
+        RestartBackoff,
+    };
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum_and_resets() {
+        let mut backoff = RestartBackoff::new();
+        assert_eq!(backoff.next_delay(), RESTART_BACKOFF_INITIAL);
+        assert_eq!(backoff.next_delay(), RESTART_BACKOFF_INITIAL * 2);
+        for _ in 0..10 {
+            backoff.next_delay();
+        }
+        assert_eq!(backoff.next_delay(), RESTART_BACKOFF_MAX);
+        assert_eq!(backoff.attempts(), 13);
+
+        backoff.reset();
+        assert_eq!(backoff.attempts(), 0);
+        assert_eq!(backoff.next_delay(), RESTART_BACKOFF_INITIAL);
+    }
+
+    #[test]
+    fn reports_only_actual_status_changes() {
+        let tracker = CaptureStatusTracker::default();
+        let mut generation = 0;
+        assert!(tracker.changed_since(&mut generation).is_none());
+
+        tracker.set_running(Some("eth0".to_string()));
+        let status = tracker.changed_since(&mut generation).unwrap();
+        assert_eq!(status.state, CaptureState::Running);
+        assert_eq!(status.device.as_deref(), Some("eth0"));
+
+        tracker.set_running(Some("eth0".to_string()));
+        assert!(tracker.changed_since(&mut generation).is_none());
+
+        tracker.set_degraded("adapter went away".to_string());
+        let status = tracker.changed_since(&mut generation).unwrap();
+        assert_eq!(status.state, CaptureState::Degraded);
+        assert_eq!(status.reason.as_deref(), Some("adapter went away"));
+    }
+}
@@ -1,14 +1,14 @@
 use libloading::{Library, Symbol};
-use log::info;
-use std::ffi::{CStr, CString};
+use log::{info, warn};
+use std::ffi::{CStr, CString, c_char, c_void};
 use std::ptr;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
 // Type definitions for pcap functions
-type PcapFindAllDevs = unsafe extern "C" fn(*mut *mut PcapIf, *mut i8) -> i32;
+type PcapFindAllDevs = unsafe extern "C" fn(*mut *mut PcapIf, *mut c_char) -> i32;
 type PcapFreeAllDevs = unsafe extern "C" fn(*mut PcapIf);
-type PcapCreate = unsafe extern "C" fn(*const i8, *mut i8) -> *mut PcapT;
+type PcapCreate = unsafe extern "C" fn(*const c_char, *mut c_char) -> *mut PcapT;
 type PcapSetSnaplen = unsafe extern "C" fn(*mut PcapT, i32) -> i32;
 type PcapSetPromisc = unsafe extern "C" fn(*mut PcapT, i32) -> i32;
 type PcapSetTimeout = unsafe extern "C" fn(*mut PcapT, i32) -> i32;
@@ -16,19 +16,36 @@ type PcapSetBufferSize = unsafe extern "C" fn(*mut PcapT, i32) -> i32;
 type PcapActivate = unsafe extern "C" fn(*mut PcapT) -> i32;
 type PcapClose = unsafe extern "C" fn(*mut PcapT);
 type PcapNextEx = unsafe extern "C" fn(*mut PcapT, *mut *mut PcapPkthdr, *mut *const u8) -> i32;
-type PcapGetErr = unsafe extern "C" fn(*mut PcapT) -> *mut i8;
+type PcapGetErr = unsafe extern "C" fn(*mut PcapT) -> *mut c_char;
 type PcapDataLink = unsafe extern "C" fn(*mut PcapT) -> i32;
+type PcapCompile =
+    unsafe extern "C" fn(*mut PcapT, *mut BpfProgram, *const c_char, i32, u32) -> i32;
+type PcapSetFilter = unsafe extern "C" fn(*mut PcapT, *mut BpfProgram) -> i32;
+type PcapFreeCode = unsafe extern "C" fn(*mut BpfProgram);
 
 const NPCAP_SNAPLEN: i32 = 65_536;
 const NPCAP_PROMISC: i32 = 1;
 const NPCAP_TIMEOUT_MS: i32 = 1_000;
 const NPCAP_BUFFER_SIZE: i32 = 64 * 1024 * 1024;
+/// Kernel-side BPF filter; everything else the capture loop would drop anyway.
+const CAPTURE_BPF_FILTER: &str = "tcp";
+const PCAP_NETMASK_UNKNOWN: u32 = 0xffff_ffff;
+/// libpcap pseudo-device that captures on every interface (Linux only).
+pub const LIBPCAP_ANY_DEVICE: &str = "any";
+
+/// Shared library names tried in order. Npcap ships `wpcap.dll`; Linux and macOS use libpcap.
+#[cfg(windows)]
+const PCAP_LIBRARY_CANDIDATES: &[&str] = &["wpcap.dll"];
+#[cfg(target_os = "macos")]
+const PCAP_LIBRARY_CANDIDATES: &[&str] = &["libpcap.A.dylib", "libpcap.dylib"];
+#[cfg(not(any(windows, target_os = "macos")))]
+const PCAP_LIBRARY_CANDIDATES: &[&str] = &["libpcap.so.1", "libpcap.so", "libpcap.so.0.8"];
 
 #[repr(C)]
 pub struct PcapIf {
     pub next: *mut PcapIf,
-    pub name: *mut i8,
-    pub description: *mut i8,
+    pub name: *mut c_char,
+    pub description: *mut c_char,
     pub addresses: *mut PcapAddr,
     pub flags: u32,
 }
@@ -49,6 +66,12 @@ pub struct PcapPkthdr {
     pub len: u32,
 }
 
+#[repr(C)]
+pub struct BpfProgram {
+    pub bf_len: u32,
+    pub bf_insns: *mut c_void,
+}
+
 pub enum PcapT {}
 
 pub struct NpcapContext {
@@ -57,11 +80,17 @@ pub struct NpcapContext {
 
 impl NpcapContext {
     pub fn new() -> Result<Self, String> {
-        unsafe {
-            let lib = Library::new("wpcap.dll")
-                .map_err(|e| format!("Failed to load wpcap.dll: {}", e))?;
-            Ok(Self { lib: Arc::new(lib) })
+        let mut errors = Vec::with_capacity(PCAP_LIBRARY_CANDIDATES.len());
+        for &name in PCAP_LIBRARY_CANDIDATES {
+            match unsafe { Library::new(name) } {
+                Ok(lib) => return Ok(Self { lib: Arc::new(lib) }),
+                Err(e) => errors.push(format!("{name}: {e}")),
+            }
         }
+        Err(format!(
+            "Failed to load packet capture library ({})",
+            errors.join("; ")
+        ))
     }
 
     pub fn list_devices(&self) -> Result<Vec<Device>, String> {
@@ -77,7 +106,7 @@ impl NpcapContext {
                 .map_err(|e| e.to_string())?;
 
             let mut alldevs: *mut PcapIf = ptr::null_mut();
-            let mut errbuf = [0i8; 256];
+            let mut errbuf = [0 as c_char; 256];
 
             if find_all_devs(&mut alldevs, errbuf.as_mut_ptr()) == -1 {
                 return Err(CStr::from_ptr(errbuf.as_ptr())
@@ -127,6 +156,36 @@ pub fn check_npcap_status() -> bool {
     NpcapContext::new().is_ok()
 }
 
+/// Description of `device` as listed by pcap, used to recognise the adapter again if it comes
+/// back under a different name.
+pub fn device_description(device: &str) -> Option<String> {
+    let devices = NpcapContext::new().and_then(|c| c.list_devices()).ok()?;
+    devices
+        .into_iter()
+        .find(|d| d.name == device)
+        .and_then(|d| d.description)
+}
+
+/// Picks the device to capture on after `device` stopped working.
+///
+/// Keeps `device` while it is still listed, then tries an adapter with the same description,
+/// then the libpcap `any` device. Returns `None` if none of them is listed right now.
+pub fn redetect_device(device: &str, description: Option<&str>) -> Option<String> {
+    let devices = NpcapContext::new().and_then(|c| c.list_devices()).ok()?;
+    let by_description = || {
+        let description = description?;
+        devices
+            .iter()
+            .find(|d| d.description.as_deref() == Some(description))
+    };
+    devices
+        .iter()
+        .find(|d| d.name == device)
+        .or_else(by_description)
+        .or_else(|| devices.iter().find(|d| d.name == LIBPCAP_ANY_DEVICE))
+        .map(|d| d.name.clone())
+}
+
 pub struct NpcapCapture {
     handle: *mut PcapT,
     lib: Arc<Library>,
@@ -171,7 +230,7 @@ impl NpcapCapture {
                 .map_err(|e| e.to_string())?;
 
             let device_c = CString::new(device_name).map_err(|e| e.to_string())?;
-            let mut errbuf = [0i8; 256];
+            let mut errbuf = [0 as c_char; 256];
 
             let handle = create(device_c.as_ptr(), errbuf.as_mut_ptr());
 
@@ -232,6 +291,14 @@ impl NpcapCapture {
                 return Err(err);
             }
 
+            // A missing filter only costs CPU, so keep capturing if it can't be applied.
+            if let Err(err) = apply_bpf_filter(&context.lib, handle, CAPTURE_BPF_FILTER) {
+                warn!(
+                    "BPF filter {:?} not applied for device {}: {}",
+                    CAPTURE_BPF_FILTER, device_name, err
+                );
+            }
+
             info!(
                 "Npcap handle configured device={} buffer_size={} bytes snaplen={} timeout_ms={}",
                 device_name, NPCAP_BUFFER_SIZE, NPCAP_SNAPLEN, NPCAP_TIMEOUT_MS
@@ -291,6 +358,46 @@ impl NpcapCapture {
     }
 }
 
+unsafe fn apply_bpf_filter(lib: &Library, handle: *mut PcapT, filter: &str) -> Result<(), String> {
+    unsafe {
+        let compile: Symbol<PcapCompile> = lib.get(b"pcap_compile").map_err(|e| e.to_string())?;
+        let set_filter: Symbol<PcapSetFilter> =
+            lib.get(b"pcap_setfilter").map_err(|e| e.to_string())?;
+        let free_code: Symbol<PcapFreeCode> =
+            lib.get(b"pcap_freecode").map_err(|e| e.to_string())?;
+        let get_err: Symbol<PcapGetErr> = lib.get(b"pcap_geterr").map_err(|e| e.to_string())?;
+
+        let filter_c = CString::new(filter).map_err(|e| e.to_string())?;
+        let mut program = BpfProgram {
+            bf_len: 0,
+            bf_insns: ptr::null_mut(),
+        };
+        if compile(
+            handle,
+            &mut program,
+            filter_c.as_ptr(),
+            1,
+            PCAP_NETMASK_UNKNOWN,
+        ) != 0
+        {
+            return Err(format!(
+                "pcap_compile failed: {}",
+                pcap_error(*get_err, handle)
+            ));
+        }
+        let result = set_filter(handle, &mut program);
+        free_code(&mut program);
+        if result != 0 {
+            return Err(format!(
+                "pcap_setfilter failed: {}",
+                pcap_error(*get_err, handle)
+            ));
+        }
+        info!("BPF filter applied: {}", filter);
+        Ok(())
+    }
+}
+
 unsafe fn pcap_error(get_err: PcapGetErr, handle: *mut PcapT) -> String {
     let err = unsafe { get_err(handle) };
     if err.is_null() {
@@ -2,7 +2,7 @@
 pub struct ParseError;
 
 #[non_exhaustive]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Pkt {
     ServerChangeInfo,
     // TODO: change all these names
@@ -56,6 +56,21 @@ pub enum Pkt {
     NotifyTimerUpdate = 0x00000049,
 }
 
+impl Pkt {
+    /// Raw notify method id. `ServerChangeInfo` is synthesized by the capture layer and maps to 0.
+    pub fn method_id(self) -> u32 {
+        self as u32
+    }
+
+    /// Inverse of [`Pkt::method_id`], including the synthetic `ServerChangeInfo`.
+    pub fn from_method_id(method_id: u32) -> Option<Self> {
+        if method_id == 0 {
+            return Some(Pkt::ServerChangeInfo);
+        }
+        Pkt::try_from(method_id).ok()
+    }
+}
+
 impl TryFrom<u32> for Pkt {
     type Error = ParseError;
 
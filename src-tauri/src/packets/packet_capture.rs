@@ -1,25 +1,43 @@
-use crate::packets;
-use crate::packets::npcap::NpcapCapture;
+use crate::packets::capture_stats::capture_stats;
+use crate::packets::capture_status::{
+    CAPTURE_STABLE_AFTER, FAILED_AFTER_ATTEMPTS, RestartBackoff, capture_status,
+};
+use crate::packets::npcap::{self, NpcapCapture};
 use crate::packets::opcodes::Pkt;
 use crate::packets::packet_process::process_packet;
-use crate::packets::reassembler::Reassembler;
-use crate::packets::utils::{Server, TCPReassembler, TcpInsertResult, tcp_sequence_before};
+use crate::packets::packet_queue::{
+    OverflowPolicy, PacketItem, PacketQueueConfig, PacketSender, packet_channel,
+};
+use crate::packets::pcap_file::PcapFileReader;
+use crate::packets::session_file::SessionReader;
+use crate::packets::stream_table::StreamTable;
+use crate::packets::utils::{Server, TcpInsertResult, tcp_sequence_before};
 use bytes::Bytes;
-use etherparse::NetSlice::Ipv4;
+use etherparse::NetSlice::{Ipv4, Ipv6};
 use etherparse::SlicedPacket;
 use etherparse::TransportSlice::Tcp;
 use log::{debug, error, info, warn};
 use once_cell::sync::OnceCell;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, OnceLock};
+use std::fs::File;
+use std::io::BufReader;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::watch;
+#[cfg(windows)]
 use windivert::WinDivert;
+#[cfg(windows)]
 use windivert::prelude::NetworkLayer;
+#[cfg(windows)]
 use windivert::prelude::WinDivertFlags;
 
 // Global sender for restart signal
 static RESTART_SENDER: OnceCell<watch::Sender<bool>> = OnceCell::new();
 
+/// How often a backoff sleep checks for a requested restart.
+const RESTART_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 const MAX_BACKTRACK_BYTES: u32 = 2 * 1024 * 1024; // 2 MiB safety window before considering a reset
 
 // Common libpcap datalink constants we care about.
@@ -27,11 +45,47 @@ const DLT_NULL: i32 = 0;
 const DLT_EN10MB: i32 = 1;
 const DLT_RAW: i32 = 12;
 const DLT_LOOP: i32 = 108;
+// Linux "cooked" captures, used by libpcap for the `any` device.
+const DLT_LINUX_SLL: i32 = 113;
+const DLT_LINUX_SLL2: i32 = 276;
+const SLL_HEADER_LEN: usize = 16;
+const SLL2_HEADER_LEN: usize = 20;
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86DD;
+// Loopback (DLT_NULL/LOOP) address families. AF_INET is 2 everywhere; AF_INET6 differs:
+// 23 on Windows, 24 on NetBSD/OpenBSD, 28 on FreeBSD, 30 on macOS.
+const LOOPBACK_AF_INET: u32 = 2;
+const LOOPBACK_AF_INET6: [u32; 4] = [23, 24, 28, 30];
 
 #[derive(Clone, Debug)]
 pub enum CaptureMethod {
+    /// Windows only.
     WinDivert,
+    /// Npcap on Windows, libpcap on Linux/macOS. Holds the capture device name.
     Npcap(String),
+    /// Replays a pcap/pcapng file through the same pipeline as live traffic.
+    PcapFile(PathBuf, ReplayPacing),
+    /// Replays a recorded session file, skipping capture and reassembly entirely.
+    SessionFile(PathBuf, ReplayPacing),
+}
+
+/// How fast a capture file is fed into the pipeline.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReplayPacing {
+    /// Feed packets as fast as the pipeline accepts them.
+    AsFastAsPossible,
+    /// Sleep between packets so they arrive with the capture's original spacing.
+    #[default]
+    OriginalTiming,
+}
+
+impl ReplayPacing {
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "fast" | "asFastAsPossible" => Self::AsFastAsPossible,
+            _ => Self::OriginalTiming,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -44,21 +98,29 @@ enum PacketFormat {
 trait PacketSource: Send {
     fn next_packet(&mut self) -> Result<Option<Vec<u8>>, String>;
     fn packet_format(&self) -> PacketFormat;
+
+    /// Unix time in milliseconds the last packet returned by `next_packet` was captured.
+    fn captured_at_ms(&self) -> u64 {
+        wall_clock_ms()
+    }
+
+    /// Returns true once the source can never yield another packet (e.g. end of a capture file).
+    fn is_exhausted(&self) -> bool {
+        false
+    }
 }
 
+#[cfg(windows)]
 struct WinDivertSource {
     handle: WinDivert<NetworkLayer>,
     buffer: Vec<u8>,
 }
 
+#[cfg(windows)]
 impl WinDivertSource {
     fn new() -> Result<Self, String> {
-        let handle = WinDivert::network(
-            "!loopback && ip && tcp",
-            0,
-            WinDivertFlags::new().set_sniff(),
-        )
-        .map_err(|e| format!("Failed to initialize WinDivert: {}", e))?;
+        let handle = WinDivert::network("!loopback && tcp", 0, WinDivertFlags::new().set_sniff())
+            .map_err(|e| format!("Failed to initialize WinDivert: {}", e))?;
 
         info!(target: "app::capture", "WinDivert handle opened");
 
@@ -69,6 +131,7 @@ impl WinDivertSource {
     }
 }
 
+#[cfg(windows)]
 impl PacketSource for WinDivertSource {
     fn next_packet(&mut self) -> Result<Option<Vec<u8>>, String> {
         self.handle
@@ -92,54 +155,197 @@ impl NpcapSource {
         info!(target: "app::capture", "Npcap handle opened device={}", device);
         Ok(Self { capture })
     }
+}
+
+impl PacketSource for NpcapSource {
+    fn next_packet(&mut self) -> Result<Option<Vec<u8>>, String> {
+        match self.capture.next_packet()? {
+            Some(data) => Ok(normalize_packet(self.capture.datalink(), data)),
+            None => Ok(None),
+        }
+    }
+
+    fn packet_format(&self) -> PacketFormat {
+        packet_format_for_datalink(self.capture.datalink())
+    }
+}
+
+fn wall_clock_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+        })
+}
+
+/// Sleeps between replayed packets so they keep the spacing they were captured with.
+struct ReplayClock {
+    pacing: ReplayPacing,
+    /// First capture timestamp and the wall-clock instant it was replayed at.
+    origin: Option<(Duration, Instant)>,
+}
+
+impl ReplayClock {
+    fn new(pacing: ReplayPacing) -> Self {
+        Self {
+            pacing,
+            origin: None,
+        }
+    }
+
+    fn wait_for_capture_time(&mut self, timestamp: Duration) {
+        if self.pacing != ReplayPacing::OriginalTiming {
+            return;
+        }
+        let (first_timestamp, started_at) = *self
+            .origin
+            .get_or_insert_with(|| (timestamp, Instant::now()));
+        let due_at = started_at + timestamp.saturating_sub(first_timestamp);
+        let now = Instant::now();
+        if due_at > now {
+            std::thread::sleep(due_at - now);
+        }
+    }
+}
+
+struct PcapFileSource {
+    reader: PcapFileReader<BufReader<File>>,
+    clock: ReplayClock,
+    datalink: i32,
+    /// Capture time of the last record read.
+    timestamp: Duration,
+    packets_read: u64,
+    exhausted: bool,
+}
+
+impl PcapFileSource {
+    fn new(path: &Path, pacing: ReplayPacing) -> Result<Self, String> {
+        let reader = PcapFileReader::open(path)?;
+        info!(
+            target: "app::capture",
+            "pcap replay opened path={} pacing={:?}",
+            path.display(),
+            pacing
+        );
+        Ok(Self {
+            reader,
+            clock: ReplayClock::new(pacing),
+            datalink: DLT_EN10MB,
+            timestamp: Duration::ZERO,
+            packets_read: 0,
+            exhausted: false,
+        })
+    }
+}
 
-    fn packet_format_for_datalink(&self) -> PacketFormat {
-        match self.capture.datalink() {
-            DLT_EN10MB => PacketFormat::Ethernet,
-            DLT_RAW | DLT_NULL | DLT_LOOP => PacketFormat::RawIp,
-            other => {
-                log_unsupported_datalink(other);
-                PacketFormat::Unsupported
+impl PacketSource for PcapFileSource {
+    fn next_packet(&mut self) -> Result<Option<Vec<u8>>, String> {
+        if self.exhausted {
+            return Ok(None);
+        }
+        match self.reader.next_record()? {
+            Some(record) => {
+                self.packets_read += 1;
+                self.datalink = record.datalink;
+                self.timestamp = record.timestamp;
+                self.clock.wait_for_capture_time(record.timestamp);
+                Ok(normalize_packet(record.datalink, record.data))
+            }
+            None => {
+                info!(
+                    target: "app::capture",
+                    "pcap replay reached end of file packets={}",
+                    self.packets_read
+                );
+                self.exhausted = true;
+                Ok(None)
             }
         }
     }
 
-    fn normalize_packet(&self, data: Vec<u8>) -> Option<Vec<u8>> {
-        match self.capture.datalink() {
-            DLT_EN10MB | DLT_RAW => Some(data),
-            DLT_NULL | DLT_LOOP => {
-                if data.len() <= 4 {
-                    return None;
+    fn packet_format(&self) -> PacketFormat {
+        packet_format_for_datalink(self.datalink)
+    }
+
+    fn captured_at_ms(&self) -> u64 {
+        u64::try_from(self.timestamp.as_millis()).unwrap_or(u64::MAX)
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+fn packet_format_for_datalink(datalink: i32) -> PacketFormat {
+    match datalink {
+        DLT_EN10MB => PacketFormat::Ethernet,
+        DLT_RAW | DLT_NULL | DLT_LOOP | DLT_LINUX_SLL | DLT_LINUX_SLL2 => PacketFormat::RawIp,
+        other => {
+            log_unsupported_datalink(other);
+            PacketFormat::Unsupported
+        }
+    }
+}
+
+/// Strips link-layer framing that `SlicedPacket` can't parse (DLT_NULL/LOOP family headers
+/// and Linux cooked-capture headers).
+fn normalize_packet(datalink: i32, data: Vec<u8>) -> Option<Vec<u8>> {
+    match datalink {
+        DLT_EN10MB | DLT_RAW => Some(data),
+        DLT_LINUX_SLL => strip_cooked_header(data, SLL_HEADER_LEN, 14),
+        DLT_LINUX_SLL2 => strip_cooked_header(data, SLL2_HEADER_LEN, 0),
+        DLT_NULL | DLT_LOOP => {
+            if data.len() <= 4 {
+                return None;
+            }
+            let family = loopback_family(datalink, [data[0], data[1], data[2], data[3]]);
+            match family {
+                family if family == LOOPBACK_AF_INET || LOOPBACK_AF_INET6.contains(&family) => {
+                    Some(data[4..].to_vec())
                 }
-                let family = u32::from_ne_bytes([data[0], data[1], data[2], data[3]]);
-                match family {
-                    2 => Some(data[4..].to_vec()), // AF_INET on Windows
-                    23 | 24 => None,               // IPv6 families, ignored for now
-                    other => {
-                        log_unsupported_loopback_family(other, self.capture.datalink());
-                        None
-                    }
+                other => {
+                    log_unsupported_loopback_family(other, datalink);
+                    None
                 }
             }
-            other => {
-                log_unsupported_datalink(other);
-                None
-            }
+        }
+        other => {
+            log_unsupported_datalink(other);
+            None
         }
     }
 }
 
-impl PacketSource for NpcapSource {
-    fn next_packet(&mut self) -> Result<Option<Vec<u8>>, String> {
-        match self.capture.next_packet()? {
-            Some(data) => Ok(self.normalize_packet(data)),
-            None => Ok(None),
-        }
+/// Reads the 4-byte loopback family header.
+///
+/// DLT_LOOP is always big-endian. DLT_NULL uses the capturing host's byte order, so a file
+/// written on a host of the other endianness shows up with the value in the upper bytes.
+fn loopback_family(datalink: i32, header: [u8; 4]) -> u32 {
+    if datalink == DLT_LOOP {
+        return u32::from_be_bytes(header);
     }
+    let family = u32::from_ne_bytes(header);
+    if family > 0xffff {
+        family.swap_bytes()
+    } else {
+        family
+    }
+}
 
-    fn packet_format(&self) -> PacketFormat {
-        self.packet_format_for_datalink()
+/// Drops the SLL/SLL2 header, keeping only IPv4 and IPv6 payloads.
+fn strip_cooked_header(
+    data: Vec<u8>,
+    header_len: usize,
+    protocol_offset: usize,
+) -> Option<Vec<u8>> {
+    if data.len() <= header_len {
+        return None;
     }
+    let protocol = u16::from_be_bytes([data[protocol_offset], data[protocol_offset + 1]]);
+    if protocol != ETHERTYPE_IPV4 && protocol != ETHERTYPE_IPV6 {
+        return None;
+    }
+    Some(data[header_len..].to_vec())
 }
 
 fn log_unsupported_loopback_family(family: u32, datalink: i32) {
@@ -155,23 +361,29 @@ fn log_unsupported_loopback_family(family: u32, datalink: i32) {
 fn log_unsupported_datalink(datalink: i32) {
     static LOGGED_DLT: OnceLock<i32> = OnceLock::new();
     if LOGGED_DLT.set(datalink).is_ok() {
-        warn!(
-            "Unsupported Npcap datalink type {}, dropping packets",
-            datalink
-        );
+        warn!("Unsupported datalink type {}, dropping packets", datalink);
     }
 }
 
 pub fn start_capture(
     method: CaptureMethod,
-) -> (
-    tokio::sync::mpsc::UnboundedReceiver<(packets::opcodes::Pkt, Bytes)>,
-    Arc<AtomicUsize>,
-) {
-    let (packet_sender, packet_receiver) =
-        tokio::sync::mpsc::unbounded_channel::<(packets::opcodes::Pkt, Bytes)>();
-    let queue_depth = Arc::new(AtomicUsize::new(0));
-    let capture_queue_depth = Arc::clone(&queue_depth);
+    queue_config: PacketQueueConfig,
+) -> tokio::sync::mpsc::Receiver<PacketItem> {
+    // File replays have no live source to fall behind, so never drop their packets.
+    let queue_config = match method {
+        CaptureMethod::PcapFile(..) | CaptureMethod::SessionFile(..) => PacketQueueConfig {
+            policy: OverflowPolicy::Block,
+            ..queue_config
+        },
+        _ => queue_config,
+    };
+    info!(
+        target: "app::capture",
+        "packet_queue capacity={} policy={:?}",
+        queue_config.capacity,
+        queue_config.policy
+    );
+    let (mut packet_sender, packet_receiver) = packet_channel(queue_config);
     let (restart_sender, mut restart_receiver) = watch::channel(false);
     RESTART_SENDER.set(restart_sender.clone()).ok();
 
@@ -182,6 +394,35 @@ pub fn start_capture(
         CaptureMethod::Npcap(dev) => {
             info!(target: "app::capture", "capture_start method=Npcap device={}", dev)
         }
+        CaptureMethod::PcapFile(path, pacing) => {
+            info!(
+                target: "app::capture",
+                "capture_start method=PcapFile path={} pacing={:?}",
+                path.display(),
+                pacing
+            )
+        }
+        CaptureMethod::SessionFile(path, pacing) => {
+            info!(
+                target: "app::capture",
+                "capture_start method=SessionFile path={} pacing={:?}",
+                path.display(),
+                pacing
+            )
+        }
+    }
+
+    if let CaptureMethod::SessionFile(path, pacing) = method {
+        std::thread::spawn(move || {
+            let replay_span = tracing::info_span!(
+                target: "app::capture",
+                "session_replay_thread",
+                path = %path.display()
+            );
+            let _replay_guard = replay_span.enter();
+            replay_session_file(&path, pacing, &mut packet_sender);
+        });
+        return packet_receiver;
     }
 
     // Use std::thread::spawn to avoid blocking the async runtime with WinDivert recv
@@ -189,52 +430,198 @@ pub fn start_capture(
         let capture_span =
             tracing::info_span!(target: "app::capture", "capture_thread", method = ?method);
         let _capture_guard = capture_span.enter();
-        loop {
-            read_packets(
-                &packet_sender,
-                &capture_queue_depth,
-                &mut restart_receiver,
-                method.clone(),
-            );
+        supervise_capture(
+            method,
+            &mut packet_sender,
+            &restart_sender,
+            &mut restart_receiver,
+        );
+    });
+    packet_receiver
+}
+
+/// Replays a pcap/pcapng file on its own thread as fast as the receiver takes packets.
+///
+/// Unlike [`start_capture`], the replay reports how it ended through the returned join handle
+/// rather than the shared capture status.
+pub fn spawn_pcap_replay(
+    path: PathBuf,
+) -> (
+    tokio::sync::mpsc::Receiver<PacketItem>,
+    std::thread::JoinHandle<Result<(), String>>,
+) {
+    let (mut packet_sender, packet_receiver) = packet_channel(PacketQueueConfig {
+        policy: OverflowPolicy::Block,
+        ..PacketQueueConfig::default()
+    });
+    let handle = std::thread::spawn(move || {
+        // A one-off replay is never restarted; the sender only keeps the receiver valid.
+        let (_restart_sender, mut restart_receiver) = watch::channel(false);
+        let method = CaptureMethod::PcapFile(path, ReplayPacing::AsFastAsPossible);
+        match read_packets(&mut packet_sender, &mut restart_receiver, method) {
+            CaptureExit::Exhausted
+            | CaptureExit::ReceiverClosed
+            | CaptureExit::RestartRequested => Ok(()),
+            CaptureExit::InitFailed(reason)
+            | CaptureExit::SourceFailed(reason)
+            | CaptureExit::Unsupported(reason) => Err(reason),
+        }
+    });
+    (packet_receiver, handle)
+}
+
+/// Why [`read_packets`] returned.
+enum CaptureExit {
+    /// [`request_restart`] was called.
+    RestartRequested,
+    /// The live meter dropped the packet receiver.
+    ReceiverClosed,
+    /// A capture file was read to the end.
+    Exhausted,
+    /// The capture source could not be opened.
+    InitFailed(String),
+    /// The capture source failed while reading.
+    SourceFailed(String),
+    /// The method can never work here, e.g. WinDivert outside Windows.
+    Unsupported(String),
+}
+
+/// Runs the capture source, reopening it with exponential backoff whenever it fails.
+///
+/// Npcap devices are re-detected before each retry, so an adapter that goes away while the
+/// laptop sleeps or switches networks is picked up again once it (or an equivalent) is back.
+fn supervise_capture(
+    mut method: CaptureMethod,
+    packet_sender: &mut PacketSender,
+    restart_sender: &watch::Sender<bool>,
+    restart_receiver: &mut watch::Receiver<bool>,
+) {
+    let status = capture_status();
+    let mut backoff = RestartBackoff::new();
+    let device_description = match &method {
+        CaptureMethod::Npcap(device) => npcap::device_description(device),
+        _ => None,
+    };
 
-            // Check if this was a requested restart or a crash/exit
-            if !*restart_receiver.borrow() {
-                warn!("Packet capture exited unexpectedly. Restarting in 1s...");
-                std::thread::sleep(std::time::Duration::from_secs(1));
+    status.set_starting();
+    loop {
+        let started_at = Instant::now();
+        let reason = match read_packets(packet_sender, restart_receiver, method.clone()) {
+            CaptureExit::RestartRequested => {
+                info!(target: "app::capture", "capture_restart reason=requested");
+                let _ = restart_sender.send(false);
+                status.record_restart();
+                status.set_starting();
+                backoff.reset();
                 continue;
             }
-
-            // Wait for restart signal if it was requested
-            while !*restart_receiver.borrow() {
-                std::thread::sleep(std::time::Duration::from_millis(100));
+            CaptureExit::ReceiverClosed => {
+                info!(target: "app::capture", "packet receiver dropped; stopping capture");
+                return;
             }
-            // Reset signal to false before next loop
+            CaptureExit::Exhausted => {
+                // A finished replay closes the channel so consumers see the end of the stream.
+                info!(target: "app::capture", "pcap replay finished; closing packet channel");
+                status.set_finished();
+                return;
+            }
+            CaptureExit::Unsupported(reason) => {
+                status.set_failed(reason);
+                return;
+            }
+            CaptureExit::InitFailed(reason) | CaptureExit::SourceFailed(reason) => reason,
+        };
+
+        // Reopening a broken capture file won't fix it.
+        if matches!(method, CaptureMethod::PcapFile(..)) {
+            status.set_failed(reason);
+            return;
+        }
+
+        if started_at.elapsed() >= CAPTURE_STABLE_AFTER {
+            backoff.reset();
+        }
+        let delay = backoff.next_delay();
+        warn!(
+            target: "app::capture",
+            "capture_restart reason=failure attempt={} delay_ms={} err={}",
+            backoff.attempts(),
+            delay.as_millis(),
+            reason
+        );
+        if backoff.attempts() >= FAILED_AFTER_ATTEMPTS {
+            status.set_failed(format!("{reason} (retrying in {}s)", delay.as_secs()));
+        } else {
+            status.set_degraded(reason);
+        }
+        if wait_for_restart(restart_receiver, delay) {
             let _ = restart_sender.send(false);
+            status.set_starting();
+            backoff.reset();
         }
-        // info!("oopsies {}", line!());
-    });
-    (packet_receiver, queue_depth)
+        status.record_restart();
+
+        if let CaptureMethod::Npcap(device) = &method
+            && let Some(redetected) = npcap::redetect_device(device, device_description.as_deref())
+            && redetected != *device
+        {
+            info!(
+                target: "app::capture",
+                "capture_device_redetected previous={} device={}",
+                device,
+                redetected
+            );
+            method = CaptureMethod::Npcap(redetected);
+        }
+    }
+}
+
+/// Sleeps for `delay`, returning `true` early if a restart is requested in the meantime.
+fn wait_for_restart(restart_receiver: &watch::Receiver<bool>, delay: Duration) -> bool {
+    let deadline = Instant::now() + delay;
+    loop {
+        if *restart_receiver.borrow() {
+            return true;
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return false;
+        }
+        std::thread::sleep(remaining.min(RESTART_POLL_INTERVAL));
+    }
 }
 
 #[allow(clippy::too_many_lines)]
 fn read_packets(
-    packet_sender: &tokio::sync::mpsc::UnboundedSender<(packets::opcodes::Pkt, Bytes)>,
-    queue_depth: &AtomicUsize,
+    packet_sender: &mut PacketSender,
     restart_receiver: &mut watch::Receiver<bool>,
     method: CaptureMethod,
-) {
+) -> CaptureExit {
     let read_span =
         tracing::info_span!(target: "app::capture", "capture_read_loop", method = ?method);
     let _read_guard = read_span.enter();
 
+    let device = match &method {
+        CaptureMethod::Npcap(device) => Some(device.clone()),
+        _ => None,
+    };
     let mut source: Box<dyn PacketSource> = match method {
+        #[cfg(windows)]
         CaptureMethod::WinDivert => match WinDivertSource::new() {
             Ok(s) => Box::new(s),
             Err(e) => {
                 error!(target: "app::capture", "capture_source_init_failed method=WinDivert err={}", e);
-                return;
+                return CaptureExit::InitFailed(e);
             }
         },
+        #[cfg(not(windows))]
+        CaptureMethod::WinDivert => {
+            error!(
+                target: "app::capture",
+                "capture_source_init_failed method=WinDivert err=WinDivert is only available on Windows"
+            );
+            return CaptureExit::Unsupported("WinDivert is only available on Windows".to_string());
+        }
         CaptureMethod::Npcap(device) => match NpcapSource::new(&device) {
             Ok(s) => Box::new(s),
             Err(e) => {
@@ -244,24 +631,55 @@ fn read_packets(
                     device,
                     e
                 );
-                return;
+                return CaptureExit::InitFailed(e);
+            }
+        },
+        CaptureMethod::SessionFile(..) => {
+            error!(target: "app::capture", "SessionFile replay does not go through read_packets");
+            return CaptureExit::Unsupported(
+                "SessionFile replay does not go through read_packets".to_string(),
+            );
+        }
+        CaptureMethod::PcapFile(path, pacing) => match PcapFileSource::new(&path, pacing) {
+            Ok(s) => Box::new(s),
+            Err(e) => {
+                error!(
+                    target: "app::capture",
+                    "capture_source_init_failed method=PcapFile path={} err={}",
+                    path.display(),
+                    e
+                );
+                return CaptureExit::InitFailed(e);
             }
         },
     };
 
-    let mut known_server: Option<Server> = None; // nothing at start
-    let mut tcp_reassembler: TCPReassembler = TCPReassembler::new();
-    let mut reassembler = Reassembler::new();
+    capture_status().set_running(device);
+    let mut streams = StreamTable::new();
+    let stats = capture_stats();
 
     loop {
+        if *restart_receiver.borrow() {
+            return CaptureExit::RestartRequested;
+        }
+        if packet_sender.is_closed() {
+            return CaptureExit::ReceiverClosed;
+        }
         let packet_data = match source.next_packet() {
             Ok(Some(data)) => data,
-            Ok(None) => continue, // Timeout or ignored packet
+            Ok(None) if source.is_exhausted() => return CaptureExit::Exhausted,
+            Ok(None) => {
+                // Timeout or ignored packet; a quiet moment to send anything coalesced.
+                let _ = packet_sender.flush_pending();
+                continue;
+            }
             Err(e) => {
                 error!(target: "app::capture", "capture_error err={}", e);
-                break; // Exit loop on error? Or retry?
+                return CaptureExit::SourceFailed(e);
             }
         };
+        stats.record_packet(packet_data.len());
+        let captured_at_ms = source.captured_at_ms();
 
         // info!("{}", line!());
         let packet_format = source.packet_format();
@@ -274,8 +692,16 @@ fn read_packets(
             continue; // if it's not ip, go next packet
         };
         // info!("{}", line!());
-        let Some(Ipv4(ip_packet)) = network_slices.net else {
-            continue;
+        let (src_addr, dst_addr): (IpAddr, IpAddr) = match &network_slices.net {
+            Some(Ipv4(ip_packet)) => (
+                ip_packet.header().source_addr().into(),
+                ip_packet.header().destination_addr().into(),
+            ),
+            Some(Ipv6(ip_packet)) => (
+                ip_packet.header().source_addr().into(),
+                ip_packet.header().destination_addr().into(),
+            ),
+            _ => continue,
         };
         // info!("{}", line!());
         let Some(Tcp(tcp_packet)) = network_slices.transport else {
@@ -283,9 +709,9 @@ fn read_packets(
         };
         // info!("{}", line!());
         let curr_server = Server::new(
-            ip_packet.header().source(),
+            src_addr,
             tcp_packet.to_header().source_port,
-            ip_packet.header().destination(),
+            dst_addr,
             tcp_packet.to_header().destination_port,
         );
         // trace!(
@@ -295,99 +721,25 @@ fn read_packets(
         //     tcp_packet.payload(),
         // );
 
-        // 1. Try to identify game server via small packets
-        if known_server != Some(curr_server) {
+        let now = Instant::now();
+        streams.evict_idle(now);
+        let Some(stream) = streams.touch(&curr_server, now) else {
             let tcp_payload = tcp_packet.payload();
-            if tcp_payload.len() >= 10 && tcp_payload[4] == 0 {
-                const FRAG_LENGTH_SIZE: usize = 4;
-                const SIGNATURE: [u8; 6] = [0x00, 0x63, 0x33, 0x53, 0x42, 0x00];
-                const MAX_FRAG_ITERATIONS: usize = 2000; // Circuit breaker
-
-                let mut i = 0usize;
-                let mut offset = 10usize;
-                while tcp_payload.len().saturating_sub(offset) >= FRAG_LENGTH_SIZE {
-                    i += 1;
-                    if i >= MAX_FRAG_ITERATIONS {
-                        error!(
-                            "TCP fragment processing stuck after {i} iterations - forcing recovery. \
-                            remaining={}, line={}",
-                            tcp_payload.len().saturating_sub(offset),
-                            line!()
-                        );
-                        break;
-                    }
-                    if i % 1000 == 0 {
-                        warn!(
-                            "High iteration count in fragment processing: iteration={i}, remaining={}, line={}",
-                            tcp_payload.len().saturating_sub(offset),
-                            line!()
-                        );
-                    }
-
-                    let len_bytes = &tcp_payload[offset..offset + FRAG_LENGTH_SIZE];
-                    let tcp_frag_payload_len = u32::from_be_bytes([
-                        len_bytes[0],
-                        len_bytes[1],
-                        len_bytes[2],
-                        len_bytes[3],
-                    ])
-                    .saturating_sub(FRAG_LENGTH_SIZE as u32)
-                        as usize;
-                    offset += FRAG_LENGTH_SIZE;
-
-                    if tcp_payload.len().saturating_sub(offset) < tcp_frag_payload_len {
-                        break;
-                    }
-
-                    let tcp_frag = &tcp_payload[offset..offset + tcp_frag_payload_len];
-                    offset += tcp_frag_payload_len;
-
-                    if tcp_frag.len() >= 5 + SIGNATURE.len()
-                        && tcp_frag[5..5 + SIGNATURE.len()] == SIGNATURE
-                    {
-                        info!(
-                            target: "app::capture",
-                            "Got Scene Server Address (by change): {curr_server}"
-                        );
-                        known_server = Some(curr_server);
-                        let payload_len = u32::try_from(tcp_payload.len()).unwrap_or(u32::MAX);
-                        let seq_end = tcp_packet.sequence_number().wrapping_add(payload_len);
-                        reset_stream(&mut tcp_reassembler, &mut reassembler, Some(seq_end));
-                        if let Err(err) = packet_sender.send((Pkt::ServerChangeInfo, Bytes::new()))
-                        {
-                            debug!("Failed to send packet: {err}");
-                        } else {
-                            queue_depth.fetch_add(1, Ordering::Relaxed);
-                        }
-                    }
-                }
-            }
-            // 2. Payload length is 98 = Login packets?
-            if tcp_payload.len() == 98 {
-                const SIGNATURE_1: [u8; 10] =
-                    [0x00, 0x00, 0x00, 0x62, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01];
-                const SIGNATURE_2: [u8; 6] = [0x00, 0x00, 0x00, 0x00, 0x0a, 0x4e];
-                if tcp_payload.len() >= 20
-                    && tcp_payload[0..10] == SIGNATURE_1
-                    && tcp_payload[14..20] == SIGNATURE_2
-                {
-                    info!(
-                        target: "app::capture",
-                        "Got Scene Server Address by Login Return Packet: {curr_server}"
-                    );
-                    known_server = Some(curr_server);
-                    let payload_len = u32::try_from(tcp_payload.len()).unwrap_or(u32::MAX);
-                    let seq_end = tcp_packet.sequence_number().wrapping_add(payload_len);
-                    reset_stream(&mut tcp_reassembler, &mut reassembler, Some(seq_end));
-                    if let Err(err) = packet_sender.send((Pkt::ServerChangeInfo, Bytes::new())) {
-                        debug!("Failed to send packet: {err}");
-                    } else {
-                        queue_depth.fetch_add(1, Ordering::Relaxed);
-                    }
+            if is_scene_server_packet(&curr_server, tcp_payload) {
+                let payload_len = u32::try_from(tcp_payload.len()).unwrap_or(u32::MAX);
+                let seq_end = tcp_packet.sequence_number().wrapping_add(payload_len);
+                let connection_id = streams.insert(curr_server, Some(seq_end), now);
+                if let Err(err) = packet_sender.send(PacketItem::new(
+                    connection_id,
+                    Pkt::ServerChangeInfo,
+                    Bytes::new(),
+                    captured_at_ms,
+                )) {
+                    debug!("Failed to send packet: {err}");
                 }
             }
             continue;
-        }
+        };
 
         let sequence_number = tcp_packet.sequence_number();
         let payload = tcp_packet.payload();
@@ -398,11 +750,8 @@ fn read_packets(
                 target: "app::capture",
                 "SYN observed for {curr_server}; resetting TCP reassembler state"
             );
-            reset_stream(
-                &mut tcp_reassembler,
-                &mut reassembler,
-                Some(sequence_number.wrapping_add(1)),
-            );
+            stream.reset(Some(sequence_number.wrapping_add(1)));
+            stats.record_syn_reset();
             if payload_len == 0 {
                 continue;
             }
@@ -415,32 +764,33 @@ fn read_packets(
 
         if payload_len == 0 {
             if defer_reset {
-                reset_stream(&mut tcp_reassembler, &mut reassembler, None);
+                stream.reset(None);
+                stats.record_fin_reset();
             }
             continue;
         }
 
-        if let Some(expected) = tcp_reassembler.next_sequence() {
-            if tcp_sequence_before(sequence_number, expected) {
-                let backwards = expected.wrapping_sub(sequence_number);
-                if backwards > MAX_BACKTRACK_BYTES {
-                    warn!(
-                        target: "app::capture",
-                        "Sequence regression detected for {curr_server}: expected {expected}, \
-                        got {sequence_number} (backwards {backwards} bytes). Resetting stream"
-                    );
-                    reset_stream(
-                        &mut tcp_reassembler,
-                        &mut reassembler,
-                        Some(sequence_number),
-                    );
-                }
+        if let Some(expected) = stream.tcp_reassembler.next_sequence()
+            && tcp_sequence_before(sequence_number, expected)
+        {
+            let backwards = expected.wrapping_sub(sequence_number);
+            if backwards > MAX_BACKTRACK_BYTES {
+                warn!(
+                    target: "app::capture",
+                    "Sequence regression detected for {curr_server}: expected {expected}, \
+                    got {sequence_number} (backwards {backwards} bytes). Resetting stream"
+                );
+                stream.reset(Some(sequence_number));
+                stats.record_sequence_regression_reset();
             }
         }
 
-        match tcp_reassembler.insert_segment(sequence_number, payload) {
+        match stream
+            .tcp_reassembler
+            .insert_segment(sequence_number, payload)
+        {
             TcpInsertResult::Contiguous(buffer) => {
-                reassembler.feed_owned(buffer);
+                stream.reassembler.feed_owned(buffer);
             }
             TcpInsertResult::SkippedGap {
                 from,
@@ -452,41 +802,155 @@ fn read_packets(
                     target: "app::capture",
                     "TCP gap skipped for {curr_server}: from={from} to={to} reason={reason:?}; clearing frame reassembler"
                 );
-                reassembler.take_remaining();
+                stats.record_gap_skipped(reason);
+                stream.reassembler.take_remaining();
                 if !data.is_empty() {
-                    reassembler.feed_owned(data);
+                    stream.reassembler.feed_owned(data);
                 }
             }
             TcpInsertResult::Gap | TcpInsertResult::NoData => {}
         }
 
-        while let Some(packet) = reassembler.try_next() {
-            process_packet(&packet, packet_sender, queue_depth);
+        while let Some(packet) = stream.reassembler.try_next() {
+            process_packet(stream.id, &packet, captured_at_ms, packet_sender);
         }
 
         if defer_reset {
-            reset_stream(&mut tcp_reassembler, &mut reassembler, None);
+            stream.reset(None);
+            stats.record_fin_reset();
         }
-        if *restart_receiver.borrow() {
-            break;
+    }
+}
+
+/// Checks whether a packet from an untracked stream identifies it as a scene server.
+fn is_scene_server_packet(server: &Server, tcp_payload: &[u8]) -> bool {
+    // 1. Try to identify game server via small packets
+    if tcp_payload.len() >= 10 && tcp_payload[4] == 0 {
+        const FRAG_LENGTH_SIZE: usize = 4;
+        const SIGNATURE: [u8; 6] = [0x00, 0x63, 0x33, 0x53, 0x42, 0x00];
+        const MAX_FRAG_ITERATIONS: usize = 2000; // Circuit breaker
+
+        let mut i = 0usize;
+        let mut offset = 10usize;
+        while tcp_payload.len().saturating_sub(offset) >= FRAG_LENGTH_SIZE {
+            i += 1;
+            if i >= MAX_FRAG_ITERATIONS {
+                error!(
+                    "TCP fragment processing stuck after {i} iterations - forcing recovery. \
+                    remaining={}, line={}",
+                    tcp_payload.len().saturating_sub(offset),
+                    line!()
+                );
+                break;
+            }
+            if i.is_multiple_of(1000) {
+                warn!(
+                    "High iteration count in fragment processing: iteration={i}, remaining={}, line={}",
+                    tcp_payload.len().saturating_sub(offset),
+                    line!()
+                );
+            }
+
+            let len_bytes = &tcp_payload[offset..offset + FRAG_LENGTH_SIZE];
+            let tcp_frag_payload_len =
+                u32::from_be_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]])
+                    .saturating_sub(FRAG_LENGTH_SIZE as u32) as usize;
+            offset += FRAG_LENGTH_SIZE;
+
+            if tcp_payload.len().saturating_sub(offset) < tcp_frag_payload_len {
+                break;
+            }
+
+            let tcp_frag = &tcp_payload[offset..offset + tcp_frag_payload_len];
+            offset += tcp_frag_payload_len;
+
+            if tcp_frag.len() >= 5 + SIGNATURE.len()
+                && tcp_frag[5..5 + SIGNATURE.len()] == SIGNATURE
+            {
+                info!(
+                    target: "app::capture",
+                    "Got Scene Server Address (by change): {server}"
+                );
+                return true;
+            }
         }
-    } // todo: if it errors, it breaks out of the loop but will it ever error?
-    // info!("{}", line!());
+    }
+    // 2. Payload length is 98 = Login packets?
+    if tcp_payload.len() == 98 {
+        const SIGNATURE_1: [u8; 10] = [0x00, 0x00, 0x00, 0x62, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01];
+        const SIGNATURE_2: [u8; 6] = [0x00, 0x00, 0x00, 0x00, 0x0a, 0x4e];
+        if tcp_payload[0..10] == SIGNATURE_1 && tcp_payload[14..20] == SIGNATURE_2 {
+            info!(
+                target: "app::capture",
+                "Got Scene Server Address by Login Return Packet: {server}"
+            );
+            return true;
+        }
+    }
+    false
 }
 
-// Function to send restart signal from another thread/task
-#[allow(dead_code)]
-pub fn request_restart() {
-    if let Some(sender) = RESTART_SENDER.get() {
-        let _ = sender.send(true);
+/// Feeds a recorded session straight into the packet channel, then closes it.
+fn replay_session_file(path: &Path, pacing: ReplayPacing, packet_sender: &mut PacketSender) {
+    let mut reader = match SessionReader::open(path) {
+        Ok(reader) => reader,
+        Err(e) => {
+            error!(
+                target: "app::capture",
+                "capture_source_init_failed method=SessionFile path={} err={}",
+                path.display(),
+                e
+            );
+            capture_status().set_failed(e);
+            return;
+        }
+    };
+    capture_status().set_running(None);
+
+    let mut clock = ReplayClock::new(pacing);
+    let mut replayed = 0u64;
+    loop {
+        let record = match reader.next_record() {
+            Ok(Some(record)) => record,
+            Ok(None) => break,
+            Err(e) => {
+                error!(target: "app::capture", "session_replay_error err={}", e);
+                capture_status().set_failed(e);
+                return;
+            }
+        };
+        clock.wait_for_capture_time(Duration::from_millis(record.captured_at_ms));
+        if packet_sender
+            .send(PacketItem::new(
+                record.connection_id,
+                record.op,
+                record.payload,
+                record.captured_at_ms,
+            ))
+            .is_err()
+        {
+            debug!("Session replay receiver dropped; stopping");
+            break;
+        }
+        replayed += 1;
     }
+    info!(
+        target: "app::capture",
+        "session replay finished path={} packets={}",
+        path.display(),
+        replayed
+    );
+    capture_status().set_finished();
 }
 
-fn reset_stream(
-    tcp_reassembler: &mut TCPReassembler,
-    reassembler: &mut Reassembler,
-    next_seq: Option<u32>,
-) {
-    reassembler.take_remaining();
-    tcp_reassembler.reset(next_seq);
+/// Asks the capture thread to close and reopen its source.
+///
+/// Fails if no capture has been started.
+pub fn request_restart() -> Result<(), String> {
+    let sender = RESTART_SENDER
+        .get()
+        .ok_or_else(|| "packet capture is not running".to_string())?;
+    sender
+        .send(true)
+        .map_err(|_| "packet capture thread has stopped".to_string())
 }
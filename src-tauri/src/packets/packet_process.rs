@@ -1,14 +1,17 @@
-use crate::packets;
+use crate::packets::capture_stats::capture_stats;
 use crate::packets::opcodes::FragmentType;
+use crate::packets::packet_queue::{PacketItem, PacketSender};
 use crate::packets::parser;
+use crate::packets::stream_table::ConnectionId;
 use bytes::Bytes;
 use log::debug;
-use std::sync::atomic::{AtomicUsize, Ordering};
 
+/// Splits a reassembled frame into packets and queues them, stamped with `captured_at_ms`.
 pub fn process_packet(
+    connection_id: ConnectionId,
     frame: &Bytes,
-    packet_sender: &tokio::sync::mpsc::UnboundedSender<(packets::opcodes::Pkt, Bytes)>,
-    queue_depth: &AtomicUsize,
+    captured_at_ms: u64,
+    packet_sender: &mut PacketSender,
 ) {
     let mut offset = 0usize;
     let buf = frame.as_ref();
@@ -51,12 +54,13 @@ pub fn process_packet(
                     payload_start,
                     payload_end,
                     is_zstd_compressed,
-                ) {
-                    if let Err(err) = packet_sender.send((method_id, payload)) {
-                        debug!("Failed to send packet: {err}");
-                    } else {
-                        queue_depth.fetch_add(1, Ordering::Relaxed);
-                    }
+                ) && let Err(err) = packet_sender.send(PacketItem::new(
+                    connection_id,
+                    method_id,
+                    payload,
+                    captured_at_ms,
+                )) {
+                    debug!("Failed to send packet: {err}");
                 }
             }
             FragmentType::FrameDown => {
@@ -72,15 +76,21 @@ pub fn process_packet(
                     match zstd::decode_all(nested_packet) {
                         Ok(tcp_fragment_decompressed) => {
                             let nested_bytes = Bytes::from(tcp_fragment_decompressed);
-                            process_packet(&nested_bytes, packet_sender, queue_depth);
+                            process_packet(
+                                connection_id,
+                                &nested_bytes,
+                                captured_at_ms,
+                                packet_sender,
+                            );
                         }
                         Err(_e) => {
                             debug!("FrameDown: zstd decompression failed");
+                            capture_stats().record_zstd_failure();
                         }
                     }
                 } else {
                     let nested_bytes = frame.slice(nested_start..payload_end);
-                    process_packet(&nested_bytes, packet_sender, queue_depth);
+                    process_packet(connection_id, &nested_bytes, captured_at_ms, packet_sender);
                 }
             }
             _ => {}
@@ -0,0 +1,390 @@
+use crate::packets::opcodes::Pkt;
+use crate::packets::stream_table::ConnectionId;
+use bytes::Bytes;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+
+/// One decoded packet on its way from the capture thread to the live meter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketItem {
+    pub connection_id: ConnectionId,
+    pub op: Pkt,
+    pub data: Bytes,
+    /// Unix time in milliseconds the frame carrying the packet was captured, so queueing
+    /// delay doesn't skew recorded timings.
+    pub captured_at_ms: u64,
+}
+
+impl PacketItem {
+    pub fn new(connection_id: ConnectionId, op: Pkt, data: Bytes, captured_at_ms: u64) -> Self {
+        Self {
+            connection_id,
+            op,
+            data,
+            captured_at_ms,
+        }
+    }
+}
+
+pub const DEFAULT_PACKET_QUEUE_CAPACITY: usize = 4096;
+const MIN_PACKET_QUEUE_CAPACITY: usize = 256;
+const MAX_PACKET_QUEUE_CAPACITY: usize = 65_536;
+
+/// Process-wide loss counters, shared by every capture started during this run.
+static PACKET_QUEUE_STATS: LazyLock<Arc<PacketQueueStats>> =
+    LazyLock::new(|| Arc::new(PacketQueueStats::default()));
+
+/// What the capture thread does with a low-priority packet when the queue is full.
+///
+/// Combat-critical packets (see [`is_critical`]) always wait for room instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait for the consumer. Nothing is lost here, but the OS capture buffer may overflow.
+    Block,
+    /// Drop the packet.
+    Drop,
+    /// Hold only the newest packet per opcode and connection of full-snapshot opcodes (see
+    /// [`is_coalescable`]) and send it once there's room; drop everything else.
+    #[default]
+    Coalesce,
+}
+
+impl OverflowPolicy {
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "block" | "Block" => Self::Block,
+            "drop" | "Drop" => Self::Drop,
+            _ => Self::Coalesce,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketQueueConfig {
+    pub capacity: usize,
+    pub policy: OverflowPolicy,
+}
+
+impl PacketQueueConfig {
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            capacity: capacity.clamp(MIN_PACKET_QUEUE_CAPACITY, MAX_PACKET_QUEUE_CAPACITY),
+            policy,
+        }
+    }
+}
+
+impl Default for PacketQueueConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_PACKET_QUEUE_CAPACITY, OverflowPolicy::default())
+    }
+}
+
+/// Packets the meter can't reconstruct later: damage/heal deltas, stream boundaries, revives
+/// and the one-off buff snapshot sent on scene entry.
+pub fn is_critical(op: Pkt) -> bool {
+    matches!(
+        op,
+        Pkt::SyncNearDeltaInfo
+            | Pkt::SyncToMeDeltaInfo
+            | Pkt::ServerChangeInfo
+            | Pkt::EnterScene
+            | Pkt::BuffInfoSync
+            | Pkt::NotifyReviveUser
+    )
+}
+
+/// Packets that carry a full snapshot, so a newer one makes an older one redundant. Deltas and
+/// dirty-data updates are never coalesced: each one changes state the next one doesn't repeat.
+pub fn is_coalescable(op: Pkt) -> bool {
+    matches!(
+        op,
+        Pkt::SyncServerTime | Pkt::SyncContainerData | Pkt::SyncDungeonData
+    )
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DropCounts {
+    /// Packets discarded outright.
+    pub dropped: u64,
+    /// Packets replaced by a newer packet of the same opcode before they were sent.
+    pub coalesced: u64,
+}
+
+/// Per-opcode record of packets lost to backpressure.
+#[derive(Debug, Default)]
+pub struct PacketQueueStats {
+    by_opcode: Mutex<HashMap<Pkt, DropCounts>>,
+    total_lost: AtomicU64,
+}
+
+impl PacketQueueStats {
+    fn record(&self, op: Pkt, update: impl FnOnce(&mut DropCounts)) {
+        if let Ok(mut by_opcode) = self.by_opcode.lock() {
+            update(by_opcode.entry(op).or_default());
+        }
+        self.total_lost.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Dropped plus coalesced packets; cheap enough to poll every emit tick.
+    pub fn total_lost(&self) -> u64 {
+        self.total_lost.load(Ordering::Relaxed)
+    }
+
+    /// Counts per opcode, ordered by method id.
+    pub fn snapshot(&self) -> Vec<(Pkt, DropCounts)> {
+        let mut counts: Vec<(Pkt, DropCounts)> = self
+            .by_opcode
+            .lock()
+            .map(|by_opcode| by_opcode.iter().map(|(op, c)| (*op, *c)).collect())
+            .unwrap_or_default();
+        counts.sort_by_key(|(op, _)| op.method_id());
+        counts
+    }
+}
+
+/// Loss counters for the packet queue.
+pub fn queue_stats() -> &'static PacketQueueStats {
+    &PACKET_QUEUE_STATS
+}
+
+/// Creates the bounded capture -> live channel.
+pub fn packet_channel(config: PacketQueueConfig) -> (PacketSender, Receiver<PacketItem>) {
+    packet_channel_with_stats(config, Arc::clone(&PACKET_QUEUE_STATS))
+}
+
+fn packet_channel_with_stats(
+    config: PacketQueueConfig,
+    stats: Arc<PacketQueueStats>,
+) -> (PacketSender, Receiver<PacketItem>) {
+    let (tx, rx) = channel(config.capacity);
+    let sender = PacketSender {
+        tx,
+        policy: config.policy,
+        pending: VecDeque::new(),
+        stats,
+    };
+    (sender, rx)
+}
+
+/// Capture-side half of the packet channel. Must not be used from inside the async runtime,
+/// since it blocks when the queue is full.
+pub struct PacketSender {
+    tx: Sender<PacketItem>,
+    policy: OverflowPolicy,
+    /// Coalesced packets waiting for room, in capture order, at most one per
+    /// (connection, opcode).
+    pending: VecDeque<PacketItem>,
+    stats: Arc<PacketQueueStats>,
+}
+
+impl PacketSender {
+    /// Queues a packet, applying the overflow policy if the queue is full.
+    ///
+    /// Only fails once the receiver is gone.
+    pub fn send(&mut self, item: PacketItem) -> Result<(), String> {
+        self.flush_pending()?;
+        if is_critical(item.op) || self.policy == OverflowPolicy::Block {
+            // Held packets were captured first, so they go out first.
+            while let Some(pending) = self.pending.pop_front() {
+                self.send_blocking(pending)?;
+            }
+            return self.send_blocking(item);
+        }
+        if !self.pending.is_empty() {
+            // Sending now would overtake the held packets.
+            self.overflow(item);
+            return Ok(());
+        }
+        match self.tx.try_send(item) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(item)) => {
+                self.overflow(item);
+                Ok(())
+            }
+            Err(TrySendError::Closed(_)) => Err("packet channel closed".to_string()),
+        }
+    }
+
+    /// Sends as many coalesced packets as currently fit.
+    pub fn flush_pending(&mut self) -> Result<(), String> {
+        while let Some(item) = self.pending.pop_front() {
+            match self.tx.try_send(item) {
+                Ok(()) => {}
+                Err(TrySendError::Full(item)) => {
+                    self.pending.push_front(item);
+                    break;
+                }
+                Err(TrySendError::Closed(_)) => return Err("packet channel closed".to_string()),
+            }
+        }
+        Ok(())
+    }
+
+    /// True once the receiver is gone.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    fn send_blocking(&self, item: PacketItem) -> Result<(), String> {
+        self.tx
+            .blocking_send(item)
+            .map_err(|_| "packet channel closed".to_string())
+    }
+
+    fn overflow(&mut self, item: PacketItem) {
+        let op = item.op;
+        if self.policy != OverflowPolicy::Coalesce || !is_coalescable(op) {
+            self.stats.record(op, |c| c.dropped += 1);
+            return;
+        }
+        // The newest snapshot takes the place of the old one at the back, keeping capture order.
+        if let Some(index) = self
+            .pending
+            .iter()
+            .position(|pending| pending.connection_id == item.connection_id && pending.op == op)
+        {
+            self.pending.remove(index);
+            self.stats.record(op, |c| c.coalesced += 1);
+        }
+        self.pending.push_back(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DropCounts, OverflowPolicy, PacketItem, PacketQueueConfig, PacketQueueStats,
+        packet_channel_with_stats,
+    };
+    use crate::packets::opcodes::Pkt;
+    use bytes::Bytes;
+    use std::sync::Arc;
+
+    fn config(policy: OverflowPolicy) -> PacketQueueConfig {
+        // Below the minimum; clamped up, so fill the queue explicitly in each test.
+        PacketQueueConfig::new(1, policy)
+    }
+
+    #[test]
+    fn drops_low_priority_packets_when_full() {
+        let stats = Arc::new(PacketQueueStats::default());
+        let config = config(OverflowPolicy::Drop);
+        let (mut sender, mut rx) = packet_channel_with_stats(config, Arc::clone(&stats));
+        for _ in 0..config.capacity {
+            sender
+                .send(PacketItem::new(1, Pkt::SyncServerTime, Bytes::new(), 0))
+                .unwrap();
+        }
+        sender
+            .send(PacketItem::new(1, Pkt::SyncNearEntities, Bytes::new(), 0))
+            .unwrap();
+        sender
+            .send(PacketItem::new(1, Pkt::SyncNearEntities, Bytes::new(), 0))
+            .unwrap();
+
+        assert_eq!(rx.len(), config.capacity);
+        assert_eq!(stats.total_lost(), 2);
+        assert_eq!(
+            stats.snapshot(),
+            vec![(
+                Pkt::SyncNearEntities,
+                DropCounts {
+                    dropped: 2,
+                    coalesced: 0
+                }
+            )]
+        );
+        rx.close();
+    }
+
+    #[test]
+    fn coalesces_snapshots_in_capture_order_and_drops_deltas() {
+        let stats = Arc::new(PacketQueueStats::default());
+        let config = config(OverflowPolicy::Coalesce);
+        let (mut sender, mut rx) = packet_channel_with_stats(config, Arc::clone(&stats));
+        for _ in 0..config.capacity {
+            sender
+                .send(PacketItem::new(1, Pkt::SyncSceneAttrs, Bytes::new(), 0))
+                .unwrap();
+        }
+        let container = |data: &'static [u8]| {
+            PacketItem::new(1, Pkt::SyncContainerData, Bytes::from_static(data), 0)
+        };
+        sender.send(container(b"old")).unwrap();
+        sender
+            .send(PacketItem::new(1, Pkt::SyncServerTime, Bytes::new(), 0))
+            .unwrap();
+        sender.send(container(b"new")).unwrap();
+        sender
+            .send(PacketItem::new(1, Pkt::SyncNearEntities, Bytes::new(), 0))
+            .unwrap();
+        assert_eq!(stats.total_lost(), 2);
+        assert_eq!(
+            stats.snapshot(),
+            vec![
+                (
+                    Pkt::SyncNearEntities,
+                    DropCounts {
+                        dropped: 1,
+                        coalesced: 0
+                    }
+                ),
+                (
+                    Pkt::SyncContainerData,
+                    DropCounts {
+                        dropped: 0,
+                        coalesced: 1
+                    }
+                ),
+            ]
+        );
+
+        while rx.try_recv().is_ok() {}
+        sender.flush_pending().unwrap();
+        assert_eq!(rx.try_recv().unwrap().op, Pkt::SyncServerTime);
+        assert_eq!(rx.try_recv().unwrap(), container(b"new"));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn critical_packets_go_out_after_held_packets() {
+        let stats = Arc::new(PacketQueueStats::default());
+        let config = config(OverflowPolicy::Coalesce);
+        let (mut sender, mut rx) = packet_channel_with_stats(config, stats);
+        for _ in 0..config.capacity {
+            sender
+                .send(PacketItem::new(1, Pkt::SyncSceneAttrs, Bytes::new(), 0))
+                .unwrap();
+        }
+        sender
+            .send(PacketItem::new(1, Pkt::SyncContainerData, Bytes::new(), 1))
+            .unwrap();
+
+        // Blocks until the receiver makes room.
+        let capture = std::thread::spawn(move || {
+            sender
+                .send(PacketItem::new(1, Pkt::SyncNearDeltaInfo, Bytes::new(), 2))
+                .unwrap();
+        });
+        let received: Vec<PacketItem> = (0..config.capacity + 2)
+            .map(|_| rx.blocking_recv().unwrap())
+            .collect();
+        capture.join().unwrap();
+        let tail: Vec<Pkt> = received[config.capacity..]
+            .iter()
+            .map(|item| item.op)
+            .collect();
+        assert_eq!(tail, vec![Pkt::SyncContainerData, Pkt::SyncNearDeltaInfo]);
+    }
+
+    #[test]
+    fn parses_overflow_policy_config() {
+        assert_eq!(OverflowPolicy::from_config("drop"), OverflowPolicy::Drop);
+        assert_eq!(OverflowPolicy::from_config("block"), OverflowPolicy::Block);
+        assert_eq!(OverflowPolicy::from_config("???"), OverflowPolicy::Coalesce);
+    }
+}
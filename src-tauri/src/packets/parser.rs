@@ -1,4 +1,5 @@
 use crate::packets;
+use crate::packets::capture_stats::capture_stats;
 use crate::packets::opcodes::Pkt;
 use bytes::Bytes;
 use log::debug;
@@ -23,6 +24,7 @@ pub fn parse_notify_fragment(
 
     if service_uuid != 0x0000000063335342 {
         debug!("Notify: service_uuid mismatch: {service_uuid:x}");
+        capture_stats().record_service_uuid_mismatch();
         return None;
     }
 
@@ -31,6 +33,7 @@ pub fn parse_notify_fragment(
             Ok(decoded) => Some((Pkt::try_from(method_id_raw).ok()?, Bytes::from(decoded))),
             Err(e) => {
                 debug!("Notify: zstd decompression failed: {e}");
+                capture_stats().record_zstd_failure();
                 None
             }
         }
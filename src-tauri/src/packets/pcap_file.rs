@@ -0,0 +1,531 @@
+use log::{info, warn};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::Path;
+use std::time::Duration;
+
+// Classic pcap magic numbers as read in little-endian order.
+const PCAP_MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+const PCAP_MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+const PCAP_MAGIC_MICROS_SWAPPED: u32 = 0xd4c3_b2a1;
+const PCAP_MAGIC_NANOS_SWAPPED: u32 = 0x4d3c_b2a1;
+
+// pcapng block types we understand; everything else is skipped.
+const PCAPNG_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+const PCAPNG_INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+const PCAPNG_OBSOLETE_PACKET: u32 = 0x0000_0002;
+const PCAPNG_SIMPLE_PACKET: u32 = 0x0000_0003;
+const PCAPNG_ENHANCED_PACKET: u32 = 0x0000_0006;
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+const PCAPNG_OPTION_END: u16 = 0;
+const PCAPNG_OPTION_IF_TSRESOL: u16 = 9;
+const PCAPNG_DEFAULT_TSRESOL: u8 = 6;
+
+// LINKTYPE_* values that differ from the DLT_* values used by the live capture path.
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const DLT_RAW: i32 = 12;
+
+/// Safety cap on a single record/block so a corrupt length can't trigger a huge allocation.
+const MAX_RECORD_SIZE: usize = 16 * 1024 * 1024;
+
+/// A single captured frame read from a pcap or pcapng file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcapRecord {
+    /// Capture timestamp relative to the Unix epoch.
+    pub timestamp: Duration,
+    /// libpcap `DLT_*` datalink type of the interface the frame was captured on.
+    pub datalink: i32,
+    /// Captured bytes (may be shorter than the original frame if a snaplen was applied).
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PcapNgInterface {
+    datalink: i32,
+    tsresol: u8,
+}
+
+#[derive(Debug)]
+enum FileFormat {
+    Classic {
+        big_endian: bool,
+        nanos: bool,
+        datalink: i32,
+    },
+    PcapNg {
+        big_endian: bool,
+        interfaces: Vec<PcapNgInterface>,
+    },
+}
+
+/// Streaming reader for classic pcap and pcapng capture files.
+///
+/// Only the pieces needed to replay traffic through the capture pipeline are parsed:
+/// interface link types, timestamp resolution and packet data.
+pub struct PcapFileReader<R: Read> {
+    reader: R,
+    format: FileFormat,
+}
+
+impl PcapFileReader<BufReader<File>> {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("open {}: {}", path.display(), e))?;
+        let reader = Self::new(BufReader::new(file))?;
+        info!(
+            target: "app::capture",
+            "pcap file opened path={} format={}",
+            path.display(),
+            reader.format_name()
+        );
+        Ok(reader)
+    }
+}
+
+impl<R: Read> PcapFileReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, String> {
+        let mut magic = [0u8; 4];
+        if !read_exact_or_eof(&mut reader, &mut magic)? {
+            return Err("capture file is empty".to_string());
+        }
+
+        let format = match u32::from_le_bytes(magic) {
+            PCAP_MAGIC_MICROS => read_classic_header(&mut reader, false, false)?,
+            PCAP_MAGIC_NANOS => read_classic_header(&mut reader, false, true)?,
+            PCAP_MAGIC_MICROS_SWAPPED => read_classic_header(&mut reader, true, false)?,
+            PCAP_MAGIC_NANOS_SWAPPED => read_classic_header(&mut reader, true, true)?,
+            PCAPNG_SECTION_HEADER => {
+                let big_endian = read_section_header_rest(&mut reader)?;
+                FileFormat::PcapNg {
+                    big_endian,
+                    interfaces: Vec::new(),
+                }
+            }
+            other => return Err(format!("unrecognized capture file magic 0x{other:08x}")),
+        };
+
+        Ok(Self { reader, format })
+    }
+
+    fn format_name(&self) -> &'static str {
+        match self.format {
+            FileFormat::Classic { .. } => "pcap",
+            FileFormat::PcapNg { .. } => "pcapng",
+        }
+    }
+
+    /// Returns the next captured frame, or `None` once the end of the file is reached.
+    pub fn next_record(&mut self) -> Result<Option<PcapRecord>, String> {
+        match self.format {
+            FileFormat::Classic {
+                big_endian,
+                nanos,
+                datalink,
+            } => self.next_classic_record(big_endian, nanos, datalink),
+            FileFormat::PcapNg { .. } => self.next_pcapng_record(),
+        }
+    }
+
+    fn next_classic_record(
+        &mut self,
+        big_endian: bool,
+        nanos: bool,
+        datalink: i32,
+    ) -> Result<Option<PcapRecord>, String> {
+        let mut header = [0u8; 16];
+        if !read_exact_or_eof(&mut self.reader, &mut header)? {
+            return Ok(None);
+        }
+
+        let ts_sec = read_u32(&header[0..4], big_endian);
+        let ts_frac = read_u32(&header[4..8], big_endian);
+        let incl_len = read_u32(&header[8..12], big_endian) as usize;
+        if incl_len > MAX_RECORD_SIZE {
+            return Err(format!("pcap record too large ({incl_len} bytes)"));
+        }
+
+        let mut data = vec![0u8; incl_len];
+        if !read_exact_or_eof(&mut self.reader, &mut data)? {
+            warn!(target: "app::capture", "pcap file ends with a truncated record; stopping");
+            return Ok(None);
+        }
+
+        let frac_nanos = if nanos {
+            ts_frac
+        } else {
+            ts_frac.saturating_mul(1_000)
+        };
+        Ok(Some(PcapRecord {
+            timestamp: Duration::new(u64::from(ts_sec), frac_nanos.min(999_999_999)),
+            datalink,
+            data,
+        }))
+    }
+
+    fn next_pcapng_record(&mut self) -> Result<Option<PcapRecord>, String> {
+        loop {
+            let mut header = [0u8; 8];
+            if !read_exact_or_eof(&mut self.reader, &mut header)? {
+                return Ok(None);
+            }
+
+            let FileFormat::PcapNg {
+                big_endian,
+                interfaces,
+            } = &mut self.format
+            else {
+                unreachable!("pcapng reader used on classic pcap file");
+            };
+
+            let block_type = read_u32(&header[0..4], *big_endian);
+            if block_type == PCAPNG_SECTION_HEADER {
+                // A new section may switch byte order, so the length is re-read after the BOM.
+                let mut bom = [0u8; 4];
+                if !read_exact_or_eof(&mut self.reader, &mut bom)? {
+                    return Ok(None);
+                }
+                *big_endian = section_is_big_endian(bom)?;
+                interfaces.clear();
+                let block_len = read_u32(&header[4..8], *big_endian) as usize;
+                skip_block_body(&mut self.reader, block_len, 12)?;
+                continue;
+            }
+
+            let block_len = read_u32(&header[4..8], *big_endian) as usize;
+            if block_len < 12 || !block_len.is_multiple_of(4) || block_len > MAX_RECORD_SIZE {
+                return Err(format!("invalid pcapng block length {block_len}"));
+            }
+
+            // Body plus the trailing copy of the block length.
+            let mut body = vec![0u8; block_len - 8];
+            if !read_exact_or_eof(&mut self.reader, &mut body)? {
+                warn!(target: "app::capture", "pcapng file ends with a truncated block; stopping");
+                return Ok(None);
+            }
+            body.truncate(block_len - 12);
+            let big_endian = *big_endian;
+
+            match block_type {
+                PCAPNG_INTERFACE_DESCRIPTION => {
+                    interfaces.push(parse_interface_description(&body, big_endian)?);
+                }
+                PCAPNG_ENHANCED_PACKET => {
+                    if body.len() < 20 {
+                        return Err("pcapng enhanced packet block too short".to_string());
+                    }
+                    let interface_id = read_u32(&body[0..4], big_endian) as usize;
+                    let ticks = (u64::from(read_u32(&body[4..8], big_endian)) << 32)
+                        | u64::from(read_u32(&body[8..12], big_endian));
+                    let captured_len = read_u32(&body[12..16], big_endian) as usize;
+                    return packet_from_block(
+                        interfaces,
+                        interface_id,
+                        ticks,
+                        &body[20..],
+                        captured_len,
+                    )
+                    .map(Some);
+                }
+                PCAPNG_SIMPLE_PACKET => {
+                    if body.len() < 4 {
+                        return Err("pcapng simple packet block too short".to_string());
+                    }
+                    let original_len = read_u32(&body[0..4], big_endian) as usize;
+                    return packet_from_block(interfaces, 0, 0, &body[4..], original_len).map(Some);
+                }
+                PCAPNG_OBSOLETE_PACKET => {
+                    if body.len() < 20 {
+                        return Err("pcapng packet block too short".to_string());
+                    }
+                    let interface_id = usize::from(read_u16(&body[0..2], big_endian));
+                    let ticks = (u64::from(read_u32(&body[4..8], big_endian)) << 32)
+                        | u64::from(read_u32(&body[8..12], big_endian));
+                    let captured_len = read_u32(&body[12..16], big_endian) as usize;
+                    return packet_from_block(
+                        interfaces,
+                        interface_id,
+                        ticks,
+                        &body[20..],
+                        captured_len,
+                    )
+                    .map(Some);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn read_classic_header<R: Read>(
+    reader: &mut R,
+    big_endian: bool,
+    nanos: bool,
+) -> Result<FileFormat, String> {
+    // version(4) + thiszone(4) + sigfigs(4) + snaplen(4) + network(4)
+    let mut header = [0u8; 20];
+    if !read_exact_or_eof(reader, &mut header)? {
+        return Err("pcap global header is truncated".to_string());
+    }
+    // The upper bits of the link type field carry FCS metadata we don't need.
+    let linktype = read_u32(&header[16..20], big_endian) & 0xffff;
+    Ok(FileFormat::Classic {
+        big_endian,
+        nanos,
+        datalink: linktype_to_datalink(linktype),
+    })
+}
+
+/// Consumes the remainder of the first section header block and returns its byte order.
+fn read_section_header_rest<R: Read>(reader: &mut R) -> Result<bool, String> {
+    let mut header = [0u8; 8];
+    if !read_exact_or_eof(reader, &mut header)? {
+        return Err("pcapng section header is truncated".to_string());
+    }
+    let big_endian = section_is_big_endian([header[4], header[5], header[6], header[7]])?;
+    let block_len = read_u32(&header[0..4], big_endian) as usize;
+    skip_block_body(reader, block_len, 12)?;
+    Ok(big_endian)
+}
+
+fn section_is_big_endian(bom: [u8; 4]) -> Result<bool, String> {
+    if u32::from_le_bytes(bom) == PCAPNG_BYTE_ORDER_MAGIC {
+        Ok(false)
+    } else if u32::from_be_bytes(bom) == PCAPNG_BYTE_ORDER_MAGIC {
+        Ok(true)
+    } else {
+        Err("invalid pcapng byte-order magic".to_string())
+    }
+}
+
+fn skip_block_body<R: Read>(
+    reader: &mut R,
+    block_len: usize,
+    consumed: usize,
+) -> Result<(), String> {
+    if block_len < consumed + 4 || !block_len.is_multiple_of(4) || block_len > MAX_RECORD_SIZE {
+        return Err(format!("invalid pcapng block length {block_len}"));
+    }
+    let mut remaining = vec![0u8; block_len - consumed];
+    if !read_exact_or_eof(reader, &mut remaining)? {
+        return Err("pcapng block is truncated".to_string());
+    }
+    Ok(())
+}
+
+fn parse_interface_description(body: &[u8], big_endian: bool) -> Result<PcapNgInterface, String> {
+    if body.len() < 8 {
+        return Err("pcapng interface description block too short".to_string());
+    }
+    let linktype = u32::from(read_u16(&body[0..2], big_endian));
+    let mut tsresol = PCAPNG_DEFAULT_TSRESOL;
+
+    let mut offset = 8usize;
+    while offset + 4 <= body.len() {
+        let code = read_u16(&body[offset..offset + 2], big_endian);
+        let len = usize::from(read_u16(&body[offset + 2..offset + 4], big_endian));
+        offset += 4;
+        if code == PCAPNG_OPTION_END || offset + len > body.len() {
+            break;
+        }
+        if code == PCAPNG_OPTION_IF_TSRESOL && len >= 1 {
+            tsresol = body[offset];
+        }
+        offset += len.div_ceil(4) * 4;
+    }
+
+    Ok(PcapNgInterface {
+        datalink: linktype_to_datalink(linktype),
+        tsresol,
+    })
+}
+
+fn packet_from_block(
+    interfaces: &[PcapNgInterface],
+    interface_id: usize,
+    ticks: u64,
+    data: &[u8],
+    captured_len: usize,
+) -> Result<PcapRecord, String> {
+    let interface = interfaces
+        .get(interface_id)
+        .ok_or_else(|| format!("pcapng packet references unknown interface {interface_id}"))?;
+    let captured_len = captured_len.min(data.len());
+    Ok(PcapRecord {
+        timestamp: ticks_to_duration(ticks, interface.tsresol),
+        datalink: interface.datalink,
+        data: data[..captured_len].to_vec(),
+    })
+}
+
+/// Converts a pcapng timestamp into a `Duration` using the interface's `if_tsresol`.
+/// The high bit selects a power-of-two resolution, otherwise it's a power of ten.
+fn ticks_to_duration(ticks: u64, tsresol: u8) -> Duration {
+    let ticks = u128::from(ticks);
+    let nanos = if tsresol & 0x80 != 0 {
+        (ticks * 1_000_000_000) >> u32::from(tsresol & 0x7f)
+    } else {
+        let exponent = u32::from(tsresol).min(38);
+        if exponent <= 9 {
+            ticks * 10u128.pow(9 - exponent)
+        } else {
+            ticks / 10u128.pow(exponent - 9)
+        }
+    };
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
+
+fn linktype_to_datalink(linktype: u32) -> i32 {
+    match linktype {
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => DLT_RAW,
+        other => i32::try_from(other).unwrap_or(i32::MAX),
+    }
+}
+
+/// Fills `buf` completely. Returns `Ok(false)` if the reader hits EOF before `buf` is full.
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<bool, String> {
+    let mut filled = 0usize;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => return Ok(false),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("read capture file: {e}")),
+        }
+    }
+    Ok(true)
+}
+
+fn read_u16(bytes: &[u8], big_endian: bool) -> u16 {
+    let raw = [bytes[0], bytes[1]];
+    if big_endian {
+        u16::from_be_bytes(raw)
+    } else {
+        u16::from_le_bytes(raw)
+    }
+}
+
+fn read_u32(bytes: &[u8], big_endian: bool) -> u32 {
+    let raw = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    if big_endian {
+        u32::from_be_bytes(raw)
+    } else {
+        u32::from_le_bytes(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PcapFileReader, PcapRecord};
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    fn classic_file(linktype: u32, records: &[(u32, u32, &[u8])]) -> Vec<u8> {
+        let mut file = Vec::new();
+        file.extend_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+        file.extend_from_slice(&2u16.to_le_bytes());
+        file.extend_from_slice(&4u16.to_le_bytes());
+        file.extend_from_slice(&0i32.to_le_bytes());
+        file.extend_from_slice(&0u32.to_le_bytes());
+        file.extend_from_slice(&65_535u32.to_le_bytes());
+        file.extend_from_slice(&linktype.to_le_bytes());
+        for (sec, usec, data) in records {
+            file.extend_from_slice(&sec.to_le_bytes());
+            file.extend_from_slice(&usec.to_le_bytes());
+            file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            file.extend_from_slice(data);
+        }
+        file
+    }
+
+    fn pcapng_block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let padded = body.len().div_ceil(4) * 4;
+        let total = (12 + padded) as u32;
+        let mut block = Vec::new();
+        block.extend_from_slice(&block_type.to_le_bytes());
+        block.extend_from_slice(&total.to_le_bytes());
+        block.extend_from_slice(body);
+        block.resize(8 + padded, 0);
+        block.extend_from_slice(&total.to_le_bytes());
+        block
+    }
+
+    #[test]
+    fn reads_classic_pcap_records() {
+        let file = classic_file(101, &[(10, 500, &b"abc"[..]), (11, 0, &b"defg"[..])]);
+        let mut reader = PcapFileReader::new(Cursor::new(file)).unwrap();
+
+        assert_eq!(
+            reader.next_record().unwrap(),
+            Some(PcapRecord {
+                timestamp: Duration::new(10, 500_000),
+                datalink: 12,
+                data: b"abc".to_vec(),
+            })
+        );
+        assert_eq!(
+            reader.next_record().unwrap().map(|r| r.data),
+            Some(b"defg".to_vec())
+        );
+        assert_eq!(reader.next_record().unwrap(), None);
+    }
+
+    #[test]
+    fn truncated_classic_record_ends_replay() {
+        let mut file = classic_file(1, &[(1, 0, &b"abcdef"[..])]);
+        file.truncate(file.len() - 2);
+        let mut reader = PcapFileReader::new(Cursor::new(file)).unwrap();
+        assert_eq!(reader.next_record().unwrap(), None);
+    }
+
+    #[test]
+    fn reads_pcapng_enhanced_packets_with_tsresol() {
+        let mut shb = Vec::new();
+        shb.extend_from_slice(&0x1a2b_3c4du32.to_le_bytes());
+        shb.extend_from_slice(&1u16.to_le_bytes());
+        shb.extend_from_slice(&0u16.to_le_bytes());
+        shb.extend_from_slice(&(-1i64).to_le_bytes());
+
+        let mut idb = Vec::new();
+        idb.extend_from_slice(&1u16.to_le_bytes());
+        idb.extend_from_slice(&0u16.to_le_bytes());
+        idb.extend_from_slice(&0u32.to_le_bytes());
+        // if_tsresol = 9 (nanoseconds), then opt_endofopt
+        idb.extend_from_slice(&9u16.to_le_bytes());
+        idb.extend_from_slice(&1u16.to_le_bytes());
+        idb.extend_from_slice(&[9, 0, 0, 0]);
+        idb.extend_from_slice(&[0, 0, 0, 0]);
+
+        let ticks: u64 = 5_000_000_123;
+        let mut epb = Vec::new();
+        epb.extend_from_slice(&0u32.to_le_bytes());
+        epb.extend_from_slice(&((ticks >> 32) as u32).to_le_bytes());
+        epb.extend_from_slice(&(ticks as u32).to_le_bytes());
+        epb.extend_from_slice(&5u32.to_le_bytes());
+        epb.extend_from_slice(&5u32.to_le_bytes());
+        epb.extend_from_slice(b"hello");
+
+        let mut file = Vec::new();
+        file.extend(pcapng_block(0x0a0d_0d0a, &shb));
+        file.extend(pcapng_block(1, &idb));
+        file.extend(pcapng_block(0x0bad_0001, b"unknown"));
+        file.extend(pcapng_block(6, &epb));
+
+        let mut reader = PcapFileReader::new(Cursor::new(file)).unwrap();
+        assert_eq!(
+            reader.next_record().unwrap(),
+            Some(PcapRecord {
+                timestamp: Duration::new(5, 123),
+                datalink: 1,
+                data: b"hello".to_vec(),
+            })
+        );
+        assert_eq!(reader.next_record().unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_unknown_magic() {
+        assert!(PcapFileReader::new(Cursor::new(vec![1, 2, 3, 4, 5])).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+use crate::packets::opcodes::Pkt;
+use crate::packets::stream_table::ConnectionId;
+use bytes::Bytes;
+use log::{info, warn};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+
+/// File magic for recorded `(ConnectionId, Pkt, Bytes)` sessions.
+const SESSION_MAGIC: [u8; 8] = *b"RLSESS\0\0";
+/// Bump when the record layout changes; readers reject any other version.
+pub const SESSION_FORMAT_VERSION: u16 = 2;
+const SESSION_ZSTD_LEVEL: i32 = 3;
+/// Safety cap on a single payload so a corrupt length can't trigger a huge allocation.
+const MAX_PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
+const RECORD_HEADER_LEN: usize = 16;
+
+/// One decoded packet as it left `process_packet`, plus the time it was captured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionRecord {
+    /// Unix timestamp in milliseconds.
+    pub captured_at_ms: u64,
+    pub connection_id: ConnectionId,
+    pub op: Pkt,
+    pub payload: Bytes,
+}
+
+/// Writes a session file.
+///
+/// Layout: an uncompressed header (magic, format version, reserved flags, session start in
+/// unix ms) followed by a single zstd stream of records. Each record is
+/// `offset_ms: u32 | connection_id: u32 | method_id: u32 | len: u32 | payload`, little-endian,
+/// where `offset_ms` is relative to the session start.
+pub struct SessionWriter<W: Write> {
+    encoder: Option<zstd::Encoder<'static, W>>,
+    started_at_ms: u64,
+    records: u64,
+}
+
+pub type SessionFileWriter = SessionWriter<BufWriter<File>>;
+
+impl SessionWriter<BufWriter<File>> {
+    pub fn create(path: &Path, started_at_ms: u64) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("create_dir_all {}: {}", parent.display(), e))?;
+        }
+        let file = File::create(path).map_err(|e| format!("create {}: {}", path.display(), e))?;
+        let writer = Self::new(BufWriter::new(file), started_at_ms)?;
+        info!(
+            target: "app::capture",
+            "session recording started path={} version={}",
+            path.display(),
+            SESSION_FORMAT_VERSION
+        );
+        Ok(writer)
+    }
+}
+
+impl<W: Write> SessionWriter<W> {
+    pub fn new(mut writer: W, started_at_ms: u64) -> Result<Self, String> {
+        let mut header = Vec::with_capacity(20);
+        header.extend_from_slice(&SESSION_MAGIC);
+        header.extend_from_slice(&SESSION_FORMAT_VERSION.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(&started_at_ms.to_le_bytes());
+        writer
+            .write_all(&header)
+            .map_err(|e| format!("write session header: {e}"))?;
+
+        let encoder = zstd::Encoder::new(writer, SESSION_ZSTD_LEVEL)
+            .map_err(|e| format!("init session zstd encoder: {e}"))?;
+        Ok(Self {
+            encoder: Some(encoder),
+            started_at_ms,
+            records: 0,
+        })
+    }
+
+    pub fn record(
+        &mut self,
+        connection_id: ConnectionId,
+        op: Pkt,
+        payload: &[u8],
+        captured_at_ms: u64,
+    ) -> Result<(), String> {
+        let encoder = self
+            .encoder
+            .as_mut()
+            .ok_or_else(|| "session writer already finished".to_string())?;
+        let offset_ms = captured_at_ms.saturating_sub(self.started_at_ms);
+        let len = u32::try_from(payload.len())
+            .map_err(|_| format!("payload too large ({} bytes)", payload.len()))?;
+
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        header[0..4].copy_from_slice(&u32::try_from(offset_ms).unwrap_or(u32::MAX).to_le_bytes());
+        header[4..8].copy_from_slice(&connection_id.to_le_bytes());
+        header[8..12].copy_from_slice(&op.method_id().to_le_bytes());
+        header[12..16].copy_from_slice(&len.to_le_bytes());
+        encoder
+            .write_all(&header)
+            .and_then(|_| encoder.write_all(payload))
+            .map_err(|e| format!("write session record: {e}"))?;
+        self.records += 1;
+        Ok(())
+    }
+
+    pub fn records(&self) -> u64 {
+        self.records
+    }
+
+    /// Flushes the zstd stream and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, String> {
+        self.finish_inner()
+    }
+
+    fn finish_inner(&mut self) -> Result<W, String> {
+        let encoder = self
+            .encoder
+            .take()
+            .ok_or_else(|| "session writer already finished".to_string())?;
+        let mut writer = encoder
+            .finish()
+            .map_err(|e| format!("finish session zstd stream: {e}"))?;
+        writer
+            .flush()
+            .map_err(|e| format!("flush session file: {e}"))?;
+        Ok(writer)
+    }
+}
+
+impl<W: Write> Drop for SessionWriter<W> {
+    fn drop(&mut self) {
+        if self.encoder.is_some()
+            && let Err(e) = self.finish_inner()
+        {
+            warn!(target: "app::capture", "session recording not finalized: {}", e);
+        }
+    }
+}
+
+impl<W: Write> fmt::Debug for SessionWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionWriter")
+            .field("started_at_ms", &self.started_at_ms)
+            .field("records", &self.records)
+            .field("finished", &self.encoder.is_none())
+            .finish()
+    }
+}
+
+/// Reads a session file written by [`SessionWriter`].
+pub struct SessionReader<R: Read> {
+    decoder: zstd::Decoder<'static, BufReader<R>>,
+    started_at_ms: u64,
+}
+
+impl SessionReader<File> {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("open {}: {}", path.display(), e))?;
+        let reader = Self::new(file)?;
+        info!(
+            target: "app::capture",
+            "session file opened path={} started_at_ms={}",
+            path.display(),
+            reader.started_at_ms
+        );
+        Ok(reader)
+    }
+}
+
+impl<R: Read> SessionReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, String> {
+        let mut header = [0u8; 20];
+        if !read_exact_or_eof(&mut reader, &mut header)? {
+            return Err("session file header is truncated".to_string());
+        }
+        if header[0..8] != SESSION_MAGIC {
+            return Err("not a session file (bad magic)".to_string());
+        }
+        let version = u16::from_le_bytes([header[8], header[9]]);
+        if version != SESSION_FORMAT_VERSION {
+            return Err(format!(
+                "unsupported session format version {version} (supported: {SESSION_FORMAT_VERSION})"
+            ));
+        }
+        let started_at_ms = u64::from_le_bytes(header[12..20].try_into().unwrap_or_default());
+
+        let decoder =
+            zstd::Decoder::new(reader).map_err(|e| format!("init session zstd decoder: {e}"))?;
+        Ok(Self {
+            decoder,
+            started_at_ms,
+        })
+    }
+
+    /// Returns the next record, or `None` at the end of the session.
+    ///
+    /// Records with opcodes this build doesn't know are skipped.
+    pub fn next_record(&mut self) -> Result<Option<SessionRecord>, String> {
+        loop {
+            let mut header = [0u8; RECORD_HEADER_LEN];
+            if !read_exact_or_eof(&mut self.decoder, &mut header)? {
+                return Ok(None);
+            }
+            let field = |at: usize| {
+                u32::from_le_bytes([header[at], header[at + 1], header[at + 2], header[at + 3]])
+            };
+            let offset_ms = field(0);
+            let connection_id = field(4);
+            let method_id = field(8);
+            let len = field(12) as usize;
+            if len > MAX_PAYLOAD_SIZE {
+                return Err(format!("session record too large ({len} bytes)"));
+            }
+
+            let mut payload = vec![0u8; len];
+            if !read_exact_or_eof(&mut self.decoder, &mut payload)? {
+                warn!(target: "app::capture", "session file ends with a truncated record; stopping");
+                return Ok(None);
+            }
+
+            let Some(op) = Pkt::from_method_id(method_id) else {
+                continue;
+            };
+            return Ok(Some(SessionRecord {
+                captured_at_ms: self.started_at_ms + u64::from(offset_ms),
+                connection_id,
+                op,
+                payload: Bytes::from(payload),
+            }));
+        }
+    }
+}
+
+/// Fills `buf` completely. Returns `Ok(false)` if the reader hits EOF before `buf` is full.
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<bool, String> {
+    let mut filled = 0usize;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => return Ok(false),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("read session file: {e}")),
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SessionReader, SessionRecord, SessionWriter};
+    use crate::packets::opcodes::Pkt;
+    use bytes::Bytes;
+    use std::io::Cursor;
+
+    #[test]
+    fn round_trips_records() {
+        let mut writer = SessionWriter::new(Vec::new(), 1_000).unwrap();
+        writer.record(1, Pkt::ServerChangeInfo, &[], 1_000).unwrap();
+        writer
+            .record(2, Pkt::SyncNearDeltaInfo, b"delta", 1_250)
+            .unwrap();
+        assert_eq!(writer.records(), 2);
+        let file = writer.finish().unwrap();
+
+        let mut reader = SessionReader::new(Cursor::new(file)).unwrap();
+        assert_eq!(
+            reader.next_record().unwrap(),
+            Some(SessionRecord {
+                captured_at_ms: 1_000,
+                connection_id: 1,
+                op: Pkt::ServerChangeInfo,
+                payload: Bytes::new(),
+            })
+        );
+        assert_eq!(
+            reader.next_record().unwrap(),
+            Some(SessionRecord {
+                captured_at_ms: 1_250,
+                connection_id: 2,
+                op: Pkt::SyncNearDeltaInfo,
+                payload: Bytes::from_static(b"delta"),
+            })
+        );
+        assert_eq!(reader.next_record().unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_other_format_versions() {
+        let file = SessionWriter::new(Vec::new(), 0).unwrap().finish().unwrap();
+        for version in [1u16, 0xff] {
+            let mut file = file.clone();
+            file[8..10].copy_from_slice(&version.to_le_bytes());
+            assert!(SessionReader::new(Cursor::new(file)).is_err());
+        }
+    }
+}
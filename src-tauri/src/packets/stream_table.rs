@@ -0,0 +1,207 @@
+use crate::packets::reassembler::Reassembler;
+use crate::packets::utils::{Server, TCPReassembler};
+use log::info;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Identifies one tracked game-server TCP stream for the lifetime of a capture.
+///
+/// IDs are handed out in detection order and never reused, so the live layer can tell a
+/// handover (new ID) apart from a reset of the same stream (same ID).
+pub type ConnectionId = u32;
+
+/// Streams that haven't delivered a packet for this long are dropped.
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Upper bound on concurrently tracked streams; the least recently seen one is evicted.
+const MAX_TRACKED_STREAMS: usize = 8;
+/// How often `evict_idle` actually walks the table.
+const STREAM_SWEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reassembly state for a single game-server stream.
+pub struct GameStream {
+    pub id: ConnectionId,
+    pub tcp_reassembler: TCPReassembler,
+    pub reassembler: Reassembler,
+    last_seen: Instant,
+}
+
+impl GameStream {
+    fn new(id: ConnectionId, next_seq: Option<u32>, now: Instant) -> Self {
+        let mut tcp_reassembler = TCPReassembler::new();
+        tcp_reassembler.reset(next_seq);
+        Self {
+            id,
+            tcp_reassembler,
+            reassembler: Reassembler::new(),
+            last_seen: now,
+        }
+    }
+
+    /// Drops any buffered bytes and restarts TCP reassembly at `next_seq`.
+    pub fn reset(&mut self, next_seq: Option<u32>) {
+        self.reassembler.take_remaining();
+        self.tcp_reassembler.reset(next_seq);
+    }
+}
+
+/// Game-server streams keyed by their server -> client 4-tuple.
+pub struct StreamTable {
+    streams: HashMap<Server, GameStream>,
+    next_id: ConnectionId,
+    last_sweep: Instant,
+}
+
+impl StreamTable {
+    pub fn new() -> Self {
+        Self {
+            streams: HashMap::new(),
+            next_id: 1,
+            last_sweep: Instant::now(),
+        }
+    }
+
+    /// Looks up a tracked stream and marks it as seen at `now`.
+    pub fn touch(&mut self, server: &Server, now: Instant) -> Option<&mut GameStream> {
+        let stream = self.streams.get_mut(server)?;
+        stream.last_seen = now;
+        Some(stream)
+    }
+
+    /// Starts tracking `server`, expecting `next_seq` as the next sequence number.
+    ///
+    /// An already tracked stream keeps its ID and is only reset. Returns the stream's ID.
+    pub fn insert(&mut self, server: Server, next_seq: Option<u32>, now: Instant) -> ConnectionId {
+        if let Some(stream) = self.streams.get_mut(&server) {
+            stream.reset(next_seq);
+            stream.last_seen = now;
+            return stream.id;
+        }
+
+        if self.streams.len() >= MAX_TRACKED_STREAMS
+            && let Some(oldest) = self
+                .streams
+                .iter()
+                .min_by_key(|(_, stream)| stream.last_seen)
+                .map(|(server, _)| *server)
+            && let Some(evicted) = self.streams.remove(&oldest)
+        {
+            info!(
+                target: "app::capture",
+                "stream_evicted reason=capacity connection_id={} server={}",
+                evicted.id,
+                oldest
+            );
+        }
+
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1).max(1);
+        self.streams
+            .insert(server, GameStream::new(id, next_seq, now));
+        info!(
+            target: "app::capture",
+            "stream_tracked connection_id={} server={} tracked={}",
+            id,
+            server,
+            self.streams.len()
+        );
+        id
+    }
+
+    /// Drops streams idle for longer than the timeout. Cheap to call per packet.
+    pub fn evict_idle(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_sweep) < STREAM_SWEEP_INTERVAL {
+            return;
+        }
+        self.last_sweep = now;
+        self.streams.retain(|server, stream| {
+            let idle = now.saturating_duration_since(stream.last_seen);
+            if idle < STREAM_IDLE_TIMEOUT {
+                return true;
+            }
+            info!(
+                target: "app::capture",
+                "stream_evicted reason=idle connection_id={} server={} idle_secs={}",
+                stream.id,
+                server,
+                idle.as_secs()
+            );
+            false
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_TRACKED_STREAMS, STREAM_IDLE_TIMEOUT, STREAM_SWEEP_INTERVAL, StreamTable};
+    use crate::packets::utils::Server;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::time::{Duration, Instant};
+
+    fn server(port: u16) -> Server {
+        Server::new(
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            port,
+            IpAddr::V4(Ipv4Addr::new(192, 168, 0, 2)),
+            50_000,
+        )
+    }
+
+    #[test]
+    fn tracks_streams_independently() {
+        let now = Instant::now();
+        let mut table = StreamTable::new();
+        let a = table.insert(server(1), Some(100), now);
+        let b = table.insert(server(2), Some(200), now);
+        assert_ne!(a, b);
+        assert_eq!(table.touch(&server(1), now).map(|s| s.id), Some(a));
+        assert_eq!(table.touch(&server(2), now).map(|s| s.id), Some(b));
+        assert!(table.touch(&server(3), now).is_none());
+
+        // The same ports over IPv6 are a different stream.
+        let v6 = Server::new(
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+            1,
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2)),
+            50_000,
+        );
+        assert!(table.touch(&v6, now).is_none());
+        assert_ne!(table.insert(v6, None, now), a);
+
+        // Re-detecting a tracked stream keeps its ID.
+        assert_eq!(table.insert(server(1), Some(500), now), a);
+        assert_eq!(table.streams.len(), 3);
+    }
+
+    #[test]
+    fn evicts_idle_streams() {
+        let start = Instant::now();
+        let mut table = StreamTable::new();
+        table.insert(server(1), None, start);
+        table.insert(server(2), None, start);
+
+        let later = start + STREAM_IDLE_TIMEOUT + STREAM_SWEEP_INTERVAL;
+        table.touch(&server(2), later - Duration::from_secs(1));
+        table.evict_idle(later);
+        assert!(table.touch(&server(1), later).is_none());
+        assert!(table.touch(&server(2), later).is_some());
+    }
+
+    #[test]
+    fn evicts_least_recently_seen_when_full() {
+        let start = Instant::now();
+        let mut table = StreamTable::new();
+        for port in 0..MAX_TRACKED_STREAMS as u16 {
+            table.insert(
+                server(port),
+                None,
+                start + Duration::from_millis(u64::from(port)),
+            );
+        }
+        table.touch(&server(0), start + Duration::from_secs(1));
+
+        table.insert(server(1_000), None, start + Duration::from_secs(2));
+        assert_eq!(table.streams.len(), MAX_TRACKED_STREAMS);
+        assert!(table.touch(&server(0), start).is_some());
+        assert!(table.touch(&server(1), start).is_none());
+    }
+}
@@ -1,17 +1,18 @@
 use std::collections::BTreeMap;
 use std::fmt;
+use std::net::{IpAddr, SocketAddr};
 use std::time::{Duration, Instant};
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Server {
-    src_addr: [u8; 4],
+    src_addr: IpAddr,
     src_port: u16,
-    dst_addr: [u8; 4],
+    dst_addr: IpAddr,
     dst_port: u16,
 }
 
 impl Server {
-    pub fn new(src_addr: [u8; 4], src_port: u16, dst_addr: [u8; 4], dst_port: u16) -> Self {
+    pub fn new(src_addr: IpAddr, src_port: u16, dst_addr: IpAddr, dst_port: u16) -> Self {
         Self {
             src_addr,
             src_port,
@@ -23,21 +24,16 @@ impl Server {
 
 impl fmt::Display for Server {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // SocketAddr brackets IPv6 addresses so the port stays unambiguous.
         write!(
             f,
-            "{}:{} -> {}:{}",
-            ip_to_str(&self.src_addr),
-            self.src_port,
-            ip_to_str(&self.dst_addr),
-            self.dst_port
+            "{} -> {}",
+            SocketAddr::new(self.src_addr, self.src_port),
+            SocketAddr::new(self.dst_addr, self.dst_port)
         )
     }
 }
 
-fn ip_to_str(ip: &[u8; 4]) -> String {
-    format!("{}.{}.{}.{}", ip[0], ip[1], ip[2], ip[3])
-}
-
 #[inline]
 pub fn tcp_sequence_before(a: u32, b: u32) -> bool {
     (a.wrapping_sub(b) as i32) < 0
@@ -261,9 +257,29 @@ impl TCPReassembler {
 
 #[cfg(test)]
 mod tests {
-    use super::{GAP_SKIP_BUFFERED_BYTES, GapSkipReason, TCPReassembler, TcpInsertResult};
+    use super::{GAP_SKIP_BUFFERED_BYTES, GapSkipReason, Server, TCPReassembler, TcpInsertResult};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
     use std::time::Duration;
 
+    #[test]
+    fn formats_server_for_both_address_families() {
+        let v4 = Server::new(
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            5003,
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)),
+            50000,
+        );
+        assert_eq!(v4.to_string(), "10.0.0.1:5003 -> 192.168.1.2:50000");
+
+        let v6 = Server::new(
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+            5003,
+            IpAddr::V6(Ipv6Addr::LOCALHOST),
+            50000,
+        );
+        assert_eq!(v6.to_string(), "[2001:db8::1]:5003 -> [::1]:50000");
+    }
+
     #[test]
     fn reassembles_in_order() {
         let mut reassembler = TCPReassembler::new();
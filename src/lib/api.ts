@@ -13,15 +13,39 @@ import type {
   HistoryEntityData as BindingRawEntityData,
   DamageSnapshot as BindingDamageSnapshot,
   DeathRecord as BindingDeathRecord,
+  PacketDropStats as BindingPacketDropStats,
+  CaptureHealth as BindingCaptureHealth,
+  CaptureStatus as BindingCaptureStatus,
+  EncounterPhase as BindingEncounterPhase,
+  BuffUptimeEntry as BindingBuffUptimeEntry,
+  TrainingDummyOptions,
+  TrainingDummyRun,
+  TrainingDummyRunFilter,
+  TrainingDummyRunStats,
 } from "./bindings";
 
 // Type definitions for event payloads
+export type TimeToKill = {
+  estimateMs: number;
+  lowMs: number;
+  highMs: number | null;
+  hpLossPerSec: number;
+};
+
+export type EnrageTimer = {
+  enrageMs: number;
+  remainingMs: number;
+  onPace: boolean | null;
+};
+
 export type BossHealth = {
   uid: number;
   monsterId: number | null;
   currentHp: number | null;
   maxHp: number | null;
   isDead: boolean;
+  timeToKill: TimeToKill | null;
+  enrage: EnrageTimer | null;
 };
 
 export type HeaderInfo = {
@@ -203,6 +227,18 @@ export type EncounterUpdatePayload = {
 export type RawCombatStats = BindingRawCombatStats;
 export type RawSkillStats = BindingRawSkillStats;
 export type RawEntityData = BindingRawEntityData;
+export type EncounterPhase = BindingEncounterPhase;
+
+export type RollingWindowStats = {
+  windowSecs: number;
+  dps: number;
+  hps: number;
+  dtps: number;
+};
+
+export type LiveEntityData = RawEntityData & {
+  rolling: RollingWindowStats[];
+};
 
 export type LiveDataPayload = {
   elapsedMs: number;
@@ -217,7 +253,8 @@ export type LiveDataPayload = {
   dungeonDifficulty: number | null;
   isPaused: boolean;
   bosses: BossHealth[];
-  entities: RawEntityData[];
+  entities: LiveEntityData[];
+  phases: EncounterPhase[];
 };
 
 export type SceneChangePayload = {
@@ -232,6 +269,17 @@ export type DeathReplayPayload = {
   records: DeathRecord[];
 };
 
+export type BuffUptimeEntry = BindingBuffUptimeEntry;
+
+export type BuffUptimeUpdatePayload = {
+  uid: number;
+  entries: BuffUptimeEntry[];
+};
+
+export type PacketDropStats = BindingPacketDropStats;
+export type CaptureHealth = BindingCaptureHealth;
+export type CaptureStatus = BindingCaptureStatus;
+
 // Event listener functions
 export const onEncounterUpdate = (
   handler: (event: Event<EncounterUpdatePayload>) => void,
@@ -306,6 +354,27 @@ export const onDeathReplay = (
   handler: (event: Event<DeathReplayPayload>) => void,
 ): Promise<UnlistenFn> => listen<DeathReplayPayload>("death-replay", handler);
 
+/** Uptime of the local player's monitored buffs in the running encounter. */
+export const onBuffUptimeUpdate = (
+  handler: (event: Event<BuffUptimeUpdatePayload>) => void,
+): Promise<UnlistenFn> =>
+  listen<BuffUptimeUpdatePayload>("buff-uptime-update", handler);
+
+/** Fired when the capture queue drops or coalesces packets; live numbers may be incomplete. */
+export const onPacketDrops = (
+  handler: (event: Event<PacketDropStats>) => void,
+): Promise<UnlistenFn> => listen<PacketDropStats>("packet-drops", handler);
+
+/** Periodic capture quality counters (traffic, TCP gaps/resets, decode failures). */
+export const onCaptureHealth = (
+  handler: (event: Event<CaptureHealth>) => void,
+): Promise<UnlistenFn> => listen<CaptureHealth>("capture-health", handler);
+
+/** Fired when the capture thread starts, recovers, degrades or gives up. */
+export const onCaptureStatus = (
+  handler: (event: Event<CaptureStatus>) => void,
+): Promise<UnlistenFn> => listen<CaptureStatus>("capture-status", handler);
+
 // Command wrappers (still using generated bindings)
 
 export const resetEncounter = (): Promise<Result<null, string>> =>
@@ -314,9 +383,19 @@ export const togglePauseEncounter = (): Promise<Result<null, string>> =>
   commands.togglePauseEncounter();
 export const startTrainingDummy = (
   monsterId: number,
-): Promise<Result<null, string>> => commands.startTrainingDummy(monsterId);
+  options: TrainingDummyOptions | null = null,
+): Promise<Result<null, string>> =>
+  commands.startTrainingDummy(monsterId, options);
 export const stopTrainingDummy = (): Promise<Result<null, string>> =>
   commands.stopTrainingDummy();
+export const getTrainingDummyRuns = (
+  filter: TrainingDummyRunFilter | null = null,
+): Promise<Result<TrainingDummyRun[], string>> =>
+  commands.getTrainingDummyRuns(filter);
+export const getTrainingDummyRunStats = (
+  filter: TrainingDummyRunFilter | null = null,
+): Promise<Result<TrainingDummyRunStats, string>> =>
+  commands.getTrainingDummyRunStats(filter);
 export const enableBlur = (): Promise<void> => commands.enableBlur();
 export const disableBlur = (): Promise<void> => commands.disableBlur();
 export const getEncounterEntitiesRaw = (
@@ -63,9 +63,9 @@ async togglePauseEncounter() : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async startTrainingDummy(monsterId: number) : Promise<Result<null, string>> {
+async startTrainingDummy(monsterId: number, options: TrainingDummyOptions | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_training_dummy", { monsterId }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_training_dummy", { monsterId, options }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -87,6 +87,127 @@ async saveAndApplyMonitorRuntimeSnapshot(snapshot: MonitorRuntimeSnapshot) : Pro
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Starts teeing the decoded packet stream into a session file.
+ * 
+ * If `path` is omitted the file is created under `<app log dir>/sessions`.
+ * 
+ * # Returns
+ * 
+ * * `Result<String, String>` - The path of the session file being written.
+ */
+async startSessionRecording(path: string | null) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_session_recording", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stops the active session recording, if any, and finalizes the file.
+ */
+async stopSessionRecording() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_session_recording") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns how many packets the capture queue has dropped or coalesced under load.
+ * 
+ * # Returns
+ * 
+ * * `Result<PacketDropStats, String>` - Per-opcode loss counts since the app started.
+ */
+async getPacketDropStats() : Promise<Result<PacketDropStats, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_packet_drop_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns capture quality counters: traffic seen, TCP gaps and resets, decompression and
+ * decode failures, and packet queue losses.
+ * 
+ * # Returns
+ * 
+ * * `Result<CaptureHealth, String>` - Counters accumulated since the app started.
+ */
+async getCaptureHealth() : Promise<Result<CaptureHealth, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_capture_health") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns the capture thread status: starting, running, degraded, failed or finished.
+ * 
+ * # Returns
+ * 
+ * * `Result<CaptureStatus, String>` - The current status.
+ */
+async getCaptureStatus() : Promise<Result<CaptureStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_capture_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Closes and reopens the capture source, skipping any pending restart backoff.
+ * 
+ * # Returns
+ * 
+ * * `Result<(), String>` - An error if no capture is running.
+ */
+async restartCapture() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restart_capture") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns where each meter-data table was loaded from and any files that failed to load.
+ * 
+ * # Returns
+ * 
+ * * `Result<Vec<DataTableStatus>, String>` - One status per table.
+ */
+async getMeterDataStatus() : Promise<Result<DataTableStatus[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meter_data_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Reloads the meter-data tables from the user override directory and the bundled files, so
+ * updated game data applies without a restart. Tables that fail to load keep their previous
+ * contents.
+ * 
+ * # Returns
+ * 
+ * * `Result<Vec<DataTableStatus>, String>` - One status per table after the reload.
+ */
+async reloadMeterData() : Promise<Result<DataTableStatus[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reload_meter_data") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Gets a list of recent encounters.
  * 
@@ -209,6 +330,121 @@ async getEncounterEntitiesRaw(encounterId: number) : Promise<Result<HistoryEntit
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Gets debuff uptime and applications on the bosses of a historical encounter.
+ * 
+ * # Arguments
+ * 
+ * * `encounter_id` - The ID of the encounter.
+ * 
+ * # Returns
+ * 
+ * * `Result<Vec<lc::BossDebuffSummary>, String>` - One entry per boss with monitored debuffs.
+ */
+async getEncounterBossDebuffs(encounterId: number) : Promise<Result<BossDebuffSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_encounter_boss_debuffs", { encounterId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the top-threat holder changes on the bosses of a historical encounter.
+ * 
+ * # Arguments
+ * 
+ * * `encounter_id` - The ID of the encounter.
+ * 
+ * # Returns
+ * 
+ * * `Result<Vec<lc::BossAggroSummary>, String>` - One entry per boss with a recorded holder.
+ */
+async getEncounterBossAggro(encounterId: number) : Promise<Result<BossAggroSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_encounter_boss_aggro", { encounterId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the local player's skill rotation in a historical encounter.
+ * 
+ * # Arguments
+ * 
+ * * `encounter_id` - The ID of the encounter.
+ * 
+ * # Returns
+ * 
+ * * `Result<Option<lc::EncounterRotation>, String>` - The rotation, if any casts were recorded.
+ */
+async getEncounterRotation(encounterId: number) : Promise<Result<EncounterRotation | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_encounter_rotation", { encounterId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets per-second damage, healing and damage taken for entities of a historical encounter.
+ * 
+ * # Arguments
+ * 
+ * * `encounter_id` - The ID of the encounter.
+ * * `entity_uids` - Entities to return; empty returns every player with combat.
+ * 
+ * # Returns
+ * 
+ * * `Result<lc::EncounterTimeSeries, String>` - Series aligned to the encounter start.
+ */
+async getEncounterTimeSeries(encounterId: number, entityUids: number[]) : Promise<Result<EncounterTimeSeries, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_encounter_time_series", { encounterId, entityUids }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets saved training dummy runs.
+ * 
+ * # Arguments
+ * 
+ * * `filter` - Restricts the runs by monster, class spec, segment length and start date.
+ * 
+ * # Returns
+ * 
+ * * `Result<Vec<TrainingDummyRun>, String>` - The matching runs, oldest first.
+ */
+async getTrainingDummyRuns(filter: TrainingDummyRunFilter | null) : Promise<Result<TrainingDummyRun[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_training_dummy_runs", { filter }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets DPS statistics across saved training dummy runs.
+ * 
+ * # Arguments
+ * 
+ * * `filter` - Restricts the runs by monster, class spec, segment length and start date.
+ * 
+ * # Returns
+ * 
+ * * `Result<TrainingDummyRunStats, String>` - Mean, spread, best run and trend of the runs.
+ */
+async getTrainingDummyRunStats(filter: TrainingDummyRunFilter | null) : Promise<Result<TrainingDummyRunStats, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_training_dummy_run_stats", { filter }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Deletes an encounter by its ID.
  * 
@@ -377,8 +613,53 @@ async optimizeLatestModules(targetAttributes: number[], excludeAttributes: numbe
 
 /** user-defined types **/
 
+/**
+ * The top-threat holder of a boss changed.
+ */
+export type AggroChange = { timestampMs: number; 
+/**
+ * New holder; `None` when the hate list emptied.
+ */
+uid: number | null }
+/**
+ * How long one player was at the top of a boss's hate list.
+ */
+export type AggroHold = { uid: number; name: string; heldMs: number; 
+/**
+ * Times the player took aggro, including holding it when the window starts.
+ */
+timesGained: number }
+/**
+ * A stretch of time the player was alive.
+ */
+export type AliveInterval = { startMs: number; endMs: number }
 export type AltFreezeConfig = { conditionBuffId: number; freezeDurationMs: number }
 export type AttrModifier = { attrId: number; basisPointsPerUnit?: number; maxReductionBasisPoints: number }
+/**
+ * Aggro history of one boss in a saved encounter.
+ */
+export type BossAggroSummary = { uid: number; monsterId: number | null; name: string; 
+/**
+ * Top-threat holder changes in time order. The first one may be from before the encounter
+ * started.
+ */
+changes: AggroChange[]; 
+/**
+ * Time each player held aggro during the encounter, longest first.
+ */
+holds: AggroHold[] }
+/**
+ * Debuff coverage on one boss of a saved encounter.
+ */
+export type BossDebuffSummary = { uid: number; monsterId: number | null; name: string; 
+/**
+ * Uptime of each monitored debuff over the encounter, longest first.
+ */
+debuffUptime: BuffUptimeEntry[]; 
+/**
+ * Debuff applications by caster.
+ */
+applications: BuffApplication[] }
 /**
  * The result of a query for boss monster template IDs.
  */
@@ -403,6 +684,73 @@ maxHp: number | null;
  * Whether the boss was defeated.
  */
 isDefeated: boolean }
+/**
+ * How often one caster applied or refreshed a buff during an encounter.
+ */
+export type BuffApplication = { baseId: number; 
+/**
+ * Entity that applied the buff, when the server reported it.
+ */
+casterUid: number | null; 
+/**
+ * Name of the caster if it was a player in the encounter.
+ */
+casterName: string | null; 
+/**
+ * Skill or other config the buff came from, when known.
+ */
+sourceConfigId: number | null; count: number }
+/**
+ * Cumulative uptime of one buff over an encounter.
+ */
+export type BuffUptimeEntry = { baseId: number; 
+/**
+ * Time with at least one instance of the buff up, up to the last combat packet.
+ */
+uptimeMs: number; 
+/**
+ * Average stack count while the buff was up.
+ */
+averageLayer: number; 
+/**
+ * Uptime relative to the encounter duration, 0-100.
+ */
+uptimePercent: number; 
+/**
+ * Share of active combat time the buff was up for, 0-100. Idle gaps don't count.
+ */
+activeUptimePercent: number }
+/**
+ * Capture quality counters, cumulative for this run.
+ * 
+ * Lets the UI tell a lossy capture apart from a meter bug.
+ */
+export type CaptureHealth = { packetsSeen: number; bytesCaptured: number; gapsSkipped: GapSkipStats; sequenceRegressionResets: number; synResets: number; 
+/**
+ * Streams reset by a FIN or RST.
+ */
+finResets: number; zstdFailures: number; serviceUuidMismatches: number; 
+/**
+ * Protobuf decode failures per opcode.
+ */
+decodeFailures: OpcodeCount[]; packetQueue: PacketDropStats }
+export type CaptureState = "starting" | "running" | "degraded" | "failed" | "finished"
+/**
+ * Capture thread status as shown in the UI.
+ */
+export type CaptureStatus = { state: CaptureState; 
+/**
+ * Why the capture is degraded or failed.
+ */
+reason: string | null; 
+/**
+ * Capture device in use, for Npcap/libpcap captures.
+ */
+device: string | null; 
+/**
+ * Restarts since the app started, requested or automatic.
+ */
+restarts: number }
 export type CounterAction = "reset" | "freeze" | "resetAndFreeze" | "resetAndFreezeKeepCounting" | "resetAndStartCount" | "startCount" | "noOp"
 export type CounterRule = { ruleId: number; sources: CounterSource[]; effectSlots: EffectSlotConfig[] }
 export type CounterSource = { damageBySkillKey: { skillKeys: number[]; increment: number; hitsRequired?: number | null } } | { damageBySkillKeyOnce: { skillKeys: number[]; increment: number } } | { damageBySkillKeySelfTarget: { skillKeys: number[]; increment: number; hitsRequired?: number | null } } | { anyDamage: { increment: number; hitsRequired?: number | null } } | { damageTaken: { skillKeys?: number[] | null; increment: number; hitsRequired?: number | null } } | { fightResourceSpent: { resourceId: number; unitsRequired: number; increment: number } } | { buffDurationTick: { buffId: number; tickIntervalMs: number; increment: number; attrCondition?: TickAttrCondition | null } } | { skillCast: { skillBaseIds: number[]; increment: number } } | { skillDurationTick: { skillBaseId: number; tickIntervalMs: number; increment: number } } | { skillCastComplete: { skillBaseIds: number[]; increment: number } } | { movementDistance: { buffId: number; attrId: number; metersRequired: number; increment: number } }
@@ -430,6 +778,22 @@ skillKey: number;
  * Raw damage value.
  */
 value: number }
+/**
+ * Where a table was loaded from.
+ */
+export type DataTableSource = "userOverride" | "bundled" | "builtIn" | "previous" | "missing"
+/**
+ * Load result of one meter-data table.
+ */
+export type DataTableStatus = { fileName: string; source: DataTableSource; 
+/**
+ * File the table was read from, if any.
+ */
+path: string | null; entries: number; 
+/**
+ * Files that were skipped and why, e.g. an invalid user override.
+ */
+errors: string[] }
 /**
  * A death replay record, capturing the damage taken within the window leading up to a death.
  */
@@ -472,6 +836,34 @@ dateToMs: number | null;
  * Whether to filter by favorite encounters.
  */
 isFavorite: boolean | null }
+/**
+ * Start of one encounter phase. A phase lasts until the next one starts.
+ */
+export type EncounterPhase = { startMs: number; reason: PhaseStartReason; 
+/**
+ * Boss that crossed the HP threshold, for `BossHp` phases.
+ */
+bossUid: number | null; bossMonsterId: number | null; 
+/**
+ * Threshold the boss dropped below, in percent.
+ */
+hpPercent: number | null; 
+/**
+ * New dungeon target, for `Objective` phases.
+ */
+targetId: number | null }
+/**
+ * Skill rotation of the local player in a saved encounter.
+ */
+export type EncounterRotation = { uid: number; name: string; 
+/**
+ * Unix time the encounter started; casts before it belong to the opener.
+ */
+startMs: number; 
+/**
+ * Whether the encounter had more casts than were kept.
+ */
+truncated: boolean; entries: RotationEntry[] }
 /**
  * A summary of an encounter.
  */
@@ -531,16 +923,90 @@ remoteEncounterId: number | null;
 /**
  * Whether the encounter is favorited.
  */
-isFavorite: boolean }
+isFavorite: boolean; 
+/**
+ * Phase boundaries of the encounter. Only filled in by `get_encounter_by_id`.
+ */
+phases: EncounterPhase[] }
+/**
+ * Time series of a historical encounter. Every entity has the same number of buckets.
+ */
+export type EncounterTimeSeries = { 
+/**
+ * Width of one bucket in milliseconds.
+ */
+bucketMs: number; 
+/**
+ * Unix time of the first bucket; bucket `i` starts at `start_ms + i * bucket_ms`.
+ */
+startMs: number; entities: EntityTimeSeries[] }
+/**
+ * Damage, healing and damage taken of one entity per time bucket.
+ */
+export type EntityTimeSeries = { uid: number; damage: number[]; healing: number[]; taken: number[] }
+/**
+ * TCP gaps given up on, by `GapSkipReason`.
+ */
+export type GapSkipStats = { bufferedBytes: number; waitTime: number; hardLimit: number }
 export type GpuSupport = { cuda_available: boolean; opencl_available: boolean }
-export type HistoryEntityData = { uid: number; name: string; classId: number; classSpec: number; className: string; classSpecName: string; abilityScore: number; seasonStrength: number; damage: RawCombatStats; damageBossOnly: RawCombatStats; healing: RawCombatStats; taken: RawCombatStats; dmgSkills: Partial<{ [key in number]: RawSkillStats }>; healSkills: Partial<{ [key in number]: RawSkillStats }>; takenSkills: Partial<{ [key in number]: RawSkillStats }>; dmgPerTarget: PerTargetStats[]; healPerTarget: PerTargetStats[]; deaths: DeathRecord[] }
-export type LiveRuntimeSnapshot = { eventUpdateRateMs: number }
+export type HistoryEntityData = { uid: number; name: string; classId: number; classSpec: number; className: string; classSpecName: string; abilityScore: number; seasonStrength: number; damage: RawCombatStats; damageBossOnly: RawCombatStats; healing: RawCombatStats; taken: RawCombatStats; dmgSkills: Partial<{ [key in number]: RawSkillStats }>; healSkills: Partial<{ [key in number]: RawSkillStats }>; takenSkills: Partial<{ [key in number]: RawSkillStats }>; dmgPerTarget: PerTargetStats[]; healPerTarget: PerTargetStats[]; deaths: DeathRecord[]; 
+/**
+ * Deaths and time spent dead within the encounter.
+ */
+life: LifeSummary; 
+/**
+ * Stats per encounter phase, indexed like `EncounterSummaryDto::phases`.
+ */
+phases: RawPhaseStats[]; 
+/**
+ * Uptime of the monitored buffs; only recorded for the local player.
+ */
+buffUptime: BuffUptimeEntry[]; 
+/**
+ * Part of `damage` dealt through summons.
+ */
+summonDamage: RawCombatStats }
+/**
+ * Life timeline reduced to an encounter window.
+ */
+export type LifeSummary = { deathCount: number; 
+/**
+ * Time spent dead inside the window.
+ */
+deadMs: number; aliveIntervals: AliveInterval[] }
+export type LiveRuntimeSnapshot = { eventUpdateRateMs: number; 
+/**
+ * Boss HP percentages that start a new encounter phase.
+ */
+phaseHpThresholds: number[]; 
+/**
+ * Lengths of the rolling DPS windows in the live payload, in seconds.
+ */
+rollingWindowsSecs: number[]; 
+/**
+ * UI locale, e.g. `zh-CN`; picks the language of game names in logs.
+ */
+locale: string }
 export type ModuleInfo = { name: string; config_id: number; uuid: number; quality: number; parts: ModulePart[] }
 export type ModulePart = { id: number; name: string; value: number }
 export type ModuleSolution = { modules: ModuleInfo[]; score: number; attr_breakdown: Partial<{ [key in string]: number }> }
 export type MonitorRuntimeSnapshot = { live: LiveRuntimeSnapshot; skill: SkillRuntimeSnapshot; monster: MonsterRuntimeSnapshot }
 export type MonsterRuntimeSnapshot = { enabled: boolean; globalIds: number[]; selfAppliedIds: number[] }
+export type OpcodeCount = { opcode: string; count: number }
+export type PacketDropEntry = { 
+/**
+ * `Pkt` variant name, e.g. `SyncNearEntities`.
+ */
+opcode: string; dropped: number; coalesced: number }
+/**
+ * Packets the capture queue discarded under backpressure, cumulative for this run.
+ */
+export type PacketDropStats = { totalDropped: number; totalCoalesced: number; byOpcode: PacketDropEntry[] }
 export type PerTargetStats = { targetUid: number; targetMonsterId: number | null; totalValue: number; damage: RawCombatStats; skills: Partial<{ [key in number]: RawSkillStats }> }
+/**
+ * Why a phase started.
+ */
+export type PhaseStartReason = "encounterStart" | "bossHp" | "objective"
 /**
  * The result of a query for player names.
  */
@@ -562,7 +1028,27 @@ name: string;
  */
 classId: number }
 export type RawCombatStats = { total: number; effectiveTotal: number; hits: number; critHits: number; critTotal: number; luckyHits: number; luckyTotal: number }
-export type RawSkillStats = { totalValue: number; effectiveTotalValue: number; hits: number; critHits: number; critTotalValue: number; luckyHits: number; luckyTotalValue: number; property: number | null; damageMode: number | null }
+/**
+ * Damage, healing and damage taken of one entity within one encounter phase.
+ */
+export type RawPhaseStats = { damage: RawCombatStats; healing: RawCombatStats; taken: RawCombatStats }
+export type RawSkillStats = { totalValue: number; effectiveTotalValue: number; hits: number; critHits: number; critTotalValue: number; luckyHits: number; luckyTotalValue: number; property: number | null; damageMode: number | null; 
+/**
+ * Part of the damage dealt through summons, by summon kind.
+ */
+summons: RawSummonStats[] }
+/**
+ * Damage a skill dealt through one kind of summon.
+ */
+export type RawSummonStats = { 
+/**
+ * Entity type of the summon, as an `EEntityType` value.
+ */
+entityType: number; monsterId: number | null; 
+/**
+ * UID of the summon that hit last.
+ */
+lastUid: number; hits: number; totalValue: number }
 /**
  * The result of a query for recent encounters.
  */
@@ -575,6 +1061,22 @@ rows: EncounterSummaryDto[];
  * The total number of encounters.
  */
 totalCount: number }
+/**
+ * One skill cast of the local player.
+ */
+export type RotationEntry = { skillId: number; castMs: number; 
+/**
+ * First hit of the local player after the cast and before the next one.
+ */
+firstHitMs: number | null; 
+/**
+ * Skill key of that hit, as used in the skill breakdowns.
+ */
+firstHitSkillKey: number | null; 
+/**
+ * Monitored buffs that were up when the skill was cast.
+ */
+activeBuffIds: number[] }
 /**
  * The result of a query for scene IDs.
  */
@@ -585,6 +1087,55 @@ export type SceneIdsResult = {
 ids: number[] }
 export type SkillRuntimeSnapshot = { enabled: boolean; monitoredSkillIds: number[]; monitoredBuffIds: number[]; monitorAllBuff: boolean; monitoredPanelAttrIds: number[]; buffCounterRules: CounterRule[] }
 export type TickAttrCondition = { attrId: number; requiredValue: number }
+/**
+ * How a training dummy run is split into segments.
+ */
+export type TrainingDummyOptions = { 
+/**
+ * Wall-clock length of a segment, counted from the first hit.
+ */
+segmentSecs: number; 
+/**
+ * Ends the segment early once this much active damage time was dealt.
+ */
+stopAfterDamageSecs: number | null }
+/**
+ * A saved training dummy segment.
+ */
+export type TrainingDummyRun = { id: number; 
+/**
+ * `None` once the encounter was deleted.
+ */
+encounterId: number | null; monsterId: number; segmentSecs: number; stopAfterDamageSecs: number | null; startedAtMs: number; durationMs: number; activeMs: number; totalDamage: number; 
+/**
+ * Damage per second over the segment's duration.
+ */
+dps: number; playerUid: number; classId: number; classSpec: number; abilityScore: number }
+/**
+ * Narrows the runs used for training dummy statistics; unset fields match everything.
+ */
+export type TrainingDummyRunFilter = { monsterId: number | null; classSpec: number | null; segmentSecs: number | null; 
+/**
+ * Earliest run start, inclusive.
+ */
+fromMs: number | null; 
+/**
+ * Latest run start, inclusive.
+ */
+toMs: number | null }
+/**
+ * DPS statistics across training dummy runs.
+ */
+export type TrainingDummyRunStats = { count: number; meanDps: number; 
+/**
+ * Sample standard deviation; 0 with fewer than two runs.
+ */
+stdDevDps: number; best: TrainingDummyRun | null; 
+/**
+ * DPS change per run from a least-squares fit over the runs in start order; `None` with
+ * fewer than two runs.
+ */
+trendDpsPerRun: number | null }
 
 /** tauri-specta globals **/
 
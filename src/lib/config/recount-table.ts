@@ -1,4 +1,5 @@
 import { getLocale, t, type AppLocale } from "$lib/i18n/index.svelte";
+import { resolveMonsterName } from "$lib/config/game-names";
 import {
   getGameData,
   getGameDataFallbackChain,
@@ -15,6 +16,7 @@ export type RawSkillStatsLike = {
   luckyTotalValue: number;
   property?: number | null;
   damageMode?: number | null;
+  summons?: { monsterId: number | null; lastUid: number; totalValue: number }[];
 };
 
 export type SkillDisplayRow = {
@@ -34,6 +36,8 @@ export type SkillDisplayRow = {
   hitsPerMinute: number;
   property: number | null;
   damageMode: number | null;
+  /** Summons that dealt part of the damage, most damage first. */
+  viaSummon?: string;
   raw: RawSkillStatsLike;
 };
 
@@ -187,10 +191,23 @@ export function buildSkillDisplayRow(
     hitsPerMinute: perMinute(hits, elapsedSecs),
     property: stats.property ?? null,
     damageMode: stats.damageMode ?? null,
+    viaSummon: summonNames(stats, locale),
     raw: stats,
   };
 }
 
+function summonNames(stats: RawSkillStatsLike, locale: AppLocale): string | undefined {
+  if (!stats.summons?.length) return undefined;
+  return [...stats.summons]
+    .sort((a, b) => Number(b.totalValue) - Number(a.totalValue))
+    .map((summon) =>
+      summon.monsterId !== null
+        ? resolveMonsterName(summon.monsterId, locale)
+        : `#${summon.lastUid}`,
+    )
+    .join(", ");
+}
+
 export function groupSkillsByRecount(
   skills: Partial<Record<number, RawSkillStatsLike>>,
   elapsedSecs: number,
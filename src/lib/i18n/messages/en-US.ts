@@ -308,14 +308,32 @@ export const enUSMessages = {
   "live.tabs.heal": "HEAL",
   "live.tabs.tanked": "TANKED",
   "live.tabs.death": "DEATH",
+  "live.tabs.buffs": "BUFFS",
   "live.table.player": "Player",
   "live.table.skill": "Skill",
+  "live.table.viaSummon": "via summon {name}",
+  "live.table.directDamage": "Direct",
+  "live.table.summonDamage": "Summons",
   "live.table.classIconAlt": "Class icon",
   "live.player.unknownClass": "Unknown class",
   "live.empty.noBoss": "No Boss",
+  "live.buffUptime.buff": "Buff",
+  "live.buffUptime.uptime": "Uptime",
+  "live.buffUptime.activeUptime": "Active uptime",
+  "live.buffUptime.averageLayer": "Avg stacks",
+  "live.buffUptime.empty": "No monitored buffs were up in this encounter",
   "live.notifications.encounterReset": "Encounter records reset",
   "live.notifications.encounterPaused": "Encounter paused",
   "live.notifications.encounterResumed": "Encounter resumed",
+  "live.notifications.packetDrops":
+    "Capture queue overloaded: {count} packets dropped, numbers may be incomplete",
+  "live.notifications.captureDegraded": "Capture interrupted, retrying: {reason}",
+  "live.notifications.captureFailed": "Capture stopped: {reason}",
+  "live.notifications.captureRecovered": "Capture resumed",
+  "live.notifications.trainingDummyFailed":
+    "Could not start training dummy mode: {reason}",
+  "live.notifications.captureLossy":
+    "Capture is lossy: {gaps} TCP gaps skipped, {failures} packets failed to decode",
   "history.list.error.deleteFailed": "Delete failed: {error}",
   "history.list.error.loadFailed": "Failed to load encounter list: {error}",
   "history.list.filters.favorite": "Favorites",
@@ -382,6 +400,9 @@ export const enUSMessages = {
   "history.detail.table.skill": "Skill",
   "history.detail.table.classIconAlt": "Class icon",
   "history.detail.player.you": " (You)",
+  "history.detail.player.deadFor": "dead {seconds}s",
+  "history.detail.player.deadForTooltip":
+    "Dead for {seconds}s in this encounter ({count} deaths)",
   "history.detail.player.unknownClass": "Unknown class",
   "history.detail.player.label": "Player:",
   "history.detail.skills.title": "Skill Details",
@@ -409,6 +430,11 @@ export const enUSMessages = {
     "Diagnostics bundle created: {path}",
   "settings.debug.toast.diagnosticsCreateFailed":
     "Failed to create diagnostics bundle: {error}",
+  "settings.debug.toast.meterDataReloaded": "Reloaded {count} data tables",
+  "settings.debug.toast.meterDataReloadErrors":
+    "Some data tables failed to load: {files}",
+  "settings.debug.toast.meterDataReloadFailed":
+    "Failed to reload data tables: {error}",
   "settings.controls.selectPlaceholder": "Select an option",
   "settings.controls.fileLoaded": "File loaded",
   "settings.controls.fileClear": "Clear",
@@ -491,12 +517,22 @@ export const enUSMessages = {
   "settings.live.refreshRate": "Refresh Rate",
   "settings.live.refreshRateDescription":
     "Live stats refresh interval (50-2000ms). Lower values feel smoother but use more CPU.",
+  "settings.live.phaseHpThresholds": "Phase HP Thresholds",
+  "settings.live.phaseHpThresholdsDescription":
+    "Boss HP percentages, separated by commas, that start a new encounter phase. Objective changes always start a new phase.",
+  "settings.live.rollingWindows": "Rolling DPS Windows",
+  "settings.live.rollingWindowsDescription":
+    "Window lengths in seconds (1-60), separated by commas, for recent DPS, HPS and damage taken per second.",
   "settings.live.trainingDummy.title": "Training Dummy Mode",
-  "settings.live.trainingDummy.defaultTarget": "Default Dummy Target",
-  "settings.live.trainingDummy.defaultTargetDescription":
-    "The header training dummy button uses this default target directly",
-  "settings.live.trainingDummy.eliteEnemy": "Elite Enemy Dummy",
-  "settings.live.trainingDummy.eliteGuardian": "Elite Guardian Dummy",
+  "settings.live.trainingDummy.monsterId": "Dummy Monster ID",
+  "settings.live.trainingDummy.monsterIdDescription":
+    "Target of the header training dummy button. Any monster ID from the monster table works, e.g. 115 (Elite Enemy Dummy) or 122 (Elite Guardian Dummy)",
+  "settings.live.trainingDummy.segmentSecs": "Segment Length (s)",
+  "settings.live.trainingDummy.segmentSecsDescription":
+    "Seconds from the first hit until the next hit starts a new segment (10-3600)",
+  "settings.live.trainingDummy.stopAfterDamageSecs": "Stop After Damage Time (s)",
+  "settings.live.trainingDummy.stopAfterDamageSecsDescription":
+    "End the segment after this many seconds of active damage; 0 turns it off",
   "settings.network.packetCapture": "Packet Capture",
   "settings.network.method": "Capture Method",
   "settings.network.methodDescription":
@@ -531,6 +567,10 @@ export const enUSMessages = {
   "settings.debug.diagnosticsBundleDescription":
     "Create a ZIP with recent logs for support and troubleshooting",
   "settings.debug.createDiagnosticsBundle": "Create Diagnostics Bundle",
+  "settings.debug.meterData": "Game Data Tables",
+  "settings.debug.meterDataDescription":
+    "Reload the meter-data tables. Files in the meter-data folder of the app data directory override the bundled ones.",
+  "settings.debug.reloadMeterData": "Reload Data",
   "settings.headerLayout.rowGap": "Row Gap",
   "settings.headerLayout.rowGapDescription": "Distance between title bar rows",
   "settings.headerLayout.itemGap": "Item Gap",
@@ -260,14 +260,31 @@ export const zhCNMessages = {
   "live.tabs.heal": "HEAL",
   "live.tabs.tanked": "TANKED",
   "live.tabs.death": "DEATH",
+  "live.tabs.buffs": "BUFF",
   "live.table.player": "玩家",
   "live.table.skill": "技能",
+  "live.table.viaSummon": "来自召唤物 {name}",
+  "live.table.directDamage": "直接伤害",
+  "live.table.summonDamage": "召唤物伤害",
   "live.table.classIconAlt": "职业图标",
   "live.player.unknownClass": "未知职业",
   "live.empty.noBoss": "No Boss",
+  "live.buffUptime.buff": "Buff",
+  "live.buffUptime.uptime": "覆盖率",
+  "live.buffUptime.activeUptime": "有效覆盖率",
+  "live.buffUptime.averageLayer": "平均层数",
+  "live.buffUptime.empty": "本场战斗中没有监控的 Buff 生效",
   "live.notifications.encounterReset": "战斗记录已重置",
   "live.notifications.encounterPaused": "战斗记录已暂停",
   "live.notifications.encounterResumed": "战斗记录已恢复",
+  "live.notifications.packetDrops":
+    "抓包队列过载：已丢弃 {count} 个数据包，数据可能不完整",
+  "live.notifications.captureDegraded": "抓包中断，正在重试：{reason}",
+  "live.notifications.captureFailed": "抓包已停止：{reason}",
+  "live.notifications.captureRecovered": "抓包已恢复",
+  "live.notifications.trainingDummyFailed": "无法开启打桩模式：{reason}",
+  "live.notifications.captureLossy":
+    "抓包数据不完整：跳过 {gaps} 处 TCP 缺口，{failures} 个数据包解析失败",
   "history.list.error.deleteFailed": "删除失败：{error}",
   "history.list.error.loadFailed": "加载战斗列表失败：{error}",
   "history.list.filters.favorite": "收藏",
@@ -334,6 +351,9 @@ export const zhCNMessages = {
   "history.detail.table.skill": "技能",
   "history.detail.table.classIconAlt": "职业图标",
   "history.detail.player.you": "（你）",
+  "history.detail.player.deadFor": "死亡 {seconds}秒",
+  "history.detail.player.deadForTooltip":
+    "本场战斗中死亡 {count} 次，共 {seconds} 秒",
   "history.detail.player.unknownClass": "未知职业",
   "history.detail.player.label": "玩家：",
   "history.detail.skills.title": "技能明细",
@@ -357,6 +377,9 @@ export const zhCNMessages = {
     "已创建调试压缩包（路径已复制）：{path}",
   "settings.debug.toast.diagnosticsCreated": "已创建调试压缩包：{path}",
   "settings.debug.toast.diagnosticsCreateFailed": "创建调试压缩包失败：{error}",
+  "settings.debug.toast.meterDataReloaded": "已重新加载 {count} 个数据表",
+  "settings.debug.toast.meterDataReloadErrors": "部分数据表加载失败：{files}",
+  "settings.debug.toast.meterDataReloadFailed": "重新加载数据表失败：{error}",
   "settings.controls.selectPlaceholder": "选择选项",
   "settings.controls.fileLoaded": "文件已加载",
   "settings.controls.fileClear": "清除",
@@ -434,12 +457,22 @@ export const zhCNMessages = {
   "settings.live.refreshRate": "刷新频率",
   "settings.live.refreshRateDescription":
     "实时统计刷新间隔（50-2000ms）。越低越流畅，但更耗 CPU。",
+  "settings.live.phaseHpThresholds": "阶段血量阈值",
+  "settings.live.phaseHpThresholdsDescription":
+    "Boss 血量百分比（逗号分隔），降到阈值以下时开始新的战斗阶段。副本目标变化时也会开始新阶段。",
+  "settings.live.rollingWindows": "滚动秒伤窗口",
+  "settings.live.rollingWindowsDescription":
+    "窗口时长（秒，1-60，逗号分隔），用于统计最近一段时间的秒伤、秒治疗与每秒承伤。",
   "settings.live.trainingDummy.title": "打桩模式",
-  "settings.live.trainingDummy.defaultTarget": "默认木桩目标",
-  "settings.live.trainingDummy.defaultTargetDescription":
-    "头部打桩按钮会直接使用这里的默认目标",
-  "settings.live.trainingDummy.eliteEnemy": "精英敌方木桩",
-  "settings.live.trainingDummy.eliteGuardian": "精英守护木桩",
+  "settings.live.trainingDummy.monsterId": "木桩怪物 ID",
+  "settings.live.trainingDummy.monsterIdDescription":
+    "头部打桩按钮使用的目标，可填写怪物表中的任意怪物 ID，例如 115（精英敌方木桩）或 122（精英守护木桩）",
+  "settings.live.trainingDummy.segmentSecs": "分段时长（秒）",
+  "settings.live.trainingDummy.segmentSecsDescription":
+    "从首次命中起计时，到时后的下一次命中开始新分段（10-3600）",
+  "settings.live.trainingDummy.stopAfterDamageSecs": "输出时长上限（秒）",
+  "settings.live.trainingDummy.stopAfterDamageSecsDescription":
+    "有效输出时间达到该秒数后结束分段；0 为关闭",
   "settings.network.packetCapture": "抓包",
   "settings.network.method": "捕获方式",
   "settings.network.methodDescription":
@@ -472,6 +505,10 @@ export const zhCNMessages = {
   "settings.debug.diagnosticsBundleDescription":
     "生成包含最近日志的 ZIP，便于支持与排查",
   "settings.debug.createDiagnosticsBundle": "创建调试压缩包",
+  "settings.debug.meterData": "游戏数据表",
+  "settings.debug.meterDataDescription":
+    "重新加载 meter-data 数据表。应用数据目录下 meter-data 文件夹中的文件会覆盖内置数据。",
+  "settings.debug.reloadMeterData": "重新加载数据",
   "settings.headerLayout.rowGap": "行间距",
   "settings.headerLayout.rowGapDescription": "标题栏多行之间的距离",
   "settings.headerLayout.itemGap": "组件间距",
@@ -16,6 +16,24 @@ function uniqueSortedNumbers(values: number[]): number[] {
   return Array.from(new Set(values)).sort((a, b) => a - b);
 }
 
+/** Parses a list like "75, 50, 25" into whole percentages between 1 and 99. */
+function parsePhaseHpThresholds(value: string): number[] {
+  const percents = value
+    .split(/[,，\s]+/)
+    .map((part) => Number(part))
+    .filter((n) => Number.isInteger(n) && n >= 1 && n <= 99);
+  return uniqueSortedNumbers(percents).reverse();
+}
+
+/** Parses a list like "5, 15, 30" into whole seconds between 1 and 60. */
+function parseRollingWindowsSecs(value: string): number[] {
+  const secs = value
+    .split(/[,，\s]+/)
+    .map((part) => Number(part))
+    .filter((n) => Number.isInteger(n) && n >= 1 && n <= 60);
+  return uniqueSortedNumbers(secs);
+}
+
 function normalizeCounterRules(rules: CounterRule[]): CounterRule[] {
   const deduped = new Map<number, CounterRule>();
   for (const rule of rules) {
@@ -170,6 +188,13 @@ export function buildMonitorRuntimeSnapshot(): MonitorRuntimeSnapshot {
   return {
     live: {
       eventUpdateRateMs: SETTINGS.live.general.state.eventUpdateRateMs,
+      phaseHpThresholds: parsePhaseHpThresholds(
+        SETTINGS.live.general.state.phaseHpThresholds,
+      ),
+      rollingWindowsSecs: parseRollingWindowsSecs(
+        SETTINGS.live.general.state.rollingWindowsSecs,
+      ),
+      locale: SETTINGS.i18n.state.locale,
     },
     skill: buildSkillRuntimeSnapshot(),
     monster: buildMonsterRuntimeSnapshot(),
@@ -864,6 +864,8 @@ const DEFAULT_GENERAL_SETTINGS = {
   abbreviationStyle: "western" as "western" | "cn",
   abbreviatedDecimalPlaces: 1,
   eventUpdateRateMs: 200,
+  phaseHpThresholds: "75, 50, 25",
+  rollingWindowsSecs: "5, 15, 30",
 };
 
 export const DEFAULT_CLASS_COLORS: Record<string, string> = {
@@ -1209,7 +1211,10 @@ const DEFAULT_SETTINGS = {
   },
   monsterMonitor: createDefaultMonsterMonitorConfig(),
   trainingDummy: {
-    defaultMonsterId: 122 as 115 | 122,
+    defaultMonsterId: 122,
+    segmentSecs: 183,
+    /** 0 turns the damage time limit off. */
+    stopAfterDamageSecs: 0,
   },
   live: {
     general: { ...DEFAULT_GENERAL_SETTINGS },
@@ -1,8 +1,14 @@
-import type { DeathRecord, LiveDataPayload, TrainingDummyState } from "$lib/api";
+import type {
+  BuffUptimeEntry,
+  DeathRecord,
+  LiveDataPayload,
+  TrainingDummyState,
+} from "$lib/api";
 
 let liveData = $state<LiveDataPayload | null>(null);
 let trainingDummyState = $state<TrainingDummyState | null>(null);
 let deathRecords = $state<DeathRecord[]>([]);
+let buffUptimeEntries = $state<BuffUptimeEntry[]>([]);
 
 export function setLiveData(data: LiveDataPayload) {
   liveData = data;
@@ -37,6 +43,22 @@ export function clearDeathRecords() {
   deathRecords = [];
 }
 
+/**
+ * Replace the local player's buff uptime for the current encounter.
+ * Each update carries the full list of monitored buffs, longest uptime first.
+ */
+export function setBuffUptime(entries: BuffUptimeEntry[]) {
+  buffUptimeEntries = entries ?? [];
+}
+
+export function getBuffUptime() {
+  return buffUptimeEntries;
+}
+
+export function clearBuffUptime() {
+  buffUptimeEntries = [];
+}
+
 export function clearLiveData() {
   liveData = null;
 }
@@ -48,10 +70,12 @@ export function clearTrainingDummyState() {
 export function clearMeterData() {
   clearLiveData();
   clearDeathRecords();
+  clearBuffUptime();
 }
 
 export function cleanupStores() {
   clearLiveData();
   clearTrainingDummyState();
   clearDeathRecords();
+  clearBuffUptime();
 }